@@ -1,17 +1,19 @@
 //! The `address` module defines the library data standard for a valid address, and provides
 //! implementation blocks to convert data from import types to the valid address format.
 use crate::{
-    AddressError, AddressErrorKind, AddressMatch, AddressStatus, Builder, Cartesian, Decode,
-    FireInspections, Geographic, IntoBin, IntoCsv, Io, LexisNexis, Mismatch, Parse,
+    AddressComponent, AddressError, AddressErrorKind, AddressMatch, AddressStatus, Builder,
+    Cartesian, Decode, DuplicateGroup, DuplicateGroups, FireInspections, Geographic, IntoBin,
+    IntoCsv, Io, LexisNexis, MatchPolicy, MatchRule, NearDuplicates, Parse, PhoneticGroups,
     PostalCommunity, State, StreetNamePostType, StreetNamePreDirectional, StreetNamePreModifier,
-    StreetNamePreType, StreetSeparator, SubaddressType, from_bin, from_csv, to_bin, to_csv,
+    StreetNamePreType, StreetSeparator, SubaddressType, from_bin, from_csv, metaphone, to_bin,
+    to_csv,
 };
 use derive_more::{Deref, DerefMut};
-use indicatif::ProgressBar;
 use nom::bytes::complete::tag;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::ops;
+use strum::IntoEnumIterator;
 use tracing::{error, info, trace};
 
 /// The `Address` trait enables the data to function as well-formed address.  The methods of the
@@ -89,49 +91,41 @@ pub trait Address {
     fn status(&self) -> &AddressStatus;
     /// The `status_mut` method returns a mutable reference to the value of the address status.
     fn status_mut(&mut self) -> &mut AddressStatus;
+    /// The `identifier` method returns the native identifier of the record in its source data,
+    /// such as the GlobalID of a Grants Pass address or the taxlot of a county address.  Returns
+    /// `None` for address types that do not carry an identifier.
+    fn identifier(&self) -> Option<String> {
+        None
+    }
 
     /// An address is coincident when the `other` address refers to the same assignment or
     /// location.  If the addresses are coincident, but details (such as the floor number or
-    /// address status) differ, then the differences are recorded as a vector of type [`Mismatch`].
-    /// The results are converted to type [`AddressMatch`].
+    /// address status) differ, then the differences are recorded as a vector of type [`crate::Mismatch`].
+    /// The results are converted to type [`AddressMatch`].  Uses the default [`MatchPolicy`], see
+    /// [`Address::coincident_with`].
     #[tracing::instrument(skip_all)]
     fn coincident<T: Address>(&self, other: &T) -> AddressMatch {
-        let mut coincident = false;
+        self.coincident_with(other, &MatchPolicy::default())
+    }
+
+    /// The `coincident_with` method compares the address to `other` under the provided `policy`.
+    /// The addresses are coincident when every component with a [`MatchRule::Required`] rule
+    /// agrees, and differences in components with a [`MatchRule::Mismatch`] rule are recorded as
+    /// a vector of type [`crate::Mismatch`].
+    #[tracing::instrument(skip_all)]
+    fn coincident_with<T: Address>(&self, other: &T, policy: &MatchPolicy) -> AddressMatch {
         let mut mismatches = Vec::new();
-        if self.number() == other.number()
-            && self.number_suffix() == other.number_suffix()
-            && self.directional() == other.directional()
-            && self.street_name_pre_modifier() == other.street_name_pre_modifier()
-            && self.street_name_pre_type() == other.street_name_pre_type()
-            && self.street_name_separator() == other.street_name_separator()
-            && self.street_name() == other.street_name()
-            && self.street_type() == other.street_type()
-            && self.subaddress_id() == other.subaddress_id()
-            && self.zip() == other.zip()
-            && self.postal_community() == other.postal_community()
-            && self.state() == other.state()
-        {
-            coincident = true;
-            if self.subaddress_type() != other.subaddress_type() {
-                mismatches.push(Mismatch::subaddress_type(
-                    *self.subaddress_type(),
-                    *other.subaddress_type(),
-                ));
-            }
-            if self.floor() != other.floor() {
-                mismatches.push(Mismatch::floor(*self.floor(), *other.floor()));
+        for component in AddressComponent::iter() {
+            if !component.differs(self, other) {
+                continue;
             }
-            if self.building() != other.building() {
-                mismatches.push(Mismatch::building(
-                    self.building().clone(),
-                    other.building().clone(),
-                ));
-            }
-            if self.status() != other.status() {
-                mismatches.push(Mismatch::status(*self.status(), *other.status()));
+            match policy.rule(component) {
+                MatchRule::Required => return AddressMatch::new(false, Vec::new()),
+                MatchRule::Mismatch => mismatches.push(component.mismatch(self, other)),
+                MatchRule::Ignored => {}
             }
         }
-        AddressMatch::new(coincident, mismatches)
+        AddressMatch::new(true, mismatches)
     }
 
     /// Returns a String representing the address label, consisting of the complete address number,
@@ -241,6 +235,17 @@ pub trait Address {
         name
     }
 
+    /// The `phonetic_street_name` method returns the [`metaphone`] code of the street name.
+    fn phonetic_street_name(&self) -> String {
+        metaphone(self.street_name())
+    }
+
+    /// The `phonetic_common_street_name` method returns the [`metaphone`] code of the common
+    /// street name, as returned by [`Address::common_street_name`].
+    fn phonetic_common_street_name(&self) -> String {
+        metaphone(&self.common_street_name())
+    }
+
     /// The `complete_address_number` method returns the address number and address number suffix,
     /// if any, as a String.
     #[tracing::instrument(skip_all)]
@@ -398,33 +403,59 @@ where
     /// include "duplicate", which retains addresses that contain a duplicate in the set.
     #[tracing::instrument(skip_all)]
     fn filter(&self, filter: &str) -> Vec<T> {
-        let mut records = Vec::new();
-        // let values = self.values();
         match filter {
-            "duplicate" => {
-                let style = indicatif::ProgressStyle::with_template(
-            "[{elapsed_precise}] {bar:40.cyan/blue} {pos:>7}/{len:7} {'Checking for duplicate addresses.'}",
-        )
-        .unwrap();
-                let mut seen = HashSet::new();
-                let bar = ProgressBar::new(self.len() as u64);
-                bar.set_style(style);
-                for address in self.iter() {
-                    let label = address.label();
-                    if !seen.contains(&label) {
-                        seen.insert(label.clone());
-                        let mut same = self.clone();
-                        same.filter_field("label", &label);
-                        if same.len() > 1 {
-                            records.append(&mut same);
-                        }
-                    }
-                    bar.inc(1);
+            "duplicate" => self.duplicates().records(),
+            _ => {
+                error!("Invalid filter provided.");
+                Vec::new()
+            }
+        }
+    }
+
+    /// The `duplicates` method groups addresses that share the same label in a single pass over
+    /// the set, returning each group with more than one member as a [`DuplicateGroup`].  Groups
+    /// are numbered in order of first appearance.
+    #[tracing::instrument(skip_all)]
+    fn duplicates(&self) -> DuplicateGroups<T> {
+        let mut index: HashMap<String, usize> = HashMap::new();
+        let mut groups: Vec<Vec<T>> = Vec::new();
+        for address in self.iter() {
+            let label = address.label();
+            match index.get(&label) {
+                Some(&position) => groups[position].push(address.clone()),
+                None => {
+                    index.insert(label, groups.len());
+                    groups.push(vec![address.clone()]);
                 }
             }
-            _ => error!("Invalid filter provided."),
         }
-        records
+        let groups = groups
+            .into_iter()
+            .filter(|group| group.len() > 1)
+            .enumerate()
+            .map(|(i, group)| DuplicateGroup::new(i + 1, group))
+            .collect::<Vec<DuplicateGroup<T>>>();
+        DuplicateGroups::new(groups)
+    }
+
+    /// The `near_duplicates` method pairs addresses that do not share a label, but have a
+    /// [`crate::SimilarityScore`] of at least `threshold`, such as a typo in the street name or a
+    /// number suffix recorded in place of a unit.  See [`NearDuplicates::from_addresses`].
+    #[tracing::instrument(skip_all)]
+    fn near_duplicates(&self, threshold: f64) -> NearDuplicates<T> {
+        NearDuplicates::from_addresses(self, threshold)
+    }
+
+    /// The `phonetic_groups` method groups the distinct common street names in the set by their
+    /// phonetic code, so that street names that sound alike (e.g. "ASHLEY" and "ASHLEE") can be
+    /// reviewed together.
+    #[tracing::instrument(skip_all)]
+    fn phonetic_groups(&self) -> PhoneticGroups {
+        let names = self
+            .iter()
+            .map(|address| address.common_street_name())
+            .collect::<HashSet<String>>();
+        PhoneticGroups::from_names(names)
     }
 
     /// The `filter_field` method returns the subset of addresses where the field `filter` is equal
@@ -1268,6 +1299,30 @@ impl PartialAddresses {
     }
 }
 
+impl<T: Address> From<&T> for PartialAddress {
+    fn from(address: &T) -> Self {
+        Self {
+            address_number: Some(address.number()),
+            address_number_suffix: address.number_suffix().clone(),
+            street_name_pre_directional: *address.directional(),
+            pre_modifier: *address.street_name_pre_modifier(),
+            pre_type: *address.street_name_pre_type(),
+            separator: *address.street_name_separator(),
+            street_name: Some(address.street_name().clone()),
+            street_name_post_type: *address.street_type(),
+            subaddress_type: *address.subaddress_type(),
+            subaddress_identifier: address.subaddress_id().clone(),
+            floor: *address.floor(),
+            building: address.building().clone(),
+            zip_code: Some(address.zip()),
+            postal_community: PostalCommunity::iter()
+                .find(|community| &community.label() == address.postal_community()),
+            state_name: Some(*address.state()),
+            status: Some(*address.status()),
+        }
+    }
+}
+
 impl From<Vec<PartialAddress>> for PartialAddresses {
     fn from(records: Vec<PartialAddress>) -> Self {
         PartialAddresses(records)
@@ -1,17 +1,50 @@
 //! The `business` module matches addresses associated with business licenses against a set of known [`Addresses`], producing a record of
 //! matching, divergent and missing addresses.
 use crate::{
-    Address, AddressErrorKind, Geographic, IntoCsv, Io, MatchStatus, Nom, Parse,
-    StreetNamePostType, StreetNamePreDirectional, deserialize_phone_number, from_csv, to_csv,
+    Address, AddressErrorKind, Addressable, Boundary, BoundaryPoint, Geographic, IntoCsv, Io,
+    MatchPartialRecord, MatchPolicy, MatchStatus, Naics, Nom, Parse, PartialAddress,
+    PostalCommunity, Rejections, State, StreetNamePostType, StreetNamePreDirectional,
+    deserialize_date, deserialize_phone_number, from_csv, from_csv_with, record_id,
+    record_identity, to_csv,
 };
+use chrono::NaiveDate;
 use derive_more::{Deref, DerefMut};
 // use galileo::galileo_types::geo::GeoPoint;
 use indicatif::ParallelProgressIterator;
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
+use strum::IntoEnumIterator;
 use tracing::info;
 
+/// The `Jurisdiction` enum records whether a business lies inside or outside city limits, which
+/// determines tax and inspection responsibility.  Reads the values "Inside City Limits" and
+/// "Outside City Limits" of the `Location` column in the EnerGov export.
+#[derive(
+    Debug,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    strum::Display,
+    strum::EnumString,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum Jurisdiction {
+    /// The business lies inside city limits.
+    #[serde(alias = "Inside City Limits")]
+    Inside,
+    /// The business lies outside city limits.
+    #[serde(alias = "Outside City Limits")]
+    Outside,
+}
+
 /// The `BusinessMatchRecord` struct holds match data for a licensed business.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, PartialOrd)]
 pub struct BusinessMatchRecord {
@@ -22,15 +55,32 @@ pub struct BusinessMatchRecord {
     business_type: String,
     dba: Option<String>,
     license: String,
-    expires: String,
+    #[serde(default, deserialize_with = "deserialize_date")]
+    opened: Option<NaiveDate>,
+    #[serde(default, deserialize_with = "deserialize_date")]
+    issued: Option<NaiveDate>,
+    #[serde(default, deserialize_with = "deserialize_date")]
+    expires: Option<NaiveDate>,
     industry_code: i64,
     community: String,
+    #[serde(default, deserialize_with = "csv::invalid_option")]
+    location: Option<Jurisdiction>,
+    #[serde(default, deserialize_with = "csv::invalid_option")]
+    jurisdiction: Option<Jurisdiction>,
     other_address_label: Option<String>,
     address_latitude: Option<f64>,
     address_longitude: Option<f64>,
+    #[serde(default)]
+    id: uuid::Uuid,
 }
 
 impl BusinessMatchRecord {
+    /// The `match_status` field represents the [`MatchStatus`] of the address associated with
+    /// the active business license.
+    pub fn match_status(&self) -> MatchStatus {
+        self.match_status.clone()
+    }
+
     /// The `company_name` field represents the registered business name associated with the
     /// active business license.
     pub fn company_name(&self) -> Option<String> {
@@ -73,6 +123,62 @@ impl BusinessMatchRecord {
         self.industry_code
     }
 
+    /// The `naics` method returns the NAICS sector and subsector of the industry code, if
+    /// recognized.
+    pub fn naics(&self) -> Option<Naics> {
+        Naics::lookup(self.industry_code)
+    }
+
+    /// The `opened` method returns the date the business opened, if recorded.
+    pub fn opened(&self) -> Option<NaiveDate> {
+        self.opened
+    }
+
+    /// The `issued` method returns the date the license was issued, if recorded.
+    pub fn issued(&self) -> Option<NaiveDate> {
+        self.issued
+    }
+
+    /// The `expires` method returns the expiration date of the license, if recorded.
+    pub fn expires(&self) -> Option<NaiveDate> {
+        self.expires
+    }
+
+    /// The `is_expired` method returns true if the license expired before `date`.
+    pub fn is_expired(&self, date: NaiveDate) -> bool {
+        expired(self.expires, date)
+    }
+
+    /// The `is_expiring` method returns true if the license has not expired on `date`, and
+    /// expires within `days` days of `date`.
+    pub fn is_expiring(&self, date: NaiveDate, days: u64) -> bool {
+        expiring(self.expires, date, days)
+    }
+
+    /// The `opened_between` method returns true if the business opened on or after `start` and
+    /// on or before `end`.
+    pub fn opened_between(&self, start: NaiveDate, end: NaiveDate) -> bool {
+        between(self.opened, start, end)
+    }
+
+    /// The `location` method returns the jurisdiction claimed on the license, if recorded.
+    pub fn location(&self) -> Option<Jurisdiction> {
+        self.location
+    }
+
+    /// The `jurisdiction` method returns the jurisdiction of the matching address, if checked.
+    /// See [`BusinessMatchRecords::check_boundary`] and
+    /// [`BusinessMatchRecords::check_addresses`].
+    pub fn jurisdiction(&self) -> Option<Jurisdiction> {
+        self.jurisdiction
+    }
+
+    /// The `jurisdiction_conflict` method returns true if the jurisdiction claimed on the license
+    /// disagrees with the jurisdiction of the matching address.
+    pub fn jurisdiction_conflict(&self) -> bool {
+        matches!((self.location, self.jurisdiction), (Some(a), Some(b)) if a != b)
+    }
+
     /// The `latitude` method returns the latitude of the address for the
     /// active business license.
     pub fn latitude(&self) -> Option<f64> {
@@ -84,6 +190,12 @@ impl BusinessMatchRecord {
     pub fn longitude(&self) -> Option<f64> {
         self.address_longitude
     }
+
+    /// The `id` method returns the unique id of the match record, derived from the license, the
+    /// match status and the matching address.
+    pub fn id(&self) -> uuid::Uuid {
+        self.id
+    }
 }
 
 /// The `BusinessMatchRecords` struct holds a vector of [`BusinessMatchRecord`] objects.
@@ -92,31 +204,26 @@ pub struct BusinessMatchRecords(Vec<BusinessMatchRecord>);
 
 impl BusinessMatchRecords {
     /// Matches the provided address associated with a business license against the addresses in
-    /// `addresses`, creating a new `BusinessMatchRecords` struct containing the results.
+    /// `addresses`, creating a new `BusinessMatchRecords` struct containing the results.  Uses the
+    /// [`MatchPolicy::partial`] policy.
     pub fn new<T: Address + Geographic>(business: &BusinessLicense, addresses: &[T]) -> Self {
-        let mut records = Vec::new();
-        for address in addresses {
-            let business_match = business.coincident(address);
-            if let Some(record) = business_match {
-                records.push(record);
-            }
-        }
+        Self::new_with_policy(business, addresses, &MatchPolicy::partial())
+    }
+
+    /// Matches the address of `business` against the addresses in `addresses` as in
+    /// [`BusinessMatchRecords::new`], using `policy` to determine which components must agree.
+    pub fn new_with_policy<T: Address + Geographic>(
+        business: &BusinessLicense,
+        addresses: &[T],
+        policy: &MatchPolicy,
+    ) -> Self {
+        let partial = business.partial_address();
+        let mut records = addresses
+            .iter()
+            .filter_map(|address| business.coincident_partial(&partial, address, policy))
+            .collect::<Vec<BusinessMatchRecord>>();
         if records.is_empty() {
-            records.push(BusinessMatchRecord {
-                match_status: MatchStatus::Missing,
-                business_address_label: business.label(),
-                company_name: business.company_name(),
-                contact_name: business.contact_name(),
-                business_type: business.business_type(),
-                dba: business.dba(),
-                license: business.license(),
-                expires: business.expires(),
-                industry_code: business.industry_code(),
-                community: business.community(),
-                other_address_label: None,
-                address_latitude: None,
-                address_longitude: None,
-            });
+            records.push(business.match_record::<T>(&partial, MatchStatus::Missing, None));
         }
         let business_record = BusinessMatchRecords(records);
         let matched = business_record.clone().filter("matching");
@@ -139,12 +246,22 @@ impl BusinessMatchRecords {
     pub fn chain<T: Address + Geographic>(
         business: &BusinessLicense,
         address_list: &[&[T]],
+    ) -> Self {
+        Self::chain_with_policy(business, address_list, &MatchPolicy::partial())
+    }
+
+    /// Compares the address of `business` against the addresses in `address_list` as in
+    /// [`BusinessMatchRecords::chain`], using `policy` to determine which components must agree.
+    pub fn chain_with_policy<T: Address + Geographic>(
+        business: &BusinessLicense,
+        address_list: &[&[T]],
+        policy: &MatchPolicy,
     ) -> Self {
         let mut matching = Vec::new();
         let mut divergent = Vec::new();
         let mut missing = Vec::new();
         for addresses in address_list {
-            let record = BusinessMatchRecords::new(business, addresses);
+            let record = BusinessMatchRecords::new_with_policy(business, addresses, policy);
             let matched = record.clone().filter("matching");
             let diverged = record.clone().filter("divergent");
             let missed = record.clone().filter("missing");
@@ -171,6 +288,16 @@ impl BusinessMatchRecords {
     pub fn compare<T: Address + Geographic + Send + Sync>(
         businesses: &BusinessLicenses,
         addresses: &[T],
+    ) -> Self {
+        Self::compare_with_policy(businesses, addresses, &MatchPolicy::partial())
+    }
+
+    /// Compares each license in `businesses` as in [`BusinessMatchRecords::compare`], using
+    /// `policy` to determine which components must agree.
+    pub fn compare_with_policy<T: Address + Geographic + Send + Sync>(
+        businesses: &BusinessLicenses,
+        addresses: &[T],
+        policy: &MatchPolicy,
     ) -> Self {
         let style = indicatif::ProgressStyle::with_template(
             "[{elapsed_precise}] {bar:40.cyan/blue} {pos:>7}/{len:7} {'Comparing addresses.'}",
@@ -178,7 +305,7 @@ impl BusinessMatchRecords {
         .unwrap();
         let record = businesses
             .par_iter()
-            .map(|address| BusinessMatchRecords::new(address, addresses))
+            .map(|address| BusinessMatchRecords::new_with_policy(address, addresses, policy))
             .progress_with_style(style)
             .collect::<Vec<BusinessMatchRecords>>();
         let mut records = Vec::new();
@@ -194,6 +321,16 @@ impl BusinessMatchRecords {
     pub fn compare_chain<T: Address + Geographic + Send + Sync>(
         businesses: &BusinessLicenses,
         addresses: &[&[T]],
+    ) -> Self {
+        Self::compare_chain_with_policy(businesses, addresses, &MatchPolicy::partial())
+    }
+
+    /// Compares each license in `businesses` as in [`BusinessMatchRecords::compare_chain`],
+    /// using `policy` to determine which components must agree.
+    pub fn compare_chain_with_policy<T: Address + Geographic + Send + Sync>(
+        businesses: &BusinessLicenses,
+        addresses: &[&[T]],
+        policy: &MatchPolicy,
     ) -> Self {
         let style = indicatif::ProgressStyle::with_template(
             "[{elapsed_precise}] {bar:40.cyan/blue} {pos:>7}/{len:7} {'Comparing addresses.'}",
@@ -201,7 +338,7 @@ impl BusinessMatchRecords {
         .unwrap();
         let record = businesses
             .par_iter()
-            .map(|address| BusinessMatchRecords::chain(address, addresses))
+            .map(|address| BusinessMatchRecords::chain_with_policy(address, addresses, policy))
             .progress_with_style(style)
             .collect::<Vec<BusinessMatchRecords>>();
         let mut records = Vec::new();
@@ -216,7 +353,8 @@ impl BusinessMatchRecords {
     /// "missing", "nonmissing", "divergent", "matching", "unique" and "multiple". The "unique"
     /// option returns records where the business name is unique.  The "multiple" options returns
     /// records where multiple licenses exist registered under the same business name. The "local"
-    /// option returns records within Grants Pass or Merlin.
+    /// option returns records within Grants Pass or Merlin.  The "jurisdiction" option returns
+    /// records where the jurisdiction claimed on the license disagrees with the matching address.
     ///
     /// As a filter, the method must either copy the data in Self to create a subset using the
     /// filter, or it must mutate the data of Self in place.  Here we take ownership of Self and
@@ -262,6 +400,7 @@ impl BusinessMatchRecords {
                 self.0 = records;
             }
             "local" => self.retain(|r| r.community == "GRANTS PASS" || r.community == "MERLIN"),
+            "jurisdiction" => self.retain(|r| r.jurisdiction_conflict()),
             _ => info!("Invalid filter provided."),
         }
         self
@@ -281,6 +420,80 @@ impl BusinessMatchRecords {
         self
     }
 
+    /// The `check_boundary` method sets the jurisdiction of each record with a matching address
+    /// to [`Jurisdiction::Inside`] if the address point lies within `boundary`, such as the city
+    /// limits polygon, and [`Jurisdiction::Outside`] otherwise.
+    pub fn check_boundary(&mut self, boundary: &Boundary) {
+        for record in self.iter_mut() {
+            if let (Some(longitude), Some(latitude)) =
+                (record.address_longitude, record.address_latitude)
+            {
+                let point = BoundaryPoint {
+                    longitude,
+                    latitude,
+                };
+                record.jurisdiction = Some(if boundary.contains(&point) {
+                    Jurisdiction::Inside
+                } else {
+                    Jurisdiction::Outside
+                });
+            }
+        }
+        info!("{} jurisdiction conflicts.", self.jurisdiction_conflicts());
+    }
+
+    /// The `check_addresses` method sets the jurisdiction of each record with a matching address
+    /// to [`Jurisdiction::Inside`] if the address is in the city address set `city`, and
+    /// [`Jurisdiction::Outside`] otherwise, such as an address only in the county address set.
+    pub fn check_addresses<T: Address>(&mut self, city: &[T]) {
+        let labels = city
+            .iter()
+            .map(|address| address.label())
+            .collect::<HashSet<String>>();
+        for record in self.iter_mut() {
+            if let Some(label) = &record.other_address_label {
+                record.jurisdiction = Some(if labels.contains(label) {
+                    Jurisdiction::Inside
+                } else {
+                    Jurisdiction::Outside
+                });
+            }
+        }
+        info!("{} jurisdiction conflicts.", self.jurisdiction_conflicts());
+    }
+
+    /// The `jurisdiction_conflicts` method returns the number of licenses with a jurisdiction
+    /// conflict.  A license can match several addresses, such as each unit in a building, so the
+    /// count is taken once per license rather than once per record.
+    pub fn jurisdiction_conflicts(&self) -> usize {
+        self.iter()
+            .filter(|r| r.jurisdiction_conflict())
+            .map(|r| r.license())
+            .collect::<HashSet<String>>()
+            .len()
+    }
+
+    /// The `filter_expired` method returns the subset of records for licenses that expired before
+    /// `date`.
+    pub fn filter_expired(mut self, date: NaiveDate) -> Self {
+        self.retain(|r| r.is_expired(date));
+        self
+    }
+
+    /// The `filter_expiring` method returns the subset of records for licenses that have not
+    /// expired on `date`, and expire within `days` days of `date`.
+    pub fn filter_expiring(mut self, date: NaiveDate, days: u64) -> Self {
+        self.retain(|r| r.is_expiring(date, days));
+        self
+    }
+
+    /// The `filter_opened` method returns the subset of records for businesses that opened on or
+    /// after `start` and on or before `end`.
+    pub fn filter_opened(mut self, start: NaiveDate, end: NaiveDate) -> Self {
+        self.retain(|r| r.opened_between(start, end));
+        self
+    }
+
     // /// Writes the contents of `BusinessMatchRecords` to a CSV file at location `title`.  Each element in
     // /// the vector of type [`BusinessMatchRecord`] maps to a row of data on the CSV.
     // pub fn to_csv(&mut self, title: std::path::PathBuf) -> Result<(), std::io::Error> {
@@ -316,15 +529,36 @@ impl IntoCsv<BusinessMatchRecords> for BusinessMatchRecords {
 pub struct BusinessLicense {
     company_name: Option<String>,
     contact_name: Option<String>,
+    #[serde(
+        rename(deserialize = "Location"),
+        default,
+        deserialize_with = "csv::invalid_option"
+    )]
+    location: Option<Jurisdiction>,
     business_type: String,
     #[serde(rename(deserialize = "dba"))]
     dba: Option<String>,
+    #[serde(
+        rename(deserialize = "OpenedDate"),
+        default,
+        deserialize_with = "deserialize_date"
+    )]
+    opened: Option<NaiveDate>,
     #[serde(deserialize_with = "deserialize_phone_number")]
     business_phone: Option<i64>,
+    #[serde(
+        rename(deserialize = "ISSUEDDATE"),
+        default,
+        deserialize_with = "deserialize_date"
+    )]
+    issued: Option<NaiveDate>,
     #[serde(rename(deserialize = "LICENSENUMBER"))]
     license: String,
-    #[serde(rename(deserialize = "EXPIRATIONDATE"))]
-    expires: String,
+    #[serde(
+        rename(deserialize = "EXPIRATIONDATE"),
+        deserialize_with = "deserialize_date"
+    )]
+    expires: Option<NaiveDate>,
     #[serde(rename = "CodeNumber")]
     industry_code: i64,
     #[serde(rename(deserialize = "ADDRESSLINE1"))]
@@ -336,6 +570,12 @@ pub struct BusinessLicense {
         deserialize_with = "StreetNamePreDirectional::deserialize_mixed"
     )]
     street_name_pre_directional: Option<StreetNamePreDirectional>,
+    #[serde(
+        rename(deserialize = "POSTDIRECTION"),
+        default,
+        deserialize_with = "StreetNamePreDirectional::deserialize_mixed"
+    )]
+    street_name_post_directional: Option<StreetNamePreDirectional>,
     #[serde(
         rename(deserialize = "STREETTYPE"),
         deserialize_with = "StreetNamePostType::deserialize_mixed"
@@ -346,6 +586,12 @@ pub struct BusinessLicense {
         deserialize_with = "csv::invalid_option"
     )]
     subaddress_identifier: Option<String>,
+    #[serde(
+        rename(deserialize = "ADDRESSLINE3"),
+        default,
+        deserialize_with = "csv::invalid_option"
+    )]
+    address_line3: Option<String>,
     #[serde(rename(deserialize = "CITY"))]
     postal_community: String,
     #[serde(rename(deserialize = "STATE"))]
@@ -356,56 +602,80 @@ pub struct BusinessLicense {
 
 impl BusinessLicense {
     /// Compares the address of `BusinessLicense` to `address`, producing either a matching
-    /// [`BusinessMatchRecord`], any divergent [`BusinessMatchRecord`], or `None` if missing.
+    /// [`BusinessMatchRecord`], any divergent [`BusinessMatchRecord`], or `None` if missing.  Uses
+    /// the [`MatchPolicy::partial`] policy.
     pub fn coincident<T: Address + Geographic>(&self, address: &T) -> Option<BusinessMatchRecord> {
-        let mut match_status = MatchStatus::Missing;
-        let mut business_match = None;
-        let mut subaddress_id = None;
-        if let Some(val) = self.subaddress_identifier.clone()
-            && !val.is_empty()
-        {
-            // info!("Subaddress not empty: {}", &val);
-            let trim_val = val.trim();
-            if !trim_val.is_empty() {
-                // info!("Writing subaddress: {}", trim_val);
-                subaddress_id = Some(trim_val.to_string());
-            }
+        self.coincident_with_policy(address, &MatchPolicy::partial())
+    }
+
+    /// Compares the address of `BusinessLicense` to `address` as in
+    /// [`BusinessLicense::coincident`], using `policy` to determine which components must agree.
+    /// The license address is read with [`Addressable::partial_address`] and matched by
+    /// [`MatchPartialRecord::coincident_with_policy`], the same matcher used for fire inspections.
+    pub fn coincident_with_policy<T: Address + Geographic>(
+        &self,
+        address: &T,
+        policy: &MatchPolicy,
+    ) -> Option<BusinessMatchRecord> {
+        self.coincident_partial(&self.partial_address(), address, policy)
+    }
+
+    /// The `coincident_partial` method compares `partial`, the parsed address of the license, to
+    /// `address` as in [`BusinessLicense::coincident_with_policy`], so that callers matching
+    /// against many addresses only parse the license address once.
+    fn coincident_partial<T: Address + Geographic>(
+        &self,
+        partial: &PartialAddress,
+        address: &T,
+        policy: &MatchPolicy,
+    ) -> Option<BusinessMatchRecord> {
+        MatchPartialRecord::coincident_with_policy(partial, address, policy)
+            .map(|record| self.match_record(partial, record.match_status(), Some(address)))
+    }
+
+    /// The `match_record` method creates a [`BusinessMatchRecord`] for the license with address
+    /// `partial` and match status `status`, matched to `address` if any.
+    fn match_record<T: Address + Geographic>(
+        &self,
+        partial: &PartialAddress,
+        status: MatchStatus,
+        address: Option<&T>,
+    ) -> BusinessMatchRecord {
+        let business_address_label = partial.label();
+        let other = address.map(record_identity);
+        let id = self.record_id(&business_address_label, &status, other.as_deref());
+        BusinessMatchRecord {
+            match_status: status,
+            business_address_label,
+            company_name: self.company_name(),
+            contact_name: self.contact_name(),
+            business_type: self.business_type(),
+            dba: self.dba(),
+            license: self.license(),
+            opened: self.opened(),
+            issued: self.issued(),
+            expires: self.expires(),
+            industry_code: self.industry_code(),
+            community: self.community(),
+            location: self.location(),
+            jurisdiction: None,
+            other_address_label: address.map(|address| address.label()),
+            address_latitude: address.map(|address| address.latitude()),
+            address_longitude: address.map(|address| address.longitude()),
+            id,
         }
-        let street_name = self.street_name.trim().to_string();
-        if self.address_number == address.complete_address_number()
-            && self.street_name_pre_directional == *address.directional()
-            && street_name == *address.street_name()
-            && self.street_name_post_type == *address.street_type()
-        // && self.postal_community == address.postal_community()
-        // && self.state_name == address.state_name()
-        {
-            if subaddress_id != *address.subaddress_id() {
-                match_status = MatchStatus::Divergent;
-            }
-            // robust against +4 codes?
-            // if self.zip_code != address.zip() {
-            //     match_status = MatchStatus::Divergent;
-            // }
-            if match_status != MatchStatus::Divergent {
-                match_status = MatchStatus::Matching;
-            }
-            business_match = Some(BusinessMatchRecord {
-                match_status,
-                business_address_label: self.label(),
-                company_name: self.company_name(),
-                contact_name: self.contact_name(),
-                business_type: self.business_type(),
-                dba: self.dba(),
-                license: self.license(),
-                expires: self.expires(),
-                industry_code: self.industry_code(),
-                community: self.community(),
-                other_address_label: Some(address.label()),
-                address_latitude: Some(address.latitude()),
-                address_longitude: Some(address.longitude()),
-            });
+    }
+
+    /// The `record_id` method returns the id of a [`BusinessMatchRecord`] for the license at
+    /// address `label` with match status `status`, matched to the address with identity `other`,
+    /// if any.  See [`crate::record_id`].
+    fn record_id(&self, label: &str, status: &MatchStatus, other: Option<&str>) -> uuid::Uuid {
+        let license = self.license();
+        let mut parts = vec!["business", &license, label, status.as_ref()];
+        if let Some(other) = other {
+            parts.push(other);
         }
-        business_match
+        record_id(&parts)
     }
 
     /// The `community` method returns the postal community name from the `postal_community` field.
@@ -446,10 +716,45 @@ impl BusinessLicense {
         self.license.to_owned()
     }
 
-    /// The `expires` field represents the time of expiration for the active business license.
-    /// This method clones the value of the field.
-    pub fn expires(&self) -> String {
-        self.expires.to_owned()
+    /// The `location` field represents the jurisdiction claimed on the license, from the
+    /// `Location` column of the EnerGov export.
+    pub fn location(&self) -> Option<Jurisdiction> {
+        self.location
+    }
+
+    /// The `opened` field represents the date the business opened, parsed from the
+    /// `OpenedDate` column of the EnerGov export.
+    pub fn opened(&self) -> Option<NaiveDate> {
+        self.opened
+    }
+
+    /// The `issued` field represents the date the license was issued, parsed from the
+    /// `ISSUEDDATE` column of the EnerGov export.
+    pub fn issued(&self) -> Option<NaiveDate> {
+        self.issued
+    }
+
+    /// The `expires` field represents the expiration date of the business license, parsed from
+    /// the `EXPIRATIONDATE` column of the EnerGov export.
+    pub fn expires(&self) -> Option<NaiveDate> {
+        self.expires
+    }
+
+    /// The `is_expired` method returns true if the license expired before `date`.
+    pub fn is_expired(&self, date: NaiveDate) -> bool {
+        expired(self.expires, date)
+    }
+
+    /// The `is_expiring` method returns true if the license has not expired on `date`, and
+    /// expires within `days` days of `date`.
+    pub fn is_expiring(&self, date: NaiveDate, days: u64) -> bool {
+        expiring(self.expires, date, days)
+    }
+
+    /// The `opened_between` method returns true if the business opened on or after `start` and
+    /// on or before `end`.
+    pub fn opened_between(&self, start: NaiveDate, end: NaiveDate) -> bool {
+        between(self.opened, start, end)
     }
 
     /// The `industry_code` method returns the value of the `industry_code` field.
@@ -457,12 +762,25 @@ impl BusinessLicense {
         self.industry_code
     }
 
+    /// The `naics` method returns the NAICS sector and subsector of the industry code, if
+    /// recognized.
+    pub fn naics(&self) -> Option<Naics> {
+        Naics::lookup(self.industry_code)
+    }
+
     /// The `pre_directional` field represents the street pre-directional designation associated
     /// with a business license.  This method clones the value of the field.
     pub fn pre_directional(&self) -> Option<StreetNamePreDirectional> {
         self.street_name_pre_directional
     }
 
+    /// The `post_directional` field represents the street post-directional designation of the
+    /// business address, which EnerGov records separately from the pre-directional.  This method
+    /// returns the cloned value of the field.
+    pub fn post_directional(&self) -> Option<StreetNamePreDirectional> {
+        self.street_name_post_directional
+    }
+
     /// The `post_type` field represents the street post type designation of the business address.
     /// This method returns the cloned value of the field.
     pub fn post_type(&self) -> Option<StreetNamePostType> {
@@ -475,24 +793,10 @@ impl BusinessLicense {
         self.subaddress_identifier.to_owned()
     }
 
-    /// The `label` method creates a string representation of the complete street address
-    /// associated with a business license.
-    fn label(&self) -> String {
-        let street_name = match self.post_type() {
-            Some(post_type) => format!("{} {}", self.street_name, post_type),
-            None => self.street_name.to_string(),
-        };
-        let complete_street_name = match self.street_name_pre_directional {
-            Some(pre_directional) => format!("{} {}", pre_directional.abbreviate(), street_name),
-            None => street_name,
-        };
-        match self.subaddress_identifier() {
-            Some(subaddress) => format!(
-                "{} {} {}",
-                self.address_number, complete_street_name, subaddress
-            ),
-            None => format!("{} {}", self.address_number, complete_street_name),
-        }
+    /// The `address_line3` field holds any additional address information entered for the
+    /// business, such as a building or unit.  This method returns the cloned value of the field.
+    pub fn address_line3(&self) -> Option<String> {
+        self.address_line3.to_owned()
     }
 
     /// EnerGov has a single field for entering a subaddress id, and staff sometimes include the
@@ -524,6 +828,77 @@ impl BusinessLicense {
     }
 }
 
+impl Addressable for BusinessLicense {
+    /// The license records the address number and suffix in ADDRESSLINE1, which is parsed into
+    /// the number and suffix of the partial address.  The remaining columns are composed into an
+    /// address string and read with [`Parse::address`], so that street names holding a pre-type
+    /// or pre-modifier (e.g. "OLD HWY 99") and subaddress types in the unit are recognized, then
+    /// standardized to city naming conventions.  City addresses carry the directional before the
+    /// street name, so a post directional is read as the pre-directional when the latter is
+    /// missing.  Falls back to copying the columns directly if the address fails to parse.
+    fn partial_address(&self) -> PartialAddress {
+        // Staff enter suffixes as "407-B", "239B" or "1650 1/2", so any single word following
+        // the number is read as the suffix.
+        let (number, suffix) = match Parse::address_number(&self.address_number) {
+            Ok((rem, number)) => {
+                let suffix = rem.trim().trim_start_matches('-').trim();
+                let suffix = (!suffix.is_empty() && !suffix.contains(char::is_whitespace))
+                    .then(|| suffix.to_uppercase());
+                (number, suffix)
+            }
+            Err(_) => (None, None),
+        };
+        let directional = self
+            .street_name_pre_directional
+            .or(self.street_name_post_directional);
+        let mut parts = Vec::new();
+        if let Some(number) = number {
+            parts.push(number.to_string());
+        }
+        if let Some(directional) = directional {
+            parts.push(directional.abbreviate());
+        }
+        parts.push(self.street_name.trim().to_uppercase());
+        if let Some(post_type) = self.street_name_post_type {
+            parts.push(post_type.abbreviate());
+        }
+        for line in [&self.subaddress_identifier, &self.address_line3]
+            .into_iter()
+            .flatten()
+        {
+            parts.push(line.trim().to_uppercase());
+        }
+        parts.retain(|part| !part.is_empty());
+        let mut address = match Parse::address(&parts.join(" ")) {
+            Ok((_, parsed)) => parsed,
+            Err(_) => {
+                let mut address = PartialAddress::new();
+                address.address_number = number;
+                address.street_name_pre_directional = directional;
+                address.street_name = Some(self.street_name.trim().to_uppercase());
+                address.street_name_post_type = self.street_name_post_type;
+                address.subaddress_identifier = self
+                    .subaddress_identifier
+                    .as_ref()
+                    .map(|value| value.trim().to_uppercase())
+                    .filter(|value| !value.is_empty());
+                address
+            }
+        };
+        address.set_address_number_suffix(suffix.as_deref());
+        address.zip_code = Some(self.zip_code);
+        let community = self.postal_community.trim().to_uppercase();
+        address.postal_community =
+            PostalCommunity::iter().find(|candidate| candidate.label() == community);
+        let state = self.state_name.trim().to_uppercase();
+        address.state_name = State::iter().find(|candidate| {
+            candidate.abbreviate() == state || candidate.to_string().to_uppercase() == state
+        });
+        address.standardize();
+        address
+    }
+}
+
 /// The `BusinessLicenses` struct holds a `records` field containing a vector of type
 /// [`BusinessLicense`].
 #[derive(Debug, Clone, Deserialize, Serialize, Deref, DerefMut)]
@@ -536,6 +911,15 @@ impl BusinessLicenses {
         Ok(BusinessLicenses(records))
     }
 
+    /// Creates a new `BusinessLicenses` struct from a CSV file located at `path`, along with a
+    /// [`Rejections`] report of the rows that failed to deserialize.
+    pub fn from_csv_with_rejections<P: AsRef<std::path::Path>>(
+        path: P,
+    ) -> Result<(Self, Rejections), Io> {
+        let (records, rejections) = from_csv_with(path, Ok::<_, std::convert::Infallible>)?;
+        Ok((BusinessLicenses(records), rejections))
+    }
+
     /// Returns the subset of `BusinessLicenses` where the value of the `filter` field is equal to
     /// the test value in `field`.  Currently `filter` can take the value `name`, referring to the
     /// company name.
@@ -548,6 +932,26 @@ impl BusinessLicenses {
         self
     }
 
+    /// The `filter_expired` method returns the subset of licenses that expired before `date`.
+    pub fn filter_expired(mut self, date: NaiveDate) -> Self {
+        self.retain(|r| r.is_expired(date));
+        self
+    }
+
+    /// The `filter_expiring` method returns the subset of licenses that have not expired on
+    /// `date`, and expire within `days` days of `date`.
+    pub fn filter_expiring(mut self, date: NaiveDate, days: u64) -> Self {
+        self.retain(|r| r.is_expiring(date, days));
+        self
+    }
+
+    /// The `filter_opened` method returns the subset of licenses for businesses that opened on
+    /// or after `start` and on or before `end`.
+    pub fn filter_opened(mut self, start: NaiveDate, end: NaiveDate) -> Self {
+        self.retain(|r| r.opened_between(start, end));
+        self
+    }
+
     /// Retains one record from each license in `BusinessLicenses`, keeping the first encountered,
     /// intended to remove duplicate licenses from a record.
     pub fn deduplicate(&self) -> Self {
@@ -583,3 +987,22 @@ impl IntoCsv<BusinessLicenses> for BusinessLicenses {
         to_csv(&mut self.0, path.as_ref().into())
     }
 }
+
+/// The `expired` function returns true if the expiration date `expires` is before `date`.
+fn expired(expires: Option<NaiveDate>, date: NaiveDate) -> bool {
+    expires.is_some_and(|expires| expires < date)
+}
+
+/// The `expiring` function returns true if the expiration date `expires` is on or after `date`,
+/// and no more than `days` days after `date`.
+fn expiring(expires: Option<NaiveDate>, date: NaiveDate, days: u64) -> bool {
+    let end = date
+        .checked_add_days(chrono::Days::new(days))
+        .unwrap_or(NaiveDate::MAX);
+    between(expires, date, end)
+}
+
+/// The `between` function returns true if `value` is on or after `start` and on or before `end`.
+fn between(value: Option<NaiveDate>, start: NaiveDate, end: NaiveDate) -> bool {
+    value.is_some_and(|value| start <= value && value <= end)
+}
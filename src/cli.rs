@@ -5,34 +5,47 @@ use clap::Parser;
 #[command(author, version, about, long_about = None)]
 pub struct Cli {
     /// The `command` field specifies the command for the program to run. Currently accepts
-    /// 'compare', 'drift', 'filter', 'save', 'orphan_streets' and 'lexisnexis' as values.
+    /// 'compare', 'drift', 'filter', 'save', 'orphan_streets', 'lexisnexis',
+    /// 'near_duplicates', 'fuzzy', 'phonetic', 'reconcile', 'matrix', 'diff', 'conflate',
+    /// 'correct', 'progress', 'business', 'expiring', 'naics', 'sync', 'fire' and 'locate' as
+    /// values.
     ///
     /// * filter
     ///   * takes [`crate::MatchRecords`] as input
     ///   * takes [`crate::BusinessMatchRecords`] with the `-b` flag
+    ///   * takes [`crate::ReconcileRecords`] with the 'reconcile' source type
+    ///   * takes [`crate::DiffRecords`] with the 'diff' source type
+    ///   * takes [`crate::FireInspectionMatchRecords`] with the 'fire' source type
     #[arg(
         short = 'c',
         long,
-        help = "Command to execute.  Valid commands include 'compare', 'drift', 'filter', 'orphan_streets', 'lexisnexis' and 'save'"
+        help = "Command to execute.  Valid commands include 'compare', 'drift', 'filter', 'orphan_streets', 'lexisnexis', 'near_duplicates', 'fuzzy', 'phonetic', 'reconcile', 'matrix', 'diff', 'conflate', 'correct', 'progress', 'business', 'expiring', 'naics', 'sync', 'fire', 'locate' and 'save'"
     )]
     pub command: String,
     /// The `source` field specifies the path the source address file.
     #[arg(short = 's', long, help = "Path to source addresses.")]
     pub source: std::path::PathBuf,
     /// The `source_type` field contains a designator for the address source.  Currently accepts
-    /// 'grants_pass' and 'josephine_county' as values.
+    /// 'grants_pass' and 'josephine_county' as values.  The 'diff' command also accepts
+    /// 'grants_pass_table', 'josephine_county_table', 'josephine_county_legacy',
+    /// 'josephine_county_legacy_table', 'common', 'business' and 'fire', reading the source and
+    /// target as the old and new versions of the same export.
     #[arg(short = 'k', long, help = "Address format for source.")]
     pub source_type: Option<String>,
     /// The `target` field specifies the path the target address file.
     #[arg(short = 't', long, help = "Path to target addresses.")]
     pub target: Option<std::path::PathBuf>,
     /// The `target_type` field contains a designator for the address target.  Currently accepts
-    /// 'grants_pass' and 'josephine_county' as values.
+    /// 'grants_pass' and 'josephine_county' as values.  The 'correct' command also accepts
+    /// 'josephine_county_legacy', and writes the corrections in the schema of the target type.
     #[arg(short = 'z', long, help = "Address format for target.")]
     pub target_type: Option<String>,
     /// The `filter` field contains a value to filter the target data.  Currently accepts
-    /// `missing`, `divergent`, `matching`, `subaddress`, `floor`, `building` and `status` as
-    /// values.
+    /// `missing`, `divergent`, `matching`, the name of any address component (e.g. `subaddress`,
+    /// `floor`, `building`, `status` or `zip`), the mismatch severities `minor`, `moderate` and
+    /// `major`, and the cardinalities `unmatched`, `one_to_one`, `one_to_many`, `many_to_one`
+    /// and `ambiguous` as values.  Records from the 'diff' command accept `added`, `removed`,
+    /// `modified`, `moved` and the name of any address component.
     #[arg(short = 'f', long, help = "Filter records by value.")]
     pub filter: Option<String>,
     /// The `duplicates` flag instructs the program to search for duplicate addresses.
@@ -69,4 +82,123 @@ pub struct Cli {
     /// accepts 'grants_pass' and 'josephine_county'.
     #[arg(short = 'y', long, help = "Address format for alternate target.")]
     pub alternate_type: Option<String>,
+    /// The `policy` field names a match policy preset ('strict', 'loose' or 'partial'), or the
+    /// path to a csv file with `component` and `rule` columns.  See [`crate::MatchPolicy`].
+    #[arg(long, help = "Match policy preset or path to a policy file.")]
+    pub policy: Option<String>,
+    /// The `threshold` field sets the minimum similarity score, between 0.0 and 1.0, for fuzzy
+    /// address comparisons.
+    #[arg(
+        long,
+        default_value = "0.8",
+        help = "Minimum similarity score for fuzzy matches."
+    )]
+    pub threshold: f64,
+    /// The `candidates` field sets the number of fuzzy candidates to report for each address.
+    #[arg(
+        long,
+        default_value = "3",
+        help = "Number of fuzzy candidates to report per address."
+    )]
+    pub candidates: usize,
+    /// The `fuzzy` flag reports the most similar address for each missing address in the
+    /// 'compare' command.  See [`crate::MatchRecords::compare_fuzzy`].
+    #[arg(
+        long,
+        default_value = "false",
+        default_missing_value = "true",
+        help = "Report fuzzy candidates for missing addresses."
+    )]
+    pub fuzzy: bool,
+    /// The `distance` field sets the maximum distance between address points, in the units of the
+    /// source coordinates, for comparisons that consider proximity.  For the 'compare' command,
+    /// the distance is in meters between geographic coordinates, and matches farther apart are
+    /// reported as displaced.  For the 'diff' command, address points that moved farther than the
+    /// distance in meters are reported as moved, or in the units of the Web Mercator coordinates
+    /// for business points.
+    #[arg(long, help = "Maximum distance between address points.")]
+    pub distance: Option<f64>,
+    /// The `summary` field specifies the path for the summary counts of the 'reconcile' and
+    /// 'progress' commands.
+    #[arg(long, help = "Path to summary output.")]
+    pub summary: Option<std::path::PathBuf>,
+    /// The `collections` field holds the named address collections for the 'matrix' and
+    /// 'conflate' commands,
+    /// each in the form 'name,type,path', where the type is 'grants_pass', 'josephine_county' or
+    /// 'common'.  Repeat the argument for each collection.
+    #[arg(
+        long = "collection",
+        help = "Named address collection as 'name,type,path'."
+    )]
+    pub collections: Vec<String>,
+    /// The `precedence` field specifies the path to the precedence policy for the 'conflate'
+    /// command, a csv file with `field`, `region` and `precedence` columns.  See
+    /// [`crate::PrecedencePolicy`].
+    #[arg(long, help = "Path to precedence policy.")]
+    pub precedence: Option<std::path::PathBuf>,
+    /// The `boundary` field specifies the path to a boundary polygon, such as the city limits, as
+    /// a csv file with `longitude` and `latitude` columns.  The 'business' command checks the
+    /// jurisdiction claimed on each license against the boundary.  Without a boundary, the check
+    /// runs only when the target is the city address set ('grants_pass').
+    #[arg(long, help = "Path to boundary polygon.")]
+    pub boundary: Option<std::path::PathBuf>,
+    /// The `changes` field specifies the path for the changed components of the 'correct'
+    /// command, one row for each corrected component with its old and new value.
+    #[arg(long, help = "Path to correction change output.")]
+    pub changes: Option<std::path::PathBuf>,
+    /// The `mismatches` field specifies the path for the mismatched component values of the
+    /// 'compare' command, one row for each mismatched component of a divergent record.
+    #[arg(long, help = "Path to mismatch output.")]
+    pub mismatches: Option<std::path::PathBuf>,
+    /// The `provenance` field specifies the path for the field provenance of the 'conflate'
+    /// command.
+    #[arg(long, help = "Path to provenance output.")]
+    pub provenance: Option<std::path::PathBuf>,
+    /// The `rejections` field specifies the path for the rejection report of the 'business',
+    /// 'fire' and 'locate' commands, listing the source rows that failed to deserialize or parse.
+    #[arg(long, help = "Path to rejection report output.")]
+    pub rejections: Option<std::path::PathBuf>,
+    /// The `address_column` field specifies where the 'locate' command reads the address of each
+    /// source row: the name of a column holding the complete address, or `component=column`
+    /// pairs separated by semicolons.  See [`crate::AddressColumns::from_spec`].
+    #[arg(
+        long,
+        help = "Address column, or 'component=column' pairs separated by semicolons.",
+        default_value = "address"
+    )]
+    pub address_column: String,
+    /// The `attributes` field names the source columns passed through to the output of the
+    /// 'locate' command.  Repeat the argument for each column.  Defaults to every column.
+    #[arg(
+        long = "attribute",
+        help = "Source column to pass through to the output."
+    )]
+    pub attributes: Vec<String>,
+    /// The `days` field specifies the number of days ahead the 'expiring' command reports
+    /// expiring business licenses.
+    #[arg(
+        long,
+        help = "Days ahead to report expiring licenses.",
+        default_value = "30"
+    )]
+    pub days: u64,
+    /// The `date` field specifies the date, as 'YYYY-MM-DD', that the 'expiring' command
+    /// measures expiration from.  Defaults to the current date.
+    #[arg(long, help = "Date to measure expiration from, as 'YYYY-MM-DD'.")]
+    pub date: Option<String>,
+    /// The `level` field specifies the level of the NAICS hierarchy the 'naics' command groups
+    /// licenses by, either 'sector' or 'subsector'.
+    #[arg(
+        long,
+        help = "NAICS level to summarize by, 'sector' or 'subsector'.",
+        default_value = "subsector"
+    )]
+    pub level: String,
+    /// The `layer` field specifies the path to the GIS business layer for the 'sync' command.
+    #[arg(long, help = "Path to GIS business layer.")]
+    pub layer: Option<std::path::PathBuf>,
+    /// The `updated` field specifies the path for the updated GIS business layer of the 'sync'
+    /// command.
+    #[arg(long, help = "Path to updated business layer output.")]
+    pub updated: Option<std::path::PathBuf>,
 }
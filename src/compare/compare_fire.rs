@@ -1,7 +1,7 @@
 //!  The `compare_fire` module implements address matching and comparison for Fire Inspections.
 use crate::{
     Address, AddressErrorKind, FireInspection, FireInspections, Geographic, IntoCsv, Io,
-    MatchPartialRecord, MatchPartialRecords, MatchStatus, from_csv, to_csv,
+    MatchPartialRecord, MatchPartialRecords, MatchPolicy, MatchStatus, from_csv, record_id, to_csv,
 };
 use derive_more::{Deref, DerefMut};
 use indicatif::ParallelProgressIterator;
@@ -25,7 +25,19 @@ impl FireInspectionMatch {
     /// from the fire inspection and comparing it against a set of fully-specified addresses.
     #[tracing::instrument(skip_all)]
     pub fn compare<T: Address + Geographic>(inspection: &FireInspection, addresses: &[T]) -> Self {
-        let record = MatchPartialRecord::compare(inspection.address(), addresses);
+        Self::compare_with_policy(inspection, addresses, &MatchPolicy::partial())
+    }
+
+    /// The `compare_with_policy` method matches the fire inspection as in
+    /// [`FireInspectionMatch::compare`], using `policy` to determine which components must agree.
+    #[tracing::instrument(skip_all)]
+    pub fn compare_with_policy<T: Address + Geographic>(
+        inspection: &FireInspection,
+        addresses: &[T],
+        policy: &MatchPolicy,
+    ) -> Self {
+        let record =
+            MatchPartialRecord::compare_with_policy(inspection.address(), addresses, policy);
         FireInspectionMatch {
             inspection: inspection.clone(),
             record,
@@ -56,6 +68,17 @@ impl FireInspectionMatches {
     pub fn compare<T: Address + Geographic + Send + Sync>(
         inspections: &FireInspections,
         addresses: &[T],
+    ) -> Self {
+        Self::compare_with_policy(inspections, addresses, &MatchPolicy::partial())
+    }
+
+    /// The `compare_with_policy` method matches the inspections as in
+    /// [`FireInspectionMatches::compare`], using `policy` to determine which components must
+    /// agree.
+    pub fn compare_with_policy<T: Address + Geographic + Send + Sync>(
+        inspections: &FireInspections,
+        addresses: &[T],
+        policy: &MatchPolicy,
     ) -> Self {
         let style = indicatif::ProgressStyle::with_template(
             "[{elapsed_precise}] {bar:40.cyan/blue} {pos:>7}/{len:7} {'Comparing addresses.'}",
@@ -63,18 +86,20 @@ impl FireInspectionMatches {
         .unwrap();
         let records = inspections
             .par_iter()
-            .map(|r| FireInspectionMatch::compare(r, addresses))
+            .map(|r| FireInspectionMatch::compare_with_policy(r, addresses, policy))
             .progress_with_style(style)
             .collect::<Vec<FireInspectionMatch>>();
         FireInspectionMatches(records)
     }
 
     /// The `filter` method filters records from Self.  Currently accepts values "missing",
-    /// "divergent" and "matching", which filter based on the match status [`MatchStatus`].
+    /// "divergent", "displaced" and "matching", which filter based on the match status
+    /// [`MatchStatus`].
     pub fn filter(&mut self, filter: &str) {
         match filter {
             "missing" => self.retain(|r| r.record()[0].match_status() == MatchStatus::Missing),
             "divergent" => self.retain(|r| r.record()[0].match_status() == MatchStatus::Divergent),
+            "displaced" => self.retain(|r| r.record()[0].match_status() == MatchStatus::Displaced),
             "matching" => self.retain(|r| r.record()[0].match_status() == MatchStatus::Matching),
             _ => info!("Invalid filter provided."),
         }
@@ -89,6 +114,12 @@ pub struct FireInspectionMatchRecord {
     status: MatchStatus,
     // The business name.
     name: String,
+    // Field used by fire dept.
+    #[serde(default)]
+    class: Option<String>,
+    // Field used by fire dept.
+    #[serde(default)]
+    subclass: Option<String>,
     // The provided business address.
     address_label: String,
     // The comparison address.
@@ -97,6 +128,9 @@ pub struct FireInspectionMatchRecord {
     longitude: Option<f64>,
     // Latitude of comparison address.
     latitude: Option<f64>,
+    // Unique id derived from the business name and the partial address match.
+    #[serde(default)]
+    id: uuid::Uuid,
 }
 
 impl FireInspectionMatchRecord {
@@ -105,6 +139,24 @@ impl FireInspectionMatchRecord {
     pub fn status(&self) -> MatchStatus {
         self.status.to_owned()
     }
+
+    /// The `name` method returns the cloned value of the `name` field, containing the business
+    /// name from the inspection.
+    pub fn name(&self) -> String {
+        self.name.to_owned()
+    }
+
+    /// The `class` method returns the cloned value of the `class` field, containing the class
+    /// assigned to the inspection by the fire department.
+    pub fn class(&self) -> Option<String> {
+        self.class.clone()
+    }
+
+    /// The `subclass` method returns the cloned value of the `subclass` field, containing the
+    /// subclass assigned to the inspection by the fire department.
+    pub fn subclass(&self) -> Option<String> {
+        self.subclass.clone()
+    }
 }
 
 /// The `FireInspectionMatchRecords` struct is wrapper for a vector of type
@@ -119,12 +171,13 @@ impl FireInspectionMatchRecords {
     //    Ok(Self(records))
     //}
     /// The `filter` method returns the subset of records that match the filter.  Current values
-    /// for the `filter` field include "missing", "divergent", "matching", which filter by address
-    /// match status.
+    /// for the `filter` field include "missing", "divergent", "displaced" and "matching", which
+    /// filter by address match status.
     pub fn filter(&mut self, filter: &str) {
         match filter {
             "missing" => self.retain(|r| r.status() == MatchStatus::Missing),
             "divergent" => self.retain(|r| r.status() == MatchStatus::Divergent),
+            "displaced" => self.retain(|r| r.status() == MatchStatus::Displaced),
             "matching" => self.retain(|r| r.status() == MatchStatus::Matching),
             _ => info!("Invalid filter provided."),
         }
@@ -145,16 +198,20 @@ impl IntoCsv<FireInspectionMatchRecords> for FireInspectionMatchRecords {
 impl From<&FireInspectionMatch> for FireInspectionMatchRecords {
     fn from(inspection: &FireInspectionMatch) -> Self {
         let mut records = Vec::new();
-        let name = inspection.inspection().name().clone();
-        let address_label = inspection.inspection().address().label();
+        let fire = inspection.inspection();
+        let name = fire.name().clone();
+        let address_label = fire.address().label();
         for record in inspection.record().iter() {
             records.push(FireInspectionMatchRecord {
                 status: record.match_status(),
                 name: name.to_owned(),
+                class: fire.class().clone(),
+                subclass: fire.subclass().clone(),
                 address_label: address_label.to_owned(),
                 other_label: record.other_label(),
                 longitude: record.longitude(),
                 latitude: record.latitude(),
+                id: record_id(&["fire", &name, &record.id().to_string()]),
             });
         }
 
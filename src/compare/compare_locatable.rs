@@ -0,0 +1,158 @@
+//! The `compare_locatable` module implements address matching for records imported from an
+//! arbitrary csv source, producing the original attributes with a match status and coordinates.
+use crate::{
+    Address, AddressErrorKind, Csv, Geographic, Io, LocatableRecords, MatchPartialRecord,
+    MatchPolicy, MatchStatus,
+};
+use derive_more::{Deref, DerefMut};
+use indicatif::ParallelProgressIterator;
+use rayon::prelude::*;
+use std::collections::HashSet;
+use tracing::info;
+
+/// The `LocatableMatchRecord` struct holds the passthrough attributes of a
+/// [`crate::LocatableRecord`] with the result of matching the record address against a set of
+/// fully-specified addresses.
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct LocatableMatchRecord {
+    /// The `attributes` field holds the values of the passthrough columns of the source record.
+    pub attributes: Vec<String>,
+    /// The `status` field is the match status of the record address.
+    pub status: MatchStatus,
+    /// The `address_label` field is the parsed address of the source record.
+    pub address_label: String,
+    /// The `other_label` field is the label of the comparison address, if any.
+    pub other_label: Option<String>,
+    /// The `longitude` field is the longitude of the comparison address, if any.
+    pub longitude: Option<f64>,
+    /// The `latitude` field is the latitude of the comparison address, if any.
+    pub latitude: Option<f64>,
+}
+
+/// The `LocatableMatchRecords` struct holds a vector of type [`LocatableMatchRecord`], along
+/// with the names of the passthrough columns.
+#[derive(Debug, Default, Clone, PartialEq, PartialOrd, Deref, DerefMut)]
+pub struct LocatableMatchRecords {
+    /// The `columns` field holds the names of the passthrough columns.
+    pub columns: Vec<String>,
+    /// The `records` field holds the match records.
+    #[deref]
+    #[deref_mut]
+    pub records: Vec<LocatableMatchRecord>,
+}
+
+impl LocatableMatchRecords {
+    /// The `compare` method matches the address of each record in `records` against
+    /// `addresses`, producing a match record for each result of
+    /// [`MatchPartialRecord::compare`].  Uses the [`MatchPolicy::partial`] policy.
+    pub fn compare<T: Address + Geographic + Send + Sync>(
+        records: &LocatableRecords,
+        addresses: &[T],
+    ) -> Self {
+        Self::compare_with_policy(records, addresses, &MatchPolicy::partial())
+    }
+
+    /// The `compare_with_policy` method matches the records as in
+    /// [`LocatableMatchRecords::compare`], using `policy` to determine which components must
+    /// agree.
+    pub fn compare_with_policy<T: Address + Geographic + Send + Sync>(
+        records: &LocatableRecords,
+        addresses: &[T],
+        policy: &MatchPolicy,
+    ) -> Self {
+        let style = indicatif::ProgressStyle::with_template(
+            "[{elapsed_precise}] {bar:40.cyan/blue} {pos:>7}/{len:7} {'Comparing addresses.'}",
+        )
+        .unwrap();
+        let matches = records
+            .par_iter()
+            .map(|record| {
+                MatchPartialRecord::compare_with_policy(&record.address, addresses, policy)
+                    .iter()
+                    .map(|matched| LocatableMatchRecord {
+                        attributes: record.attributes.clone(),
+                        status: matched.match_status(),
+                        address_label: matched.address_label(),
+                        other_label: matched.other_label(),
+                        longitude: matched.longitude(),
+                        latitude: matched.latitude(),
+                    })
+                    .collect::<Vec<LocatableMatchRecord>>()
+            })
+            .progress_with_style(style)
+            .collect::<Vec<Vec<LocatableMatchRecord>>>();
+        Self {
+            columns: records.columns.clone(),
+            records: matches.into_iter().flatten().collect(),
+        }
+    }
+
+    /// The `filter` method filters records from Self.  Currently accepts values "missing",
+    /// "divergent", "displaced" and "matching", which filter based on the match status
+    /// [`MatchStatus`].
+    pub fn filter(&mut self, filter: &str) {
+        match filter {
+            "missing" => self.retain(|r| r.status == MatchStatus::Missing),
+            "divergent" => self.retain(|r| r.status == MatchStatus::Divergent),
+            "displaced" => self.retain(|r| r.status == MatchStatus::Displaced),
+            "matching" => self.retain(|r| r.status == MatchStatus::Matching),
+            _ => info!("Invalid filter provided."),
+        }
+    }
+
+    /// The `to_csv` method writes the records to a csv file at `path`, with the passthrough
+    /// columns of the source followed by the `status`, `address_label`, `other_label`,
+    /// `longitude` and `latitude` columns.  A passthrough column sharing the name of one of these
+    /// columns is written with a "source_" prefix, repeated until the name is free, so the
+    /// headers stay unique.
+    pub fn to_csv<P: AsRef<std::path::Path>>(&self, path: P) -> Result<(), AddressErrorKind> {
+        let path = path.as_ref().to_path_buf();
+        let mut wtr = csv::Writer::from_path(&path)
+            .map_err(|source| Csv::new(path.clone(), source, line!(), file!().into()))?;
+        let output = [
+            "status",
+            "address_label",
+            "other_label",
+            "longitude",
+            "latitude",
+        ];
+        let mut taken = self
+            .columns
+            .iter()
+            .map(|column| column.as_str())
+            .chain(output)
+            .map(|column| column.to_string())
+            .collect::<HashSet<String>>();
+        let mut header = Vec::with_capacity(self.columns.len() + output.len());
+        for column in self.columns.iter() {
+            if output.contains(&column.as_str()) {
+                let mut name = format!("source_{}", column);
+                while taken.contains(&name) {
+                    name = format!("source_{}", name);
+                }
+                taken.insert(name.clone());
+                header.push(name);
+            } else {
+                header.push(column.clone());
+            }
+        }
+        for column in output {
+            header.push(column.to_string());
+        }
+        wtr.write_record(&header)
+            .map_err(|source| Csv::new(path.clone(), source, line!(), file!().into()))?;
+        for record in self.iter() {
+            let mut row = record.attributes.clone();
+            row.push(record.status.to_string());
+            row.push(record.address_label.clone());
+            row.push(record.other_label.clone().unwrap_or_default());
+            row.push(record.longitude.map(|x| x.to_string()).unwrap_or_default());
+            row.push(record.latitude.map(|x| x.to_string()).unwrap_or_default());
+            wtr.write_record(&row)
+                .map_err(|source| Csv::new(path.clone(), source, line!(), file!().into()))?;
+        }
+        wtr.flush()
+            .map_err(|source| Io::new(path.clone(), source, line!(), file!().into()))?;
+        Ok(())
+    }
+}
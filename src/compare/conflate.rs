@@ -0,0 +1,362 @@
+//! The `conflate` module contains types for merging named address collections into a single set
+//! of addresses, choosing each field by precedence and recording the source of every field.
+use crate::{
+    Address, AddressComponent, AddressErrorKind, Boundary, Cartesian, Geographic, IntoCsv, Io,
+    MatchPolicy, SpatialAddress, SpatialAddresses, from_csv, from_csv_with, to_csv,
+};
+use derive_more::{Deref, DerefMut};
+use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
+use tracing::info;
+
+use super::matrix::canonical_groups;
+
+/// The `STREET` constant lists the components selected by the "street" field of a precedence
+/// rule.
+const STREET: [AddressComponent; 6] = [
+    AddressComponent::Directional,
+    AddressComponent::PreModifier,
+    AddressComponent::PreType,
+    AddressComponent::Separator,
+    AddressComponent::StreetName,
+    AddressComponent::StreetType,
+];
+
+/// The `Region` enum limits a precedence rule to addresses inside or outside the [`Boundary`] of
+/// a [`PrecedencePolicy`].
+#[derive(
+    Debug,
+    Default,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    strum::Display,
+    strum::EnumString,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum Region {
+    /// The rule applies to every address.
+    #[default]
+    Anywhere,
+    /// The rule applies to addresses inside the boundary.
+    Inside,
+    /// The rule applies to addresses outside the boundary.
+    Outside,
+}
+
+/// The `PrecedenceRule` struct represents a single rule of a [`PrecedencePolicy`], used to read
+/// and write the policy as csv.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct PrecedenceRule {
+    field: String,
+    #[serde(default)]
+    region: Region,
+    precedence: String,
+}
+
+impl PrecedenceRule {
+    /// The `validate` method returns the rule if the field names "all", "street", "coordinates"
+    /// or an [`AddressComponent`], and an error describing the field otherwise.
+    fn validate(self) -> Result<Self, String> {
+        let known = matches!(self.field.as_str(), "all" | "street" | "coordinates")
+            || AddressComponent::iter().any(|component| component.to_string() == self.field);
+        if known {
+            Ok(self)
+        } else {
+            Err(format!("unknown field {}", self.field))
+        }
+    }
+
+    /// The `applies` method returns true if the rule selects `field` for an address where
+    /// `inside` reports whether the address lies inside the boundary, or `None` if there is no
+    /// boundary.
+    fn applies(&self, field: &str, inside: Option<bool>) -> bool {
+        let selected = match self.field.as_str() {
+            "all" => true,
+            "street" => STREET
+                .iter()
+                .any(|component| component.to_string() == field),
+            name => name == field,
+        };
+        let located = match self.region {
+            Region::Anywhere => true,
+            Region::Inside => inside == Some(true),
+            Region::Outside => inside == Some(false),
+        };
+        selected && located
+    }
+}
+
+/// The `PrecedencePolicy` struct determines which collection supplies each field of a conflated
+/// address.  Rules are checked in order, and the first rule that applies to a field gives the
+/// names of the collections in order of precedence.  Collections not named by the rule, and
+/// fields with no applicable rule, fall back to the order of the collections in the conflation.
+///
+/// The field of a rule is the name of an [`AddressComponent`], "street" for the components of
+/// the complete street name, "coordinates" for the address point, or "all" for every field.
+/// Rules limited to a [`Region`] only apply when the policy has a [`Boundary`].  The rules can be
+/// read from a csv file with `field`, `region` and `precedence` columns, separating the names
+/// in the precedence with semicolons, such as the following.  Loading fails on any row with an
+/// unknown field or region.  See [`PrecedencePolicy::validate`] to check the collection names.
+///
+/// ```text
+/// field,region,precedence
+/// street,anywhere,city;county
+/// coordinates,outside,county;city
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PrecedencePolicy {
+    rules: Vec<PrecedenceRule>,
+    boundary: Option<Boundary>,
+    tolerance: f64,
+}
+
+impl Default for PrecedencePolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PrecedencePolicy {
+    /// Creates a new `PrecedencePolicy` with no rules and no boundary.  Address points closer
+    /// than one meter are not reported as a conflict.
+    pub fn new() -> Self {
+        Self {
+            rules: Vec::new(),
+            boundary: None,
+            tolerance: 1.0,
+        }
+    }
+
+    /// The `with_rule` method returns the policy with a rule giving the collections named in
+    /// `precedence` precedence over `field` in `region`, after any existing rules.
+    pub fn with_rule(mut self, field: &str, region: Region, precedence: &[&str]) -> Self {
+        self.rules.push(PrecedenceRule {
+            field: field.to_string(),
+            region,
+            precedence: precedence.join(";"),
+        });
+        self
+    }
+
+    /// The `with_boundary` method returns the policy with `boundary` used by rules limited to a
+    /// [`Region`].
+    pub fn with_boundary(mut self, boundary: Boundary) -> Self {
+        self.boundary = Some(boundary);
+        self
+    }
+
+    /// The `with_tolerance` method returns the policy with the distance in meters that address
+    /// points can differ before the coordinates are reported as a conflict set to `tolerance`.
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// The `validate` method returns an error naming the first collection in the precedence of a
+    /// rule that is not among `names`, the names of the collections in the conflation.  A rule
+    /// naming a missing collection would otherwise never apply.
+    pub fn validate(&self, names: &[&str]) -> Result<(), String> {
+        for rule in self.rules.iter() {
+            for name in rule.precedence.split(';').map(str::trim) {
+                if !names.contains(&name) {
+                    return Err(format!(
+                        "unknown collection {} in the precedence of field {}",
+                        name, rule.field
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The `precedence` method returns the names of the collections in order of precedence for
+    /// `field` at the address point `point`, from the first rule that applies.  Returns an empty
+    /// vector if no rule applies.
+    pub fn precedence<T: Geographic + ?Sized>(&self, field: &str, point: &T) -> Vec<String> {
+        let inside = self
+            .boundary
+            .as_ref()
+            .map(|boundary| boundary.contains(point));
+        self.rules
+            .iter()
+            .find(|rule| rule.applies(field, inside))
+            .map(|rule| {
+                rule.precedence
+                    .split(';')
+                    .map(|name| name.trim().to_string())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+impl IntoCsv<PrecedencePolicy> for PrecedencePolicy {
+    fn from_csv<P: AsRef<std::path::Path>>(path: P) -> Result<Self, Io> {
+        let (rules, rejections) = from_csv_with(&path, PrecedenceRule::validate)?;
+        if let Some(rejection) = rejections.first() {
+            let message = format!(
+                "{} invalid precedence rows, first at row {}: {}",
+                rejections.len(),
+                rejection.row,
+                rejection.error
+            );
+            let source = std::io::Error::new(std::io::ErrorKind::InvalidData, message);
+            return Err(Io::new(
+                path.as_ref().into(),
+                source,
+                line!(),
+                file!().into(),
+            ));
+        }
+        Ok(Self {
+            rules,
+            ..Self::new()
+        })
+    }
+
+    fn to_csv<P: AsRef<std::path::Path>>(&mut self, path: P) -> Result<(), AddressErrorKind> {
+        to_csv(&mut self.rules, path.as_ref().into())
+    }
+}
+
+/// The `ProvenanceRecord` struct reports the collection that supplied a single field of a
+/// conflated address.
+#[derive(Debug, Default, Clone, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct ProvenanceRecord {
+    /// The `index` field is the position of the conflated address in the output.
+    pub index: usize,
+    /// The `label` field is the label of the conflated address.
+    pub label: String,
+    /// The `field` field is the name of the [`AddressComponent`], or "coordinates" for the
+    /// address point.
+    pub field: String,
+    /// The `source` field is the name of the collection that supplied the field.
+    pub source: String,
+    /// The `conflict` field is true if the collections containing the address disagree on the
+    /// field.
+    pub conflict: bool,
+    /// The `sources` field lists the names of the collections containing the address,
+    /// separated by semicolons.
+    pub sources: String,
+}
+
+/// The `ProvenanceRecords` struct holds a vector of type [`ProvenanceRecord`].
+#[derive(Debug, Default, Clone, PartialEq, PartialOrd, Serialize, Deserialize, Deref, DerefMut)]
+pub struct ProvenanceRecords(Vec<ProvenanceRecord>);
+
+impl ProvenanceRecords {
+    /// The `filter` method returns the subset of records that meet the filter requirement.  The
+    /// `filter` parameter can take the value "conflict", returning the fields where the
+    /// collections disagree, or the name of a field.
+    pub fn filter(mut self, filter: &str) -> Self {
+        match filter {
+            "conflict" => self.retain(|r| r.conflict),
+            _ => self.retain(|r| r.field == filter),
+        }
+        self
+    }
+}
+
+impl IntoCsv<ProvenanceRecords> for ProvenanceRecords {
+    fn from_csv<P: AsRef<std::path::Path>>(path: P) -> Result<Self, Io> {
+        let records = from_csv(path)?;
+        Ok(Self(records))
+    }
+
+    fn to_csv<P: AsRef<std::path::Path>>(&mut self, path: P) -> Result<(), AddressErrorKind> {
+        to_csv(&mut self.0, path.as_ref().into())
+    }
+}
+
+/// The `Conflation` struct holds the merged addresses from a conflation of named collections,
+/// and the [`ProvenanceRecords`] for every field of the merged addresses.
+#[derive(Debug, Default, Clone, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct Conflation {
+    /// The `addresses` field holds the merged addresses.
+    pub addresses: SpatialAddresses,
+    /// The `provenance` field holds a record for each field of each merged address.
+    pub provenance: ProvenanceRecords,
+}
+
+impl Conflation {
+    /// The `merge` method conflates the named collections in `sources` into a single set of
+    /// addresses.  Addresses are grouped as in [`crate::MatrixRecords::compare`], using `policy`
+    /// to determine which components must agree.  Each field of a merged address is taken from
+    /// the collection with the highest precedence under `precedence` that contains the address.
+    /// Regions are evaluated at the address point of the first collection containing the
+    /// address.
+    pub fn merge<T: Address + Geographic + Cartesian + Clone>(
+        sources: &[(&str, &[T])],
+        precedence: &PrecedencePolicy,
+        policy: &MatchPolicy,
+    ) -> Self {
+        let groups = canonical_groups(sources, policy);
+        info!("Conflating {} addresses.", groups.len());
+        let mut addresses = Vec::new();
+        let mut provenance = Vec::new();
+        for (index, group) in groups.iter().enumerate() {
+            let members = group
+                .iter()
+                .enumerate()
+                .filter_map(|(source, member)| {
+                    member.map(|(position, _)| (sources[source].0, &sources[source].1[position]))
+                })
+                .collect::<Vec<(&str, &T)>>();
+            let founder = members[0].1;
+            let names = members
+                .iter()
+                .map(|(name, _)| *name)
+                .collect::<Vec<&str>>()
+                .join(";");
+            let winner = |field: &str| {
+                precedence
+                    .precedence(field, founder)
+                    .iter()
+                    .find_map(|name| members.iter().find(|(source, _)| source == name))
+                    .unwrap_or(&members[0])
+            };
+            let (source, located) = winner("coordinates");
+            let mut merged = SpatialAddress::from(*located);
+            let mut fields = vec![(
+                "coordinates".to_string(),
+                source.to_string(),
+                members
+                    .iter()
+                    .any(|(_, other)| located.haversine(*other) > precedence.tolerance),
+            )];
+            for component in AddressComponent::iter() {
+                let field = component.to_string();
+                let (source, address) = winner(&field);
+                component.assign(*address, &mut merged);
+                let conflict = members
+                    .iter()
+                    .any(|(_, other)| component.differs(*address, *other));
+                fields.push((field, source.to_string(), conflict));
+            }
+            let label = merged.label();
+            for (field, source, conflict) in fields {
+                provenance.push(ProvenanceRecord {
+                    index,
+                    label: label.clone(),
+                    field,
+                    source,
+                    conflict,
+                    sources: names.clone(),
+                });
+            }
+            addresses.push(merged);
+        }
+        Self {
+            addresses: SpatialAddresses::new(addresses),
+            provenance: ProvenanceRecords(provenance),
+        }
+    }
+}
@@ -0,0 +1,210 @@
+//! The `correct` module contains types for producing corrections to a target set of addresses,
+//! in the native schema of the target, from the components of matching source addresses.
+use crate::{
+    Address, AddressComponent, AddressErrorKind, IntoCsv, Io, MatchPolicy, MatchRule, Mismatch,
+    from_csv, to_csv,
+};
+use derive_more::{Deref, DerefMut};
+use indicatif::ParallelProgressIterator;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use strum::IntoEnumIterator;
+use tracing::info;
+
+/// The `Correction` struct holds a corrected copy of a single target record, in the native
+/// schema of the target.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Correction<U> {
+    /// The `index` field is the position of the record in the target set.
+    pub index: usize,
+    /// The `record` field is the target record with the corrected components taken from the
+    /// matching source address.  Fields outside the address components, such as the native
+    /// identifier, are unchanged.
+    pub record: U,
+    /// The `corrected` field lists the components corrected in the record.
+    pub corrected: Vec<AddressComponent>,
+    /// The `changes` field holds a [`Mismatch`] for each corrected component, with the value of
+    /// the target record in the `from` field and the corrected value in the `to` field.
+    pub changes: Vec<Mismatch>,
+    /// The `skipped` field lists the mismatched components that the native schema of the target
+    /// cannot hold, such as the building of a schema without a building field.
+    pub skipped: Vec<AddressComponent>,
+}
+
+/// The `Corrections` struct holds a vector of type [`Correction`].
+#[derive(Debug, Clone, PartialEq, Deref, DerefMut)]
+pub struct Corrections<U>(Vec<Correction<U>>);
+
+impl<U: Address + Clone + Send + Sync> Corrections<U> {
+    /// The `compare` method compares each address in `target` against `source`, using `policy`
+    /// to determine which components must agree, and corrects the components reported as a
+    /// [`crate::Mismatch`] to the values of the source address.  Target addresses are compared
+    /// after [`Address::standardize`], but the corrected record keeps the native values of the
+    /// components that are not corrected.  A target address is only corrected when it
+    /// coincides with a single source address.  Target addresses with an exact match, with no
+    /// match, or with several divergent matches produce no correction.
+    pub fn compare<T: Address + Send + Sync>(
+        source: &[T],
+        target: &[U],
+        policy: &MatchPolicy,
+    ) -> Self {
+        let blocked = policy.rule(AddressComponent::Number) == MatchRule::Required;
+        let mut blocks: HashMap<i64, Vec<usize>> = HashMap::new();
+        if blocked {
+            for (index, address) in source.iter().enumerate() {
+                blocks.entry(address.number()).or_default().push(index);
+            }
+        }
+        let all = (0..source.len()).collect::<Vec<usize>>();
+        let style = indicatif::ProgressStyle::with_template(
+            "[{elapsed_precise}] {bar:40.cyan/blue} {pos:>7}/{len:7} {'Correcting addresses.'}",
+        )
+        .unwrap();
+        let records = target
+            .par_iter()
+            .enumerate()
+            .map(|(index, address)| {
+                let mut standard = address.clone();
+                standard.standardize();
+                let candidates = if blocked {
+                    blocks.get(&standard.number()).map_or(&[][..], |c| &c[..])
+                } else {
+                    &all[..]
+                };
+                let mut matches = Vec::new();
+                for &source_index in candidates {
+                    let address_match = standard.coincident_with(&source[source_index], policy);
+                    if address_match.coincident {
+                        match address_match.mismatches {
+                            Some(mismatches) => matches.push((source_index, mismatches.to_vec())),
+                            None => return None,
+                        }
+                    }
+                }
+                if matches.len() != 1 {
+                    return None;
+                }
+                let (source_index, mismatches) = &matches[0];
+                let other = &source[*source_index];
+                let mut record = address.clone();
+                let mut corrected = Vec::new();
+                let mut changes = Vec::new();
+                let mut skipped = Vec::new();
+                for mismatch in mismatches {
+                    let component = mismatch.component();
+                    let mut candidate = record.clone();
+                    component.assign(other, &mut candidate);
+                    // Reject the correction if the schema cannot hold the value, or if writing
+                    // the component changes another component of the record.
+                    let accepted = AddressComponent::iter().all(|c| {
+                        if c == component {
+                            !c.differs(other, &candidate)
+                        } else {
+                            !c.differs(&record, &candidate)
+                        }
+                    });
+                    if accepted {
+                        changes.push(component.mismatch(&record, &candidate));
+                        record = candidate;
+                        corrected.push(component);
+                    } else {
+                        skipped.push(component);
+                    }
+                }
+                Some(Correction {
+                    index,
+                    record,
+                    corrected,
+                    changes,
+                    skipped,
+                })
+            })
+            .progress_with_style(style)
+            .collect::<Vec<Option<Correction<U>>>>();
+        let records = records
+            .into_iter()
+            .flatten()
+            .filter(|correction| !correction.corrected.is_empty())
+            .collect::<Vec<Correction<U>>>();
+        info!("{} target records corrected.", records.len());
+        Self(records)
+    }
+
+    /// The `records` method returns the corrected target records, in the native schema of the
+    /// target.
+    pub fn records(&self) -> Vec<U> {
+        self.iter()
+            .map(|correction| correction.record.clone())
+            .collect()
+    }
+
+    /// The `changes` method returns a [`CorrectionRecord`] for each corrected component, holding
+    /// the old and new value of the component.
+    pub fn changes(&self) -> CorrectionRecords {
+        let mut records = Vec::new();
+        for correction in self.iter() {
+            for change in &correction.changes {
+                let (old, new) = change.values();
+                records.push(CorrectionRecord {
+                    index: correction.index,
+                    id: correction.record.identifier(),
+                    label: correction.record.label(),
+                    component: change.component(),
+                    old,
+                    new,
+                });
+            }
+        }
+        CorrectionRecords(records)
+    }
+
+    /// The `count` method returns the number of records where `component` was corrected.
+    pub fn count(&self, component: AddressComponent) -> usize {
+        self.iter()
+            .filter(|correction| correction.corrected.contains(&component))
+            .count()
+    }
+}
+
+impl<U: Address + Clone + Send + Sync + Serialize> Corrections<U> {
+    /// The `to_csv` method writes the corrected target records to a csv file at `path`, using
+    /// the column names of the native schema, so the file can be loaded directly by the agency
+    /// maintaining the target.  See [`Corrections::changes`] for the changed components.
+    pub fn to_csv<P: AsRef<std::path::Path>>(&self, path: P) -> Result<(), AddressErrorKind> {
+        to_csv(&mut self.records(), path.as_ref().into())
+    }
+}
+
+/// The `CorrectionRecord` struct reports a single corrected component of a target record, so the
+/// agency maintaining the target can review each change before loading the corrected records.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct CorrectionRecord {
+    /// The `index` field is the position of the record in the target set.
+    pub index: usize,
+    /// The `id` field is the native identifier of the target record, if any.
+    pub id: Option<String>,
+    /// The `label` field is the label of the corrected target record.
+    pub label: String,
+    /// The `component` field is the corrected [`AddressComponent`].
+    pub component: AddressComponent,
+    /// The `old` field is the value of the component in the target record, empty if missing.
+    pub old: String,
+    /// The `new` field is the corrected value of the component, empty if missing.
+    pub new: String,
+}
+
+/// The `CorrectionRecords` struct holds a vector of type [`CorrectionRecord`].
+#[derive(Debug, Default, Clone, PartialEq, PartialOrd, Serialize, Deserialize, Deref, DerefMut)]
+pub struct CorrectionRecords(Vec<CorrectionRecord>);
+
+impl IntoCsv<CorrectionRecords> for CorrectionRecords {
+    fn from_csv<P: AsRef<std::path::Path>>(path: P) -> Result<Self, Io> {
+        let records = from_csv(path)?;
+        Ok(Self(records))
+    }
+
+    fn to_csv<P: AsRef<std::path::Path>>(&mut self, path: P) -> Result<(), AddressErrorKind> {
+        to_csv(&mut self.0, path.as_ref().into())
+    }
+}
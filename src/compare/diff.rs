@@ -0,0 +1,318 @@
+//! The `diff` module contains types for comparing two versions of the same address export,
+//! reporting the records added, removed and modified between the versions.
+use crate::{
+    Address, AddressComponent, AddressErrorKind, Business, Distance, FireInspection, Geographic,
+    IntoCsv, Io, from_csv, to_csv,
+};
+use derive_more::{Deref, DerefMut};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use strum::IntoEnumIterator;
+use tracing::info;
+
+/// The `DiffStatus` enum describes the change to a record between an old and new version of an
+/// address export.
+#[derive(
+    Debug,
+    Default,
+    Copy,
+    Clone,
+    PartialEq,
+    PartialOrd,
+    Eq,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    strum::Display,
+    strum::EnumString,
+    strum::EnumIter,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum DiffStatus {
+    /// The record is present in the new version, but not in the old version.
+    #[default]
+    Added,
+    /// The record is present in the old version, but not in the new version.
+    Removed,
+    /// The record is present in both versions, with changed components or a moved address point.
+    Modified,
+}
+
+/// The `DiffRecord` struct reports a single change between an old and new version of an address
+/// export.  Fields for the version missing the record are empty.
+#[derive(Debug, Default, Clone, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct DiffRecord {
+    /// The `status` field is the change to the record.
+    pub status: DiffStatus,
+    /// The `key` field is the value used to pair records between versions: the native
+    /// identifier of the record (see [`Address::identifier`]), or the label when the record has
+    /// no identifier.  Businesses are keyed on the license, and fire inspections on the business
+    /// name and label.
+    pub key: String,
+    /// The `old_label` field is the label of the record in the old version, if present.
+    pub old_label: Option<String>,
+    /// The `new_label` field is the label of the record in the new version, if present.
+    pub new_label: Option<String>,
+    /// The `old_index` field is the position of the record in the old version.
+    pub old_index: Option<usize>,
+    /// The `new_index` field is the position of the record in the new version.
+    pub new_index: Option<usize>,
+    /// The `changes` field lists the names of the changed components of a modified record,
+    /// separated by semicolons.
+    pub changes: Option<String>,
+    /// The `distance` field is the distance between the old and new address points of a modified
+    /// record, when the comparison considers location.
+    pub distance: Option<f64>,
+    /// The `moved` field is true if the address point moved farther than the distance threshold.
+    pub moved: bool,
+}
+
+impl DiffRecord {
+    /// The `changed` method returns true if the component `component` is listed among the
+    /// changes of the record.
+    pub fn changed(&self, component: AddressComponent) -> bool {
+        let name = component.to_string();
+        self.changes
+            .as_ref()
+            .is_some_and(|changes| changes.split("; ").any(|change| change == name))
+    }
+}
+
+/// The `DiffRecords` struct holds a vector of type [`DiffRecord`].
+#[derive(Debug, Default, Clone, PartialEq, PartialOrd, Serialize, Deserialize, Deref, DerefMut)]
+pub struct DiffRecords(Vec<DiffRecord>);
+
+impl DiffRecords {
+    /// The `compare` method reports the addresses added, removed and modified between the `old`
+    /// and `new` versions of an address export.  Records are paired on their native identifier,
+    /// falling back to the label for records without one.  Where several records share a key,
+    /// they are paired in the order they appear.
+    pub fn compare<T: Address>(old: &[T], new: &[T]) -> Self {
+        Self::diff(
+            old,
+            new,
+            &|address| address.identifier().unwrap_or_else(|| address.label()),
+            &|address| address.label(),
+            &|a, b| {
+                AddressComponent::iter()
+                    .filter(|component| component.differs(a, b))
+                    .collect()
+            },
+            None,
+        )
+    }
+
+    /// The `compare_geographic` method compares the `old` and `new` versions of an address
+    /// export as [`DiffRecords::compare`], and also reports address points that moved farther
+    /// than `threshold`, measured in meters using [`Geographic::haversine`].
+    pub fn compare_geographic<T: Address + Geographic>(
+        old: &[T],
+        new: &[T],
+        threshold: f64,
+    ) -> Self {
+        Self::diff(
+            old,
+            new,
+            &|address| address.identifier().unwrap_or_else(|| address.label()),
+            &|address| address.label(),
+            &|a, b| {
+                AddressComponent::iter()
+                    .filter(|component| component.differs(a, b))
+                    .collect()
+            },
+            Some((threshold, &|a: &T, b: &T| a.haversine(b))),
+        )
+    }
+
+    /// The `compare_businesses` method reports the businesses added, removed and modified
+    /// between the `old` and `new` versions of a business export, paired on the license
+    /// identifier.  Changes are reported for the components of the business address.
+    pub fn compare_businesses(old: &[Business], new: &[Business]) -> Self {
+        Self::diff(
+            old,
+            new,
+            &|business| business.license(),
+            &|business| business.address().label(),
+            &|a, b| {
+                let (a, b) = (a.address(), b.address());
+                AddressComponent::iter()
+                    .filter(|component| component.differs_partials(&a, &b))
+                    .collect()
+            },
+            None,
+        )
+    }
+
+    /// The `compare_businesses_cartesian` method compares the `old` and `new` versions of a
+    /// business export as [`DiffRecords::compare_businesses`], and also reports business points
+    /// that moved farther than `threshold`, in the units of the Web Mercator coordinates of the
+    /// layer.  A business point added to or removed from a business is reported as moved.
+    pub fn compare_businesses_cartesian(
+        old: &[Business],
+        new: &[Business],
+        threshold: f64,
+    ) -> Self {
+        Self::diff(
+            old,
+            new,
+            &|business| business.license(),
+            &|business| business.address().label(),
+            &|a, b| {
+                let (a, b) = (a.address(), b.address());
+                AddressComponent::iter()
+                    .filter(|component| component.differs_partials(&a, &b))
+                    .collect()
+            },
+            Some((threshold, &business_distance)),
+        )
+    }
+
+    /// The `compare_inspections` method reports the fire inspections added, removed and
+    /// modified between the `old` and `new` versions of a fire inspection export.  Inspections
+    /// have no native identifier, so records are paired on the business name and address label,
+    /// keeping apart the inspections of several businesses at the same address.
+    pub fn compare_inspections(old: &[FireInspection], new: &[FireInspection]) -> Self {
+        Self::diff(
+            old,
+            new,
+            &|inspection| format!("{}: {}", inspection.name(), inspection.address().label()),
+            &|inspection| inspection.address().label(),
+            &|a, b| {
+                AddressComponent::iter()
+                    .filter(|component| component.differs_partials(a.address(), b.address()))
+                    .collect()
+            },
+            None,
+        )
+    }
+
+    /// The `diff` method pairs the records of `old` and `new` using `key`, reporting unpaired
+    /// records as added or removed.  Paired records are modified if `changes` reports any
+    /// changed components, or if `spatial` holds a threshold and distance function and the
+    /// paired records lie farther apart than the threshold.
+    fn diff<T>(
+        old: &[T],
+        new: &[T],
+        key: &dyn Fn(&T) -> String,
+        label: &dyn Fn(&T) -> String,
+        changes: &dyn Fn(&T, &T) -> Vec<AddressComponent>,
+        spatial: Option<(f64, Distance<T, T>)>,
+    ) -> Self {
+        let mut keys: HashMap<String, VecDeque<usize>> = HashMap::new();
+        for (index, record) in old.iter().enumerate() {
+            keys.entry(key(record)).or_default().push_back(index);
+        }
+        let mut paired = vec![false; old.len()];
+        let mut records = Vec::new();
+        let mut unchanged = 0;
+        for (new_index, record) in new.iter().enumerate() {
+            let record_key = key(record);
+            let old_index = keys
+                .get_mut(&record_key)
+                .and_then(|queue| queue.pop_front());
+            match old_index {
+                Some(old_index) => {
+                    paired[old_index] = true;
+                    let previous = &old[old_index];
+                    let changed = changes(previous, record);
+                    let distance = spatial.map(|(_, distance)| distance(previous, record));
+                    let moved = match (spatial, distance) {
+                        (Some((threshold, _)), Some(distance)) => distance > threshold,
+                        _ => false,
+                    };
+                    if changed.is_empty() && !moved {
+                        unchanged += 1;
+                        continue;
+                    }
+                    let changes = if changed.is_empty() {
+                        None
+                    } else {
+                        Some(
+                            changed
+                                .iter()
+                                .map(|component| component.to_string())
+                                .collect::<Vec<String>>()
+                                .join("; "),
+                        )
+                    };
+                    records.push(DiffRecord {
+                        status: DiffStatus::Modified,
+                        key: record_key,
+                        old_label: Some(label(previous)),
+                        new_label: Some(label(record)),
+                        old_index: Some(old_index),
+                        new_index: Some(new_index),
+                        changes,
+                        distance,
+                        moved,
+                    });
+                }
+                None => records.push(DiffRecord {
+                    status: DiffStatus::Added,
+                    key: record_key,
+                    new_label: Some(label(record)),
+                    new_index: Some(new_index),
+                    ..Default::default()
+                }),
+            }
+        }
+        for (old_index, record) in old.iter().enumerate() {
+            if !paired[old_index] {
+                records.push(DiffRecord {
+                    status: DiffStatus::Removed,
+                    key: key(record),
+                    old_label: Some(label(record)),
+                    old_index: Some(old_index),
+                    ..Default::default()
+                });
+            }
+        }
+        info!("{} records unchanged.", unchanged);
+        Self(records)
+    }
+
+    /// The `count` method returns the number of records with the status `status`.
+    pub fn count(&self, status: DiffStatus) -> usize {
+        self.iter().filter(|r| r.status == status).count()
+    }
+
+    /// The `filter` method returns the subset of records that meet the filter requirement.  The
+    /// `filter` parameter can take the values "added", "removed", "modified", "moved", or the
+    /// name of any address component, returning the records where that component changed.
+    pub fn filter(mut self, filter: &str) -> Self {
+        if filter == "moved" {
+            self.retain(|r| r.moved);
+        } else if let Ok(status) = filter.parse::<DiffStatus>() {
+            self.retain(|r| r.status == status);
+        } else if let Ok(component) = filter.parse::<AddressComponent>() {
+            self.retain(|r| r.changed(component));
+        } else {
+            info!("Invalid filter provided.");
+        }
+        self
+    }
+}
+
+/// The `business_distance` function returns the distance between the business points of `a` and
+/// `b`, zero if neither business has a point, or infinite if only one does.
+fn business_distance(a: &Business, b: &Business) -> f64 {
+    let point = |business: &Business| business.x_coordinate().zip(business.y_coordinate());
+    match (point(a), point(b)) {
+        (Some((ax, ay)), Some((bx, by))) => (ax - bx).hypot(ay - by),
+        (None, None) => 0.0,
+        _ => f64::INFINITY,
+    }
+}
+
+impl IntoCsv<DiffRecords> for DiffRecords {
+    fn from_csv<P: AsRef<std::path::Path>>(path: P) -> Result<Self, Io> {
+        let records = from_csv(path)?;
+        Ok(Self(records))
+    }
+
+    fn to_csv<P: AsRef<std::path::Path>>(&mut self, path: P) -> Result<(), AddressErrorKind> {
+        to_csv(&mut self.0, path.as_ref().into())
+    }
+}
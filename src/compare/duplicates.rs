@@ -0,0 +1,200 @@
+//! The `duplicates` module contains types for reporting duplicate addresses within a single set of
+//! addresses.
+use crate::{
+    Address, AddressComponent, AddressErrorKind, AddressStatus, IntoCsv, Io, State,
+    StreetNamePostType, StreetNamePreDirectional, StreetNamePreModifier, StreetNamePreType,
+    StreetSeparator, SubaddressType, from_csv, to_csv,
+};
+use derive_more::{Deref, DerefMut};
+use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
+
+/// The `DuplicateGroup` struct holds a set of addresses that share the same address label,
+/// along with the names of any fields that differ between members of the group.
+#[derive(Debug, Clone, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct DuplicateGroup<T> {
+    /// The `id` field is the group identifier, numbered from 1 in order of first appearance in
+    /// the source addresses.
+    pub id: usize,
+    /// The `label` field holds the address label shared by members of the group.
+    pub label: String,
+    /// The `records` field holds the addresses in the group.
+    pub records: Vec<T>,
+    /// The `differences` field holds the names of fields that do not have the same value across
+    /// all members of the group.
+    pub differences: Vec<String>,
+}
+
+impl<T: Address> DuplicateGroup<T> {
+    /// Creates a new `DuplicateGroup` with identifier `id` from a vector of addresses sharing the
+    /// same label.  Each member of the group is compared against the first member to determine
+    /// which fields differ.
+    pub fn new(id: usize, records: Vec<T>) -> Self {
+        let label = records.first().map(|r| r.label()).unwrap_or_default();
+        let mut differences: Vec<String> = Vec::new();
+        if let Some((first, rest)) = records.split_first() {
+            for record in rest {
+                for field in differing_fields(first, record) {
+                    if !differences.contains(&field) {
+                        differences.push(field);
+                    }
+                }
+            }
+        }
+        Self {
+            id,
+            label,
+            records,
+            differences,
+        }
+    }
+
+    /// The `size` method returns the number of addresses in the group.
+    pub fn size(&self) -> usize {
+        self.records.len()
+    }
+}
+
+/// The `differing_fields` function returns the names of the address components where `a` and `b`
+/// hold different values.
+fn differing_fields<T: Address, U: Address>(a: &T, b: &U) -> Vec<String> {
+    AddressComponent::iter()
+        .filter(|component| component.differs(a, b))
+        .map(|component| component.to_string())
+        .collect()
+}
+
+/// The `DuplicateGroups` struct holds a vector of type [`DuplicateGroup`].
+#[derive(Debug, Clone, PartialEq, PartialOrd, Serialize, Deserialize, Deref, DerefMut)]
+pub struct DuplicateGroups<T>(Vec<DuplicateGroup<T>>);
+
+impl<T: Clone> DuplicateGroups<T> {
+    /// Creates a new `DuplicateGroups` from a vector of type [`DuplicateGroup`].
+    pub fn new(groups: Vec<DuplicateGroup<T>>) -> Self {
+        Self(groups)
+    }
+
+    /// The `records` method returns the members of every group as a flat vector of addresses, in
+    /// group order.
+    pub fn records(&self) -> Vec<T> {
+        self.iter()
+            .flat_map(|group| group.records.iter().cloned())
+            .collect::<Vec<T>>()
+    }
+}
+
+/// The `DuplicateRecord` struct represents a single member of a [`DuplicateGroup`], designed to
+/// export to csv with one row per address, tagged with the identifier of its group.
+#[derive(Debug, Default, Clone, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct DuplicateRecord {
+    /// The `group_id` field is the identifier of the duplicate group containing the address.
+    pub group_id: usize,
+    /// The `group_size` field is the number of addresses in the duplicate group.
+    pub group_size: usize,
+    /// The `differences` field lists the fields that differ within the group, separated by
+    /// semicolons.
+    pub differences: String,
+    /// The `label` field is the text representation of the address.
+    pub label: String,
+    /// The `number` field represents the address number component of the complete address
+    /// number.
+    pub number: i64,
+    /// The `number_suffix` field represents the address number suffix component of the complete
+    /// address number.
+    pub number_suffix: Option<String>,
+    /// The `directional` field represents the street name pre directional component of the
+    /// complete street name.
+    pub directional: Option<StreetNamePreDirectional>,
+    /// The `pre_modifier` field represents the street name pre modifier component of the complete
+    /// street name.
+    pub pre_modifier: Option<StreetNamePreModifier>,
+    /// The `pre_type` field represents the street name pre type component of the complete street
+    /// name.
+    pub pre_type: Option<StreetNamePreType>,
+    /// The `separator` field represents the separator element component of the complete street
+    /// name.
+    pub separator: Option<StreetSeparator>,
+    /// The `street_name` field represents the street name component of the complete street name.
+    pub street_name: String,
+    /// The `street_type` field represents the street name post type component of the complete street
+    /// name.
+    pub street_type: Option<StreetNamePostType>,
+    /// The `subaddress_type` field represents the subaddress type component of the complete
+    /// subaddress.
+    pub subaddress_type: Option<SubaddressType>,
+    /// The `subaddress_id` field represents the subaddress identifier component of the complete
+    /// subaddress.
+    pub subaddress_id: Option<String>,
+    /// The `floor` field represents the floor identifier, corresponding to the `Floor` field from the NENA standard.
+    pub floor: Option<i64>,
+    /// The `building` field represents the building identifier, corresponding to the `Building` field from the NENA standard.
+    pub building: Option<String>,
+    /// The `zip` field represents the postal zip code of the address.
+    pub zip: i64,
+    /// The `postal_community` field represents the postal community component of the address,
+    /// being either the unincorporated or incorporated municipality name.
+    pub postal_community: String,
+    /// The `state` field represents the state name component of the address.
+    pub state: State,
+    /// The `status` field represents the local status of the address as determined by the relevant
+    /// addressing authority.
+    pub status: AddressStatus,
+}
+
+impl DuplicateRecord {
+    /// Creates a new `DuplicateRecord` for `address`, a member of `group`.
+    pub fn new<T: Address, U>(address: &T, group: &DuplicateGroup<U>) -> Self {
+        Self {
+            group_id: group.id,
+            group_size: group.records.len(),
+            differences: group.differences.join("; "),
+            label: address.label(),
+            number: address.number(),
+            number_suffix: address.number_suffix().clone(),
+            directional: *address.directional(),
+            pre_modifier: *address.street_name_pre_modifier(),
+            pre_type: *address.street_name_pre_type(),
+            separator: *address.street_name_separator(),
+            street_name: address.street_name().clone(),
+            street_type: *address.street_type(),
+            subaddress_type: *address.subaddress_type(),
+            subaddress_id: address.subaddress_id().clone(),
+            floor: *address.floor(),
+            building: address.building().clone(),
+            zip: address.zip(),
+            postal_community: address.postal_community().clone(),
+            state: *address.state(),
+            status: *address.status(),
+        }
+    }
+}
+
+/// The `DuplicateRecords` struct holds a vector of type [`DuplicateRecord`].
+#[derive(Debug, Default, Clone, PartialEq, PartialOrd, Serialize, Deserialize, Deref, DerefMut)]
+pub struct DuplicateRecords(Vec<DuplicateRecord>);
+
+impl<T: Address> From<&DuplicateGroups<T>> for DuplicateRecords {
+    fn from(groups: &DuplicateGroups<T>) -> Self {
+        let records = groups
+            .iter()
+            .flat_map(|group| {
+                group
+                    .records
+                    .iter()
+                    .map(|address| DuplicateRecord::new(address, group))
+            })
+            .collect::<Vec<DuplicateRecord>>();
+        Self(records)
+    }
+}
+
+impl IntoCsv<DuplicateRecords> for DuplicateRecords {
+    fn from_csv<P: AsRef<std::path::Path>>(path: P) -> Result<Self, Io> {
+        let records = from_csv(path)?;
+        Ok(Self(records))
+    }
+
+    fn to_csv<P: AsRef<std::path::Path>>(&mut self, path: P) -> Result<(), AddressErrorKind> {
+        to_csv(&mut self.0, path.as_ref().into())
+    }
+}
@@ -1,53 +1,278 @@
 //! The `eponym` module is the eponymous module for `compare`.  Contains types and methods for
 //! comparing addresses.
 use crate::{
-    Address, AddressErrorKind, AddressStatus, Geographic, IntoCsv, Io, PartialAddress,
-    PartialAddresses, SubaddressType, from_csv, to_csv,
+    Address, AddressComponent, AddressErrorKind, AddressStatus, Cartesian, FuzzyIndex, Geographic,
+    IntoCsv, Io, MatchPolicy, MatchRule, PartialAddress, PartialAddresses, State,
+    StreetNamePostType, StreetNamePreDirectional, StreetNamePreModifier, StreetNamePreType,
+    StreetSeparator, SubaddressType, from_csv, record_id, record_identities, record_identity,
+    to_csv,
 };
 use derive_more::{Deref, DerefMut};
 use indicatif::ParallelProgressIterator;
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use strum::IntoEnumIterator;
 use tracing::info;
 
+/// The `MismatchSeverity` enum ranks how strongly a [`Mismatch`] suggests the compared addresses
+/// refer to different locations.
+#[derive(
+    Debug,
+    Default,
+    Copy,
+    Clone,
+    PartialEq,
+    PartialOrd,
+    Eq,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    strum::Display,
+    strum::EnumString,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum MismatchSeverity {
+    /// The `Minor` variant marks descriptive differences that do not change the location, such as
+    /// the floor, building, subaddress type or address status.
+    #[default]
+    Minor,
+    /// The `Moderate` variant marks differences in components that are often recorded
+    /// inconsistently between datasets, such as the post type, zip or postal community.
+    Moderate,
+    /// The `Major` variant marks differences in the components that locate the address, such as
+    /// the address number, directional, street name or subaddress identifier.
+    Major,
+}
+
 /// The `Mismatch` enum tracks the fields of an address that can diverge while still potentially
-/// referring to the same location.
+/// referring to the same location.  Each variant holds the value of the subject address in the
+/// `from` field, and the value of the comparison address in the `to` field.
 #[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash, Serialize, Deserialize)]
 pub enum Mismatch {
+    /// Represents a mismatch in the address number.
+    Number {
+        /// The address number of the subject address.
+        from: i64,
+        /// The address number of the comparison address.
+        to: i64,
+    },
+    /// Represents a mismatch in the address number suffix.
+    NumberSuffix {
+        /// The address number suffix of the subject address.
+        from: Option<String>,
+        /// The address number suffix of the comparison address.
+        to: Option<String>,
+    },
+    /// Represents a mismatch in the street name pre directional.
+    Directional {
+        /// The pre directional of the subject address.
+        from: Option<StreetNamePreDirectional>,
+        /// The pre directional of the comparison address.
+        to: Option<StreetNamePreDirectional>,
+    },
+    /// Represents a mismatch in the street name pre modifier.
+    PreModifier {
+        /// The pre modifier of the subject address.
+        from: Option<StreetNamePreModifier>,
+        /// The pre modifier of the comparison address.
+        to: Option<StreetNamePreModifier>,
+    },
+    /// Represents a mismatch in the street name pre type.
+    PreType {
+        /// The pre type of the subject address.
+        from: Option<StreetNamePreType>,
+        /// The pre type of the comparison address.
+        to: Option<StreetNamePreType>,
+    },
+    /// Represents a mismatch in the street name separator.
+    Separator {
+        /// The separator of the subject address.
+        from: Option<StreetSeparator>,
+        /// The separator of the comparison address.
+        to: Option<StreetSeparator>,
+    },
+    /// Represents a mismatch in the street name.
+    StreetName {
+        /// The street name of the subject address.
+        from: String,
+        /// The street name of the comparison address.
+        to: String,
+    },
+    /// Represents a mismatch in the street name post type.
+    StreetType {
+        /// The post type of the subject address.
+        from: Option<StreetNamePostType>,
+        /// The post type of the comparison address.
+        to: Option<StreetNamePostType>,
+    },
     /// Represents a mismatch in the subaddress type.
-    SubaddressType(String),
+    SubaddressType {
+        /// The subaddress type of the subject address.
+        from: Option<SubaddressType>,
+        /// The subaddress type of the comparison address.
+        to: Option<SubaddressType>,
+    },
+    /// Represents a mismatch in the subaddress identifier.
+    SubaddressId {
+        /// The subaddress identifier of the subject address.
+        from: Option<String>,
+        /// The subaddress identifier of the comparison address.
+        to: Option<String>,
+    },
     /// Represents a mismatch in the floor number.
-    Floor(String),
+    Floor {
+        /// The floor of the subject address.
+        from: Option<i64>,
+        /// The floor of the comparison address.
+        to: Option<i64>,
+    },
     /// Represents a mismatch in the building identifier.
-    Building(String),
+    Building {
+        /// The building identifier of the subject address.
+        from: Option<String>,
+        /// The building identifier of the comparison address.
+        to: Option<String>,
+    },
+    /// Represents a mismatch in the postal zip code.
+    Zip {
+        /// The zip code of the subject address.
+        from: i64,
+        /// The zip code of the comparison address.
+        to: i64,
+    },
+    /// Represents a mismatch in the postal community.
+    PostalCommunity {
+        /// The postal community of the subject address.
+        from: String,
+        /// The postal community of the comparison address.
+        to: String,
+    },
+    /// Represents a mismatch in the state name.
+    State {
+        /// The state of the subject address.
+        from: State,
+        /// The state of the comparison address.
+        to: State,
+    },
     /// Represents a mismatch in the address status.
-    Status(String),
+    Status {
+        /// The status of the subject address.
+        from: AddressStatus,
+        /// The status of the comparison address.
+        to: AddressStatus,
+    },
 }
 
 impl Mismatch {
-    /// The `subaddress_type` method captures information about the mismatch between subaddress
-    /// type fields as a message contained in the enum variant.
+    /// The `subaddress_type` method captures the mismatch between subaddress type fields.
     pub fn subaddress_type(from: Option<SubaddressType>, to: Option<SubaddressType>) -> Self {
-        let message = format!("{:?} not equal to {:?}", from, to);
-        Self::SubaddressType(message)
+        Self::SubaddressType { from, to }
     }
 
-    /// The `floor` method captures information about the mismatch between the `floor` fields as a message contained in the enum variant.
+    /// The `floor` method captures the mismatch between the `floor` fields.
     pub fn floor(from: Option<i64>, to: Option<i64>) -> Self {
-        let message = format!("{:?} not equal to {:?}", from, to);
-        Self::Floor(message)
+        Self::Floor { from, to }
     }
 
-    /// The `building` method captures information about the mismatch between the `building` fields as a message contained in the enum variant.
+    /// The `building` method captures the mismatch between the `building` fields.
     pub fn building(from: Option<String>, to: Option<String>) -> Self {
-        let message = format!("{:?} not equal to {:?}", from, to);
-        Self::Building(message)
+        Self::Building { from, to }
     }
 
-    /// The `status` method captures information about the mismatch between the `status` fields as a message contained in the enum variant.
+    /// The `status` method captures the mismatch between the `status` fields.
     pub fn status(from: AddressStatus, to: AddressStatus) -> Self {
-        let message = format!("{} not equal to {}", from, to);
-        Self::Status(message)
+        Self::Status { from, to }
+    }
+
+    /// The `component` method returns the [`AddressComponent`] where the mismatch occurs.
+    pub fn component(&self) -> AddressComponent {
+        match self {
+            Self::Number { .. } => AddressComponent::Number,
+            Self::NumberSuffix { .. } => AddressComponent::NumberSuffix,
+            Self::Directional { .. } => AddressComponent::Directional,
+            Self::PreModifier { .. } => AddressComponent::PreModifier,
+            Self::PreType { .. } => AddressComponent::PreType,
+            Self::Separator { .. } => AddressComponent::Separator,
+            Self::StreetName { .. } => AddressComponent::StreetName,
+            Self::StreetType { .. } => AddressComponent::StreetType,
+            Self::SubaddressType { .. } => AddressComponent::SubaddressType,
+            Self::SubaddressId { .. } => AddressComponent::SubaddressId,
+            Self::Floor { .. } => AddressComponent::Floor,
+            Self::Building { .. } => AddressComponent::Building,
+            Self::Zip { .. } => AddressComponent::Zip,
+            Self::PostalCommunity { .. } => AddressComponent::PostalCommunity,
+            Self::State { .. } => AddressComponent::State,
+            Self::Status { .. } => AddressComponent::Status,
+        }
+    }
+
+    /// The `severity` method returns the [`MismatchSeverity`] of the mismatch.
+    pub fn severity(&self) -> MismatchSeverity {
+        match self {
+            Self::Number { .. }
+            | Self::Directional { .. }
+            | Self::StreetName { .. }
+            | Self::SubaddressId { .. } => MismatchSeverity::Major,
+            Self::NumberSuffix { .. }
+            | Self::PreModifier { .. }
+            | Self::PreType { .. }
+            | Self::Separator { .. }
+            | Self::StreetType { .. }
+            | Self::Zip { .. }
+            | Self::PostalCommunity { .. }
+            | Self::State { .. } => MismatchSeverity::Moderate,
+            Self::SubaddressType { .. }
+            | Self::Floor { .. }
+            | Self::Building { .. }
+            | Self::Status { .. } => MismatchSeverity::Minor,
+        }
+    }
+
+    /// The `values` method returns the value of the subject address and the value of the
+    /// comparison address as text.  Missing values are empty.
+    pub fn values(&self) -> (String, String) {
+        fn text<T: std::fmt::Display>(value: &Option<T>) -> String {
+            value
+                .as_ref()
+                .map(|value| value.to_string())
+                .unwrap_or_default()
+        }
+        match self {
+            Self::Number { from, to } | Self::Zip { from, to } => {
+                (from.to_string(), to.to_string())
+            }
+            Self::StreetName { from, to } | Self::PostalCommunity { from, to } => {
+                (from.clone(), to.clone())
+            }
+            Self::NumberSuffix { from, to }
+            | Self::SubaddressId { from, to }
+            | Self::Building { from, to } => (text(from), text(to)),
+            Self::Directional { from, to } => (text(from), text(to)),
+            Self::PreModifier { from, to } => (text(from), text(to)),
+            Self::PreType { from, to } => (text(from), text(to)),
+            Self::Separator { from, to } => (text(from), text(to)),
+            Self::StreetType { from, to } => (text(from), text(to)),
+            Self::SubaddressType { from, to } => (text(from), text(to)),
+            Self::Floor { from, to } => (text(from), text(to)),
+            Self::State { from, to } => (from.to_string(), to.to_string()),
+            Self::Status { from, to } => (from.to_string(), to.to_string()),
+        }
+    }
+
+    /// The `message` method describes the mismatch as text, in the form "{from} not equal to
+    /// {to}", using the values from [`Mismatch::values`].
+    pub fn message(&self) -> String {
+        let (from, to) = self.values();
+        format!("{} not equal to {}", from, to)
+    }
+}
+
+impl std::fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.component(), self.message())
     }
 }
 
@@ -75,8 +300,9 @@ impl Mismatches {
     }
 }
 
-/// The `AddressMatch` is an intermediary data structure used internally to aggregate match information from
-/// comparing types that implement [`crate::Addresses`], for the purpose of producing [`MatchRecords`].
+/// The `AddressMatch` is an intermediary data structure used internally to aggregate match
+/// information from comparing types that implement [`crate::Addresses`], for the purpose of
+/// producing [`MatchRecords`].
 #[derive(Debug, Default, Clone, PartialEq, PartialOrd, Eq, Ord, Hash, Serialize, Deserialize)]
 pub struct AddressMatch {
     /// The `coincident` field indicates the compared addresses refer to the same location, or are
@@ -103,13 +329,31 @@ impl AddressMatch {
 }
 
 /// The `MatchStatus` enum delineates whether a given address has a match (the `Matching` variant),
-/// has a match but differs in some descriptive fields (the `Divergent` variant), or does not have
-/// a match in the comparison set (the `Missing` variant).
+/// has a match but differs in some descriptive fields (the `Divergent` variant), has a match at a
+/// distant location (the `Displaced` variant), or does not have a match in the comparison set
+/// (the `Missing` variant).
 ///
 /// We have derived Default using the Missing variant, mostly so structs that take a `MatchStatus`
 /// as a field can also derive default.  Properly speaking, there is no meaningful default for this
 /// struct, but if you need to create one first and fill it in later, you can.
-#[derive(Debug, Default, Clone, PartialEq, PartialOrd, Eq, Ord, Hash, Serialize, Deserialize)]
+///
+/// The variant name, returned by `as_ref` and `to_string` as in the serialized form, is part of
+/// the key of every match record id, so renaming a variant changes the ids of its records.  See
+/// [`crate::record_id`].
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    PartialOrd,
+    Eq,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    strum::AsRefStr,
+    strum::Display,
+)]
 pub enum MatchStatus {
     /// The `Matching` variant indicates an address has an exact match in the comparison set.
     Matching,
@@ -117,11 +361,51 @@ pub enum MatchStatus {
     /// the address contains fields with different values than in the comparison (e.g. the
     /// address has status 'Retired' compared to 'Current').
     Divergent,
+    /// The `Displaced` variant indicates an address has a match in the comparison set, but the
+    /// address points lie farther apart than the distance threshold of a spatial comparison,
+    /// which usually means the address was assigned twice.  See
+    /// [`MatchRecords::new_with_distance`].
+    Displaced,
     #[default]
     /// The `Missing` variant indicates the address does not have a match in the comparison set.
     Missing,
 }
 
+/// The `Cardinality` enum classifies the outcome of a subject address by the number of addresses
+/// it matches in the comparison set, and the number of subject addresses matching the same
+/// comparison address.
+#[derive(
+    Debug,
+    Default,
+    Copy,
+    Clone,
+    PartialEq,
+    PartialOrd,
+    Eq,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    strum::Display,
+    strum::EnumString,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum Cardinality {
+    /// The subject address has no match in the comparison set.
+    #[default]
+    Unmatched,
+    /// The subject address matches a single address, which matches no other subject address.
+    OneToOne,
+    /// The subject address is a parent address without a unit, matching the units at the site.
+    OneToMany,
+    /// The subject address matches a single address, which also matches other subject addresses.
+    ManyToOne,
+    /// The subject address matches several addresses that are not units of a parent address, or
+    /// shares its matches with other subject addresses.
+    Ambiguous,
+}
+
 /// A `MatchRecord` reports the match results for a single address compared against a set of
 /// addresses.  Designed to plot and diagnose missing and divergent addresses.
 #[derive(Debug, Default, Clone, PartialEq, PartialOrd, Serialize, Deserialize)]
@@ -133,7 +417,8 @@ pub struct MatchRecord {
     /// The `subaddress_type` field indicates a difference in subaddress type between a subject
     /// address and its match, if present.  E.g. "SUITE" does not match "APARTMENT".
     pub subaddress_type: Option<String>,
-    /// The `floor` field indicates the subject address and its match, if present, have different floor numbers.
+    /// The `floor` field indicates the subject address and its match, if present, have different
+    /// floor numbers.
     pub floor: Option<String>,
     /// The `building` field indicates the subject address and its match, if present, have
     /// different building identifiers.
@@ -141,14 +426,140 @@ pub struct MatchRecord {
     /// The `status` field indicates the subject address and its match, if present, have different
     /// values for the address status. E.g. "Current" does not match "Other".
     pub status: Option<String>,
+    /// The `components` field lists the mismatched components of a divergent record, separated by
+    /// semicolons.  The values of each component are reported by [`MismatchRecords`].
+    #[serde(default)]
+    pub components: Option<String>,
+    /// The `mismatches` field holds the [`Mismatch`] for each mismatched component of a divergent
+    /// record.  Not written to csv, see [`MismatchRecords`].
+    #[serde(skip)]
+    pub mismatches: Vec<Mismatch>,
+    /// The `severity` field is the highest [`MismatchSeverity`] among the mismatches of a
+    /// divergent record.
+    #[serde(default)]
+    pub severity: Option<MismatchSeverity>,
+    /// The `cardinality` field classifies the outcome of the subject address.  See
+    /// [`MatchRecords::classify`].
+    #[serde(default)]
+    pub cardinality: Cardinality,
+    /// The `target_count` field is the number of addresses in the comparison set matching the
+    /// subject address.
+    #[serde(default)]
+    pub target_count: usize,
+    /// The `source_count` field is the number of subject addresses matching the same address in
+    /// the comparison set as this record, taking the largest count where the subject address has
+    /// several matches.
+    #[serde(default)]
+    pub source_count: usize,
+    /// The `distance` field is the distance between the subject address and its match, reported
+    /// by spatial comparisons.  See [`MatchRecords::new_with_distance`].
+    #[serde(default)]
+    pub distance: Option<f64>,
     /// The `longitude` field represents the 'x' value of the address point.  Depending on the
     /// input from the caller, the value may be in decimal degrees, meters or feet.
     pub longitude: f64,
     /// The `latitude` field represents the 'y' value of the address point.  Depending on the
     /// input from the caller, the value may be in decimal degrees, meters or feet.
     pub latitude: f64,
-    /// The `id` field is an internal unique id.
+    /// The `id` field is a unique id derived from the identity of the subject address, the match
+    /// status and the identity of the matching address, so that repeated runs on the same inputs
+    /// produce the same id.  When comparing a set of subject addresses, repeated subject addresses
+    /// are told apart by their occurrence.  See [`crate::record_id`] and
+    /// [`crate::record_identities`].
     pub id: uuid::Uuid,
+    /// The `source_id` field is the native identifier of the subject address, if any.  See
+    /// [`Address::identifier`].
+    #[serde(default)]
+    pub source_id: Option<String>,
+    /// The `target_label` field is the text representation of the matching address in the
+    /// comparison set, if present.
+    #[serde(default)]
+    pub target_label: Option<String>,
+    /// The `target_longitude` field represents the 'x' value of the matching address point, if
+    /// present.
+    #[serde(default)]
+    pub target_longitude: Option<f64>,
+    /// The `target_latitude` field represents the 'y' value of the matching address point, if
+    /// present.
+    #[serde(default)]
+    pub target_latitude: Option<f64>,
+    /// The `target_id` field is the native identifier of the matching address, if any.  E.g. the
+    /// GlobalID of a Grants Pass address, or the taxlot or object id of a county address.
+    #[serde(default)]
+    pub target_id: Option<String>,
+    /// The `candidate_label` field is the text representation of the most similar address in the
+    /// comparison set, reported for missing addresses by [`MatchRecords::compare_fuzzy`].
+    #[serde(default)]
+    pub candidate_label: Option<String>,
+    /// The `candidate_score` field is the total [`crate::SimilarityScore`] of the candidate in
+    /// `candidate_label`.
+    #[serde(default)]
+    pub candidate_score: Option<f64>,
+    /// The `source_identity` field is the identity of the subject address used in the record id.
+    /// Not written to csv.
+    #[serde(skip)]
+    pub source_identity: Option<String>,
+}
+
+impl MatchRecord {
+    /// The `source_key` method returns the identity of the subject address, using the identity
+    /// from the record id if present, then the native identifier, otherwise the address label
+    /// and coordinates.
+    fn source_key(&self) -> String {
+        if let Some(identity) = &self.source_identity {
+            return identity.clone();
+        }
+        match &self.source_id {
+            Some(id) => id.clone(),
+            None => format!(
+                "{} ({}, {})",
+                self.address_label, self.longitude, self.latitude
+            ),
+        }
+    }
+
+    /// The `target_key` method returns the identity of the matching address, if present, using
+    /// the native identifier if present, otherwise the address label and coordinates.
+    fn target_key(&self) -> Option<String> {
+        match (&self.target_id, &self.target_label) {
+            (Some(id), _) => Some(id.clone()),
+            (None, Some(label)) => Some(format!(
+                "{} ({:?}, {:?})",
+                label, self.target_longitude, self.target_latitude
+            )),
+            (None, None) => None,
+        }
+    }
+
+    /// The `set_mismatch` method records `mismatch` in the `mismatches` and `components` fields,
+    /// along with the message in the field for the subaddress type, floor, building or status,
+    /// and raises the `severity` field to the severity of `mismatch` if it is higher.
+    pub fn set_mismatch(&mut self, mismatch: &Mismatch) {
+        let message = Some(mismatch.message());
+        match mismatch.component() {
+            AddressComponent::SubaddressType => self.subaddress_type = message,
+            AddressComponent::Floor => self.floor = message,
+            AddressComponent::Building => self.building = message,
+            AddressComponent::Status => self.status = message,
+            _ => {}
+        }
+        let component = mismatch.component().to_string();
+        self.components = match self.components.take() {
+            Some(components) => Some(format!("{};{}", components, component)),
+            None => Some(component),
+        };
+        self.mismatches.push(mismatch.clone());
+        self.severity = self.severity.max(Some(mismatch.severity()));
+    }
+
+    /// The `has_mismatch` method returns true if the record lists `component` as mismatched.
+    pub fn has_mismatch(&self, component: AddressComponent) -> bool {
+        self.components.as_ref().is_some_and(|components| {
+            components
+                .split(';')
+                .any(|name| name == component.to_string())
+        })
+    }
 }
 
 impl Geographic for MatchRecord {
@@ -161,6 +572,10 @@ impl Geographic for MatchRecord {
     }
 }
 
+/// The `Distance` type is a function measuring the distance between a subject address and a
+/// matching address, used by [`MatchRecords::new_with_distance`].
+pub type Distance<'a, T, U> = &'a dyn Fn(&T, &U) -> f64;
+
 /// The `MatchRecords` struct holds a vector of type [`MatchRecord`].
 #[derive(Debug, Default, Clone, PartialEq, PartialOrd, Serialize, Deserialize, Deref, DerefMut)]
 pub struct MatchRecords(Vec<MatchRecord>);
@@ -170,126 +585,405 @@ impl MatchRecords {
     /// addresses, and returns the `MatchRecords` for the subject address.  A subject address can
     /// match against multiple candidates (e.g. a parent address will match against all
     /// subaddresses associated with the parent), so the result type must potentially accommodate
-    /// multiple records.
+    /// multiple records.  Uses the default [`MatchPolicy`].
     pub fn new<T: Address + Geographic, U: Address + Geographic>(
         self_address: &T,
         other_addresses: &[U],
+    ) -> Self {
+        Self::new_with_policy(self_address, other_addresses, &MatchPolicy::default())
+    }
+
+    /// The `new_with_policy` method compares a single subject address against a set of
+    /// addresses as in [`MatchRecords::new`], using `policy` to determine which components must
+    /// agree.
+    pub fn new_with_policy<T: Address + Geographic, U: Address + Geographic>(
+        self_address: &T,
+        other_addresses: &[U],
+        policy: &MatchPolicy,
+    ) -> Self {
+        Self::matches(self_address, other_addresses, policy, None, None)
+    }
+
+    /// The `new_with_distance` method compares a single subject address against a set of
+    /// addresses as in [`MatchRecords::new_with_policy`], reporting the `distance` between the
+    /// subject address and each match.  Matches farther apart than `threshold` have the
+    /// [`MatchStatus::Displaced`] status.  Records are ordered from the nearest match, with
+    /// matches at an unknown distance (e.g. missing coordinates) last.  When the subject address
+    /// matches several addresses that are not units of the subject address, every match is kept
+    /// and reported as [`Cardinality::Ambiguous`].
+    pub fn new_with_distance<T: Address + Geographic, U: Address + Geographic>(
+        self_address: &T,
+        other_addresses: &[U],
+        policy: &MatchPolicy,
+        threshold: f64,
+        distance: Distance<T, U>,
+    ) -> Self {
+        Self::matches(
+            self_address,
+            other_addresses,
+            policy,
+            Some((threshold, distance)),
+            None,
+        )
+    }
+
+    /// The `matches` method implements [`MatchRecords::new_with_policy`] and
+    /// [`MatchRecords::new_with_distance`], with the distance threshold and function in
+    /// `spatial` for spatial comparisons.  When comparing a set of subject addresses, `identity`
+    /// is the identity of the subject address from [`record_identities`], so that repeated
+    /// subject addresses receive different ids.
+    fn matches<T: Address + Geographic, U: Address + Geographic>(
+        self_address: &T,
+        other_addresses: &[U],
+        policy: &MatchPolicy,
+        spatial: Option<(f64, Distance<T, U>)>,
+        identity: Option<&str>,
     ) -> Self {
         let address_label = self_address.label();
         let latitude = self_address.latitude();
         let longitude = self_address.longitude();
-        let id = uuid::Uuid::new_v4();
+        let source = identity
+            .map(str::to_string)
+            .unwrap_or_else(|| record_identity(self_address));
+
+        let mut record = MatchRecord {
+            address_label,
+            longitude,
+            latitude,
+            source_id: self_address.identifier(),
+            source_identity: Some(source.clone()),
+            ..Default::default()
+        };
 
         let mut match_record = Vec::new();
+        // True while every match is a unit of the subject address.
+        let mut units = self_address.subaddress_id().is_none() && self_address.building().is_none();
 
         for address in other_addresses {
-            let address_match = self_address.coincident(address);
+            let address_match = self_address.coincident_with(address, policy);
             if address_match.coincident {
-                let mut subaddress_type = None;
-                let mut floor = None;
-                let mut building = None;
-                let mut status = None;
+                units &= address.subaddress_id().is_some() || address.building().is_some();
+                let mut matched = record.clone();
+                matched.target_label = Some(address.label());
+                matched.target_longitude = Some(address.longitude());
+                matched.target_latitude = Some(address.latitude());
+                matched.target_id = address.identifier();
                 match address_match.mismatches {
-                    None => match_record.push(MatchRecord {
-                        match_status: MatchStatus::Matching,
-                        address_label: address_label.clone(),
-                        subaddress_type,
-                        floor,
-                        building,
-                        status,
-                        longitude,
-                        latitude,
-                        id,
-                    }),
+                    None => matched.match_status = MatchStatus::Matching,
                     Some(mismatches) => {
+                        matched.match_status = MatchStatus::Divergent;
                         for mismatch in mismatches.iter() {
-                            match mismatch {
-                                Mismatch::SubaddressType(message) => {
-                                    subaddress_type = Some(message.to_owned())
-                                }
-                                Mismatch::Floor(message) => floor = Some(message.to_owned()),
-                                Mismatch::Building(message) => building = Some(message.to_owned()),
-                                Mismatch::Status(message) => status = Some(message.to_owned()),
-                            }
+                            matched.set_mismatch(mismatch);
                         }
-                        match_record.push(MatchRecord {
-                            match_status: MatchStatus::Divergent,
-                            address_label: address_label.clone(),
-                            subaddress_type,
-                            floor,
-                            building,
-                            status,
-                            longitude,
-                            latitude,
-                            id,
-                        })
                     }
                 }
+                if let Some((threshold, distance)) = spatial {
+                    let distance = distance(self_address, address);
+                    matched.distance = Some(distance);
+                    if distance > threshold {
+                        matched.match_status = MatchStatus::Displaced;
+                    }
+                }
+                matched.id = record_id(&[
+                    "match",
+                    &source,
+                    matched.match_status.as_ref(),
+                    &record_identity(address),
+                ]);
+                match_record.push(matched);
             }
         }
+        let cardinality = match match_record.len() {
+            0 => Cardinality::Unmatched,
+            1 => Cardinality::OneToOne,
+            _ if units => Cardinality::OneToMany,
+            _ => Cardinality::Ambiguous,
+        };
+        if spatial.is_some() {
+            // Degenerate coordinates produce a NaN distance, which sorts after any number.
+            match_record.sort_by(|a, b| {
+                let a = a.distance.unwrap_or(f64::NAN);
+                let b = b.distance.unwrap_or(f64::NAN);
+                a.total_cmp(&b)
+            });
+        }
+        let target_count = match_record.len();
+        for matched in match_record.iter_mut() {
+            matched.target_count = target_count;
+            matched.source_count = 1;
+            matched.cardinality = cardinality;
+        }
         if match_record.is_empty() {
-            match_record.push(MatchRecord {
-                match_status: MatchStatus::Missing,
-                address_label,
-                subaddress_type: None,
-                floor: None,
-                building: None,
-                status: None,
-                longitude,
-                latitude,
-                id,
-            })
+            record.id = record_id(&["match", &source, record.match_status.as_ref()]);
+            match_record.push(record)
         }
         MatchRecords(match_record)
     }
 
     /// For each address in `self_addresses`, the `compare` method calculates the match record for
     /// the subject address compared against the addresses in `other_addresses`, and returns the
-    /// results in a [`MatchRecords`] struct.
+    /// results in a [`MatchRecords`] struct.  Uses the default [`MatchPolicy`].
     pub fn compare<T: Address + Geographic + Send + Sync, U: Address + Geographic + Send + Sync>(
         self_addresses: &[T],
         other_addresses: &[U],
+    ) -> Self {
+        Self::compare_with_policy(self_addresses, other_addresses, &MatchPolicy::default())
+    }
+
+    /// The `compare_with_policy` method calculates match records as in [`MatchRecords::compare`],
+    /// using `policy` to determine which components must agree.
+    pub fn compare_with_policy<
+        T: Address + Geographic + Send + Sync,
+        U: Address + Geographic + Send + Sync,
+    >(
+        self_addresses: &[T],
+        other_addresses: &[U],
+        policy: &MatchPolicy,
     ) -> Self {
         let style = indicatif::ProgressStyle::with_template(
             "[{elapsed_precise}] {bar:40.cyan/blue} {pos:>7}/{len:7} {'Comparing addresses.'}",
         )
         .unwrap();
+        let identities = record_identities(self_addresses);
         let record = self_addresses
             .par_iter()
-            .map(|address| MatchRecords::new(address, other_addresses))
+            .zip(identities.par_iter())
+            .map(|(address, identity)| {
+                MatchRecords::matches(address, other_addresses, policy, None, Some(identity))
+            })
             .progress_with_style(style)
             .collect::<Vec<MatchRecords>>();
         let mut records = Vec::new();
         for mut item in record {
             records.append(&mut item);
         }
-        MatchRecords(records)
+        let mut records = MatchRecords(records);
+        records.classify();
+        records
+    }
+
+    /// The `compare_geographic` method calculates match records as in
+    /// [`MatchRecords::compare_with_policy`], reporting the distance in meters between the
+    /// geographic coordinates of matching addresses.  Matches farther apart than `threshold`
+    /// meters have the [`MatchStatus::Displaced`] status.  See
+    /// [`MatchRecords::new_with_distance`].
+    pub fn compare_geographic<
+        T: Address + Geographic + Send + Sync,
+        U: Address + Geographic + Send + Sync,
+    >(
+        self_addresses: &[T],
+        other_addresses: &[U],
+        policy: &MatchPolicy,
+        threshold: f64,
+    ) -> Self {
+        Self::compare_with_distance(
+            self_addresses,
+            other_addresses,
+            policy,
+            threshold,
+            |a, b| a.haversine(b),
+        )
+    }
+
+    /// The `compare_cartesian` method calculates match records as in
+    /// [`MatchRecords::compare_with_policy`], reporting the distance between the projected
+    /// coordinates of matching addresses, in the units of the coordinates.  Matches farther apart
+    /// than `threshold` have the [`MatchStatus::Displaced`] status.  See
+    /// [`MatchRecords::new_with_distance`].
+    pub fn compare_cartesian<
+        T: Address + Geographic + Cartesian + Send + Sync,
+        U: Address + Geographic + Cartesian + Send + Sync,
+    >(
+        self_addresses: &[T],
+        other_addresses: &[U],
+        policy: &MatchPolicy,
+        threshold: f64,
+    ) -> Self {
+        Self::compare_with_distance(
+            self_addresses,
+            other_addresses,
+            policy,
+            threshold,
+            |a, b| a.distance(b),
+        )
+    }
+
+    /// The `compare_with_distance` method implements [`MatchRecords::compare_geographic`] and
+    /// [`MatchRecords::compare_cartesian`], using `distance` to measure the distance between
+    /// matching addresses.
+    fn compare_with_distance<
+        T: Address + Geographic + Send + Sync,
+        U: Address + Geographic + Send + Sync,
+        F: Fn(&T, &U) -> f64 + Sync,
+    >(
+        self_addresses: &[T],
+        other_addresses: &[U],
+        policy: &MatchPolicy,
+        threshold: f64,
+        distance: F,
+    ) -> Self {
+        let style = indicatif::ProgressStyle::with_template(
+            "[{elapsed_precise}] {bar:40.cyan/blue} {pos:>7}/{len:7} {'Comparing addresses.'}",
+        )
+        .unwrap();
+        let identities = record_identities(self_addresses);
+        let record = self_addresses
+            .par_iter()
+            .zip(identities.par_iter())
+            .map(|(address, identity)| {
+                MatchRecords::matches(
+                    address,
+                    other_addresses,
+                    policy,
+                    Some((threshold, &distance)),
+                    Some(identity),
+                )
+            })
+            .progress_with_style(style)
+            .collect::<Vec<MatchRecords>>();
+        let mut records = Vec::new();
+        for mut item in record {
+            records.append(&mut item);
+        }
+        let mut records = MatchRecords(records);
+        records.classify();
+        records
+    }
+
+    /// The `compare_fuzzy` method calculates match records as in
+    /// [`MatchRecords::compare_with_policy`], or as in [`MatchRecords::compare_geographic`] if
+    /// `distance` is set.  For each missing address, the most similar address in
+    /// `other_addresses` is reported in the `candidate_label` and `candidate_score` fields, if its
+    /// total similarity score is at least `threshold`.  Candidates are limited to addresses
+    /// sharing the address number or phonetic street name, see [`FuzzyIndex`].
+    pub fn compare_fuzzy<
+        T: Address + Geographic + Send + Sync,
+        U: Address + Geographic + Send + Sync,
+    >(
+        self_addresses: &[T],
+        other_addresses: &[U],
+        policy: &MatchPolicy,
+        threshold: f64,
+        distance: Option<f64>,
+    ) -> Self {
+        let index = FuzzyIndex::new(other_addresses);
+        let style = indicatif::ProgressStyle::with_template(
+            "[{elapsed_precise}] {bar:40.cyan/blue} {pos:>7}/{len:7} {'Comparing addresses.'}",
+        )
+        .unwrap();
+        let identities = record_identities(self_addresses);
+        let record = self_addresses
+            .par_iter()
+            .zip(identities.par_iter())
+            .map(|(address, identity)| {
+                let haversine = |a: &T, b: &U| a.haversine(b);
+                let spatial = distance.map(|threshold| (threshold, &haversine as Distance<T, U>));
+                let mut records = MatchRecords::matches(
+                    address,
+                    other_addresses,
+                    policy,
+                    spatial,
+                    Some(identity),
+                );
+                for record in records.iter_mut() {
+                    if record.match_status == MatchStatus::Missing
+                        && let Some((candidate, score)) =
+                            index.candidates(address, 1, threshold).first()
+                    {
+                        record.candidate_label = Some(candidate.label());
+                        record.candidate_score = Some(score.total);
+                    }
+                }
+                records
+            })
+            .progress_with_style(style)
+            .collect::<Vec<MatchRecords>>();
+        let mut records = Vec::new();
+        for mut item in record {
+            records.append(&mut item);
+        }
+        let mut records = MatchRecords(records);
+        records.classify();
+        records
+    }
+
+    /// The `classify` method sets the `source_count` and `cardinality` fields of the records by
+    /// counting the subject addresses that match each address in the comparison set.  A subject
+    /// address with a single match is one-to-one, or many-to-one if other subject addresses share
+    /// the match.  A subject address with several matches keeps the one-to-many cardinality
+    /// assigned by [`MatchRecords::new`] only if no other subject address shares its matches,
+    /// and is otherwise ambiguous.  Called by [`MatchRecords::compare`].
+    pub fn classify(&mut self) {
+        let mut sources: HashMap<String, HashSet<String>> = HashMap::new();
+        for record in self.iter() {
+            if let Some(target) = record.target_key() {
+                sources
+                    .entry(target)
+                    .or_default()
+                    .insert(record.source_key());
+            }
+        }
+        let mut source_counts: HashMap<String, usize> = HashMap::new();
+        for record in self.iter() {
+            if let Some(target) = record.target_key() {
+                let count = source_counts.entry(record.source_key()).or_default();
+                *count = (*count).max(sources[&target].len());
+            }
+        }
+        for record in self.iter_mut() {
+            if record.target_count == 0 {
+                continue;
+            }
+            let source_count = source_counts
+                .get(&record.source_key())
+                .copied()
+                .unwrap_or(0);
+            record.source_count = source_count;
+            record.cardinality = match (record.target_count, source_count) {
+                (1, 1) => Cardinality::OneToOne,
+                (1, _) => Cardinality::ManyToOne,
+                (_, 1) => record.cardinality,
+                _ => Cardinality::Ambiguous,
+            };
+        }
     }
 
     /// The `filter` method returns the subset of `MatchRecords` that meet the filter requirement.
     /// The `filter` parameter takes a string reference that can take the values "matching",
-    /// "missing", "divergent", "subaddress", "floor", "building" and "status".  When filtering by
-    /// match status, the return records contain those records where the match status equals the
-    /// filter value.  For the mismatch fields, the return records contain values where a mismatch
-    /// is present in the provided field.
+    /// "missing", "divergent", "displaced", the name of any [`AddressComponent`] in snake case
+    /// (e.g. "street_type" or "zip"), "subaddress" as an alias for "subaddress_type", or the name
+    /// of a [`MismatchSeverity`] ("minor", "moderate" or "major").  When filtering by match
+    /// status, the return records contain those records where the match status equals the filter
+    /// value.  For components, the return records contain divergent records listing the component
+    /// as mismatched.  For severities, the return records contain divergent records where the
+    /// highest severity equals the filter value.  The filter also accepts the name of a
+    /// [`Cardinality`] ("unmatched", "one_to_one", "one_to_many", "many_to_one" or
+    /// "ambiguous"), returning the records with the matching cardinality.
     pub fn filter(mut self, filter: &str) -> Self {
+        let filter = match filter {
+            "subaddress" => "subaddress_type",
+            _ => filter,
+        };
         match filter {
             "matching" => self.retain(|r| r.match_status == MatchStatus::Matching),
             "missing" => self.retain(|r| r.match_status == MatchStatus::Missing),
             "divergent" => self.retain(|r| r.match_status == MatchStatus::Divergent),
-            "subaddress" => self.retain(|r| {
-                r.match_status == MatchStatus::Divergent && r.subaddress_type.is_some()
-            }),
-            "floor" => {
-                self.retain(|r| r.match_status == MatchStatus::Divergent && r.floor.is_some())
-            }
-            "building" => {
-                self.retain(|r| r.match_status == MatchStatus::Divergent && r.building.is_some())
-            }
-            "status" => {
-                self.retain(|r| r.match_status == MatchStatus::Divergent && r.status.is_some())
+            "displaced" => self.retain(|r| r.match_status == MatchStatus::Displaced),
+            _ => {
+                if let Ok(component) = filter.parse::<AddressComponent>() {
+                    self.retain(|r| {
+                        r.match_status == MatchStatus::Divergent && r.has_mismatch(component)
+                    })
+                } else if let Ok(severity) = filter.parse::<MismatchSeverity>() {
+                    self.retain(|r| {
+                        r.match_status == MatchStatus::Divergent && r.severity == Some(severity)
+                    })
+                } else if let Ok(cardinality) = filter.parse::<Cardinality>() {
+                    self.retain(|r| r.cardinality == cardinality)
+                } else {
+                    info!("Invalid filter provided.")
+                }
             }
-            _ => info!("Invalid filter provided."),
         }
         self
     }
@@ -306,6 +1000,65 @@ impl IntoCsv<MatchRecords> for MatchRecords {
     }
 }
 
+/// The `MismatchRecord` struct reports a single mismatched component of a divergent
+/// [`MatchRecord`], holding the values of the subject and comparison addresses.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct MismatchRecord {
+    /// The `id` field is the id of the [`MatchRecord`] reporting the mismatch.
+    pub id: uuid::Uuid,
+    /// The `address_label` field is the text representation of the subject address.
+    pub address_label: String,
+    /// The `target_label` field is the text representation of the comparison address.
+    pub target_label: Option<String>,
+    /// The `component` field is the mismatched [`AddressComponent`].
+    pub component: AddressComponent,
+    /// The `severity` field is the [`MismatchSeverity`] of the mismatch.
+    pub severity: MismatchSeverity,
+    /// The `source` field is the value of the component in the subject address, empty if missing.
+    pub source: String,
+    /// The `target` field is the value of the component in the comparison address, empty if
+    /// missing.
+    pub target: String,
+}
+
+/// The `MismatchRecords` struct holds a vector of type [`MismatchRecord`].
+#[derive(Debug, Default, Clone, PartialEq, PartialOrd, Serialize, Deserialize, Deref, DerefMut)]
+pub struct MismatchRecords(Vec<MismatchRecord>);
+
+impl From<&MatchRecords> for MismatchRecords {
+    /// Creates a [`MismatchRecord`] for each mismatch of each record in `records`.  Records read
+    /// from csv do not carry their mismatches, so convert the records of a comparison.
+    fn from(records: &MatchRecords) -> Self {
+        let mut mismatches = Vec::new();
+        for record in records.iter() {
+            for mismatch in &record.mismatches {
+                let (source, target) = mismatch.values();
+                mismatches.push(MismatchRecord {
+                    id: record.id,
+                    address_label: record.address_label.clone(),
+                    target_label: record.target_label.clone(),
+                    component: mismatch.component(),
+                    severity: mismatch.severity(),
+                    source,
+                    target,
+                });
+            }
+        }
+        Self(mismatches)
+    }
+}
+
+impl IntoCsv<MismatchRecords> for MismatchRecords {
+    fn from_csv<P: AsRef<std::path::Path>>(path: P) -> Result<Self, Io> {
+        let records = from_csv(path)?;
+        Ok(Self(records))
+    }
+
+    fn to_csv<P: AsRef<std::path::Path>>(&mut self, path: P) -> Result<(), AddressErrorKind> {
+        to_csv(&mut self.0, path.as_ref().into())
+    }
+}
+
 /// The `MatchPartialRecord` struct contains match data for a [`PartialAddress`].
 #[derive(Debug, Clone, PartialEq, PartialOrd, Serialize, Deserialize)]
 pub struct MatchPartialRecord {
@@ -319,99 +1072,95 @@ pub struct MatchPartialRecord {
     longitude: Option<f64>,
     /// The `latitude` field represents the 'y' value of the matching address, if present.
     latitude: Option<f64>,
+    /// The `id` field is a unique id derived from the partial address, the match status and the
+    /// identity of the matching address.  See [`crate::record_id`].
+    #[serde(default)]
+    id: uuid::Uuid,
 }
 
 impl MatchPartialRecord {
     /// The `coincident` method attempts to match fields present in the partial address against the
     /// comparison address, returning a `MatchPartialRecord` if successful.  Returns `None` if
-    /// the match status is "missing".
+    /// the match status is "missing".  Uses the [`MatchPolicy::partial`] policy.
     pub fn coincident<T: Address + Geographic>(
         partial: &PartialAddress,
         address: &T,
     ) -> Option<MatchPartialRecord> {
-        let mut match_status = MatchStatus::Missing;
-
-        if let Some(value) = partial.address_number
-            && value == address.number()
-        {
-            match_status = MatchStatus::Matching;
-        }
-
-        if &partial.street_name_pre_directional != address.directional()
-            && match_status == MatchStatus::Matching
-        {
-            match_status = MatchStatus::Missing;
-        }
-
-        if let Some(value) = &partial.street_name
-            && value != address.street_name()
-            && match_status == MatchStatus::Matching
-        {
-            match_status = MatchStatus::Missing;
-        }
-
-        if let Some(value) = partial.street_name_post_type()
-            && let &Some(street_type) = address.street_type()
-            && value != street_type
-            && match_status == MatchStatus::Matching
-        {
-            match_status = MatchStatus::Missing;
-        }
-
-        if &partial.subaddress_identifier() != address.subaddress_id()
-            && match_status == MatchStatus::Matching
-        {
-            match_status = MatchStatus::Divergent;
-        }
-
-        if address.subaddress_id().is_none()
-            && &partial.building() != address.building()
-            && match_status == MatchStatus::Matching
-        {
-            match_status = MatchStatus::Divergent;
-        }
-
-        if address.subaddress_id().is_none()
-            && address.building().is_none()
-            && &partial.floor() != address.floor()
-            && match_status == MatchStatus::Matching
-        {
-            match_status = MatchStatus::Divergent;
-        }
+        Self::coincident_with_policy(partial, address, &MatchPolicy::partial())
+    }
 
-        if match_status != MatchStatus::Missing {
-            Some(MatchPartialRecord {
-                match_status,
-                address_label: partial.label(),
-                other_label: Some(address.label()),
-                longitude: Some(address.longitude()),
-                latitude: Some(address.latitude()),
-            })
-        } else {
-            None
+    /// The `coincident_with_policy` method matches the partial address against the comparison
+    /// address as in [`MatchPartialRecord::coincident`], using `policy` to determine which
+    /// components must agree.  A difference in a component with a [`MatchRule::Mismatch`] rule
+    /// marks the record as divergent.
+    pub fn coincident_with_policy<T: Address + Geographic>(
+        partial: &PartialAddress,
+        address: &T,
+        policy: &MatchPolicy,
+    ) -> Option<MatchPartialRecord> {
+        let mut match_status = MatchStatus::Matching;
+        for component in AddressComponent::iter() {
+            if !component.differs_partial(partial, address) {
+                continue;
+            }
+            match policy.rule(component) {
+                MatchRule::Required => return None,
+                MatchRule::Mismatch => match_status = MatchStatus::Divergent,
+                MatchRule::Ignored => {}
+            }
         }
+        let address_label = partial.label();
+        let id = record_id(&[
+            "partial",
+            &address_label,
+            match_status.as_ref(),
+            &record_identity(address),
+        ]);
+        Some(MatchPartialRecord {
+            match_status,
+            address_label,
+            other_label: Some(address.label()),
+            longitude: Some(address.longitude()),
+            latitude: Some(address.latitude()),
+            id,
+        })
     }
 
     /// The `compare` method attempts to match fields present in the partial address against a set
-    /// of comparison addresses, returning a [`MatchPartialRecords`].
+    /// of comparison addresses, returning a [`MatchPartialRecords`].  Uses the
+    /// [`MatchPolicy::partial`] policy.
     pub fn compare<T: Address + Geographic>(
         partial: &PartialAddress,
         addresses: &[T],
+    ) -> MatchPartialRecords {
+        Self::compare_with_policy(partial, addresses, &MatchPolicy::partial())
+    }
+
+    /// The `compare_with_policy` method matches the partial address against a set of comparison
+    /// addresses as in [`MatchPartialRecord::compare`], using `policy` to determine which
+    /// components must agree.
+    pub fn compare_with_policy<T: Address + Geographic>(
+        partial: &PartialAddress,
+        addresses: &[T],
+        policy: &MatchPolicy,
     ) -> MatchPartialRecords {
         let mut records = Vec::new();
         for address in addresses {
-            let coincident = MatchPartialRecord::coincident(partial, address);
+            let coincident = MatchPartialRecord::coincident_with_policy(partial, address, policy);
             if let Some(record) = coincident {
                 records.push(record);
             }
         }
         if records.is_empty() {
+            let address_label = partial.label();
+            let id = record_id(&["partial", &address_label, MatchStatus::Missing.as_ref()]);
             records.push(MatchPartialRecord {
                 match_status: MatchStatus::Missing,
-                address_label: partial.label(),
+                address_label,
                 other_label: None,
                 longitude: None,
                 latitude: None,
+                id,
             })
         }
         let compared = MatchPartialRecords(records);
@@ -447,6 +1196,11 @@ impl MatchPartialRecord {
     pub fn latitude(&self) -> Option<f64> {
         self.latitude
     }
+
+    /// The `id` method returns the value of the `id` field.
+    pub fn id(&self) -> uuid::Uuid {
+        self.id
+    }
 }
 
 /// The `MatchPartialRecords` struct holds a vector of type [`MatchPartialRecord`].
@@ -458,10 +1212,20 @@ pub struct MatchPartialRecords(Vec<MatchPartialRecord>);
 impl MatchPartialRecords {
     /// For each partial address in `self_addresses`, the `compare` method attempts to match the
     /// fields present in the partial address against the addresses in `other_addresses`, returning
-    /// a `MatchPartialRecords`.
+    /// a `MatchPartialRecords`.  Uses the [`MatchPolicy::partial`] policy.
     pub fn compare<T: Address + Geographic + Send + Sync>(
         self_addresses: &PartialAddresses,
         other_addresses: &[T],
+    ) -> Self {
+        Self::compare_with_policy(self_addresses, other_addresses, &MatchPolicy::partial())
+    }
+
+    /// The `compare_with_policy` method matches each partial address in `self_addresses` as in
+    /// [`MatchPartialRecords::compare`], using `policy` to determine which components must agree.
+    pub fn compare_with_policy<T: Address + Geographic + Send + Sync>(
+        self_addresses: &PartialAddresses,
+        other_addresses: &[T],
+        policy: &MatchPolicy,
     ) -> Self {
         let style = indicatif::ProgressStyle::with_template(
             "[{elapsed_precise}] {bar:40.cyan/blue} {pos:>7}/{len:7} {'Comparing addresses.'}",
@@ -469,7 +1233,9 @@ impl MatchPartialRecords {
         .unwrap();
         let record = self_addresses
             .par_iter()
-            .map(|address| MatchPartialRecord::compare(address, other_addresses))
+            .map(|address| {
+                MatchPartialRecord::compare_with_policy(address, other_addresses, policy)
+            })
             .progress_with_style(style)
             .collect::<Vec<MatchPartialRecords>>();
         let mut records = Vec::new();
@@ -479,10 +1245,10 @@ impl MatchPartialRecords {
         MatchPartialRecords(records)
     }
 
-    /// The `filter` method returns the subset of `PartialMatchRecords` that meet the filter requirement.
-    /// The `filter` parameter takes a string reference that can take the values "matching",
-    /// "missing", or "divergent".  The return records contain those records where the match status equals the
-    /// filter value.
+    /// The `filter` method returns the subset of `PartialMatchRecords` that meet the filter
+    /// requirement.  The `filter` parameter takes a string reference that can take the values
+    /// "matching", "missing", or "divergent".  The return records contain those records where the
+    /// match status equals the filter value.
     pub fn filter(mut self, filter: &str) -> Self {
         match filter {
             "missing" => self.retain(|r| r.match_status == MatchStatus::Missing),
@@ -0,0 +1,570 @@
+//! The `fuzzy` module contains types and methods for scoring the similarity of addresses that do
+//! not match exactly, such as street names with typos or unit designations recorded as a number
+//! suffix.
+use crate::{
+    Address, AddressComponent, AddressErrorKind, Cartesian, Geographic, IntoCsv, Io, MatchPolicy,
+    MatchRule, from_csv, to_csv,
+};
+use derive_more::{Deref, DerefMut};
+use indicatif::ParallelProgressIterator;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Weight of the address number component in the total similarity score.
+const NUMBER_WEIGHT: f64 = 0.3;
+/// Weight of the street name pre directional component in the total similarity score.
+const DIRECTIONAL_WEIGHT: f64 = 0.1;
+/// Weight of the street name component in the total similarity score.
+const STREET_NAME_WEIGHT: f64 = 0.4;
+/// Minimum street name contribution to the total similarity score when the street names share a
+/// phonetic code.
+const PHONETIC_SIMILARITY: f64 = 0.9;
+/// Weight of the street name post type component in the total similarity score.
+const POST_TYPE_WEIGHT: f64 = 0.1;
+/// Weight of the subaddress component in the total similarity score.
+const SUBADDRESS_WEIGHT: f64 = 0.1;
+
+/// The `SimilarityScore` struct holds a component-wise comparison of two addresses.  Each
+/// component score ranges from 0.0 (no similarity) to 1.0 (identical), and the `total` field is
+/// the weighted sum of the components.
+#[derive(Debug, Default, Copy, Clone, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct SimilarityScore {
+    /// The `number` field is the normalized edit distance similarity between the address
+    /// numbers, 1.0 when equal, so that a mistyped digit still scores well.
+    pub number: f64,
+    /// The `directional` field is 1.0 when the street name pre directionals are equal, 0.5 when
+    /// only one address has a directional, and 0.0 when they differ.
+    pub directional: f64,
+    /// The `street_name` field is the normalized edit distance similarity between the street
+    /// names.
+    pub street_name: f64,
+    /// The `phonetic` field is 1.0 when the street names share a phonetic code, and 0.0
+    /// otherwise.  When the street names sound alike, the street name contributes at least 0.9 of
+    /// its weight to the total.
+    pub phonetic: f64,
+    /// The `post_type` field is 1.0 when the street name post types are equal, 0.5 when only one
+    /// address has a post type, and 0.0 when they differ.
+    pub post_type: f64,
+    /// The `subaddress` field is 1.0 when the units are equivalent, 0.5 when only one address has
+    /// a unit, and 0.0 when they differ.  An address number suffix is treated as a unit, so "101
+    /// A" is equivalent to "101 #A".
+    pub subaddress: f64,
+    /// The `total` field is the weighted sum of the component scores.
+    pub total: f64,
+}
+
+impl SimilarityScore {
+    /// Creates a new `SimilarityScore` comparing address `a` against address `b`.
+    pub fn new<T: Address, U: Address>(a: &T, b: &U) -> Self {
+        let number = strsim::normalized_damerau_levenshtein(
+            &a.number().to_string(),
+            &b.number().to_string(),
+        );
+        let directional = optional_score(a.directional(), b.directional());
+        let street_name = street_similarity(a, b);
+        let phonetic = if a.phonetic_common_street_name() == b.phonetic_common_street_name() {
+            1.0
+        } else {
+            0.0
+        };
+        let post_type = optional_score(a.street_type(), b.street_type());
+        let subaddress = optional_score(&unit(a), &unit(b));
+        let total = number * NUMBER_WEIGHT
+            + directional * DIRECTIONAL_WEIGHT
+            + street_name.max(phonetic * PHONETIC_SIMILARITY) * STREET_NAME_WEIGHT
+            + post_type * POST_TYPE_WEIGHT
+            + subaddress * SUBADDRESS_WEIGHT;
+        Self {
+            number,
+            directional,
+            street_name,
+            phonetic,
+            post_type,
+            subaddress,
+            total,
+        }
+    }
+
+    /// The `is_unit_of_parent` method returns `true` if every component other than the unit is
+    /// identical and only one of the two addresses has a unit, meaning one address is a unit of
+    /// the other.
+    pub fn is_unit_of_parent(&self) -> bool {
+        self.number == 1.0
+            && self.directional == 1.0
+            && self.street_name == 1.0
+            && self.post_type == 1.0
+            && self.subaddress == 0.5
+    }
+
+    /// The `reasons` method returns a description of each component where address `a` and address
+    /// `b` are similar but not identical, used to explain why two addresses were paired.
+    pub fn reasons<T: Address, U: Address>(&self, a: &T, b: &U) -> Vec<String> {
+        let mut reasons = Vec::new();
+        if self.number < 1.0 {
+            reasons.push(format!("number {} differs from {}", a.number(), b.number()));
+        }
+        if self.directional < 1.0 {
+            reasons.push(format!(
+                "directional {:?} differs from {:?}",
+                a.directional(),
+                b.directional()
+            ));
+        }
+        if a.common_street_name() != b.common_street_name() {
+            reasons.push(format!(
+                "street name {} similar to {} ({:.2})",
+                a.common_street_name(),
+                b.common_street_name(),
+                self.street_name
+            ));
+            if self.phonetic == 1.0 {
+                reasons.push(format!(
+                    "street name {} sounds like {}",
+                    a.common_street_name(),
+                    b.common_street_name()
+                ));
+            }
+        }
+        if self.post_type < 1.0 {
+            reasons.push(format!(
+                "post type {:?} differs from {:?}",
+                a.street_type(),
+                b.street_type()
+            ));
+        }
+        if a.number_suffix() != b.number_suffix() && self.subaddress == 1.0 {
+            reasons.push(format!(
+                "unit {} equivalent to {}",
+                a.complete_address_number(),
+                b.complete_address_number()
+            ));
+        } else if self.subaddress < 1.0 {
+            reasons.push(format!("unit {:?} differs from {:?}", unit(a), unit(b)));
+        }
+        reasons
+    }
+}
+
+/// The `optional_score` function compares two optional values, returning 1.0 if equal, 0.5 if
+/// only one value is present and 0.0 if they differ.
+fn optional_score<T: PartialEq>(a: &Option<T>, b: &Option<T>) -> f64 {
+    match (a, b) {
+        (Some(x), Some(y)) if x == y => 1.0,
+        (None, None) => 1.0,
+        (Some(_), Some(_)) => 0.0,
+        _ => 0.5,
+    }
+}
+
+/// The `street_similarity` function returns the normalized Damerau-Levenshtein similarity of the
+/// street names of `a` and `b`.  The names are compared both with and without the abbreviated
+/// post type, and the greater similarity is returned, so that "BEAVILLA VIEW" scores well against
+/// "BEAVILLA VW DR".
+fn street_similarity<T: Address, U: Address>(a: &T, b: &U) -> f64 {
+    let name =
+        strsim::normalized_damerau_levenshtein(&a.common_street_name(), &b.common_street_name());
+    let full = strsim::normalized_damerau_levenshtein(&street_with_type(a), &street_with_type(b));
+    name.max(full)
+}
+
+/// The `street_with_type` function returns the common street name of the address followed by the
+/// abbreviated post type, if present.
+fn street_with_type<T: Address>(address: &T) -> String {
+    match address.street_type() {
+        Some(post_type) => format!(
+            "{} {}",
+            address.common_street_name(),
+            post_type.abbreviate()
+        ),
+        None => address.common_street_name(),
+    }
+}
+
+/// The `unit` function returns a normalized unit designation for the address, combining the
+/// address number suffix and the subaddress identifier.  Returns `None` if the address has
+/// neither.
+fn unit<T: Address>(address: &T) -> Option<String> {
+    let parts = [address.number_suffix(), address.subaddress_id()]
+        .into_iter()
+        .flatten()
+        .map(|part| part.trim().trim_start_matches('#').to_uppercase())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<String>>();
+    if parts.is_empty() {
+        None
+    } else {
+        Some(parts.join(" "))
+    }
+}
+
+/// The `NearDuplicate` struct holds a pair of addresses from the same set that do not share a
+/// label, but are similar enough that they may refer to the same location.
+#[derive(Debug, Clone, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct NearDuplicate<T> {
+    /// The `left` field holds the first address of the pair.
+    pub left: T,
+    /// The `right` field holds the second address of the pair.
+    pub right: T,
+    /// The `score` field holds the similarity score of the pair.
+    pub score: SimilarityScore,
+    /// The `distance` field holds the distance between the address points, when coordinates are
+    /// available.
+    pub distance: Option<f64>,
+    /// The `reasons` field describes why the addresses were paired.
+    pub reasons: Vec<String>,
+}
+
+impl<T: Address + Clone> NearDuplicate<T> {
+    /// The `compare` method scores `left` against `right`, returning a `NearDuplicate` if the
+    /// labels differ, the units do not conflict and the total score is at least `threshold`.  A
+    /// parent address and one of its units, such as "100 MAIN ST" and "100 MAIN ST APT 1", differ
+    /// only in the unit and are not reported.
+    pub fn compare(left: &T, right: &T, threshold: f64) -> Option<Self> {
+        if left.label() == right.label() {
+            return None;
+        }
+        // Distinct units at the same address are expected, not duplicates.
+        if let (Some(a), Some(b)) = (unit(left), unit(right))
+            && a != b
+        {
+            return None;
+        }
+        let score = SimilarityScore::new(left, right);
+        if score.total < threshold || score.is_unit_of_parent() {
+            return None;
+        }
+        let reasons = score.reasons(left, right);
+        Some(Self {
+            left: left.clone(),
+            right: right.clone(),
+            score,
+            distance: None,
+            reasons,
+        })
+    }
+}
+
+/// The `NearDuplicates` struct holds a vector of type [`NearDuplicate`].
+#[derive(Debug, Default, Clone, PartialEq, PartialOrd, Serialize, Deserialize, Deref, DerefMut)]
+pub struct NearDuplicates<T>(Vec<NearDuplicate<T>>);
+
+impl<T: Address + Clone> NearDuplicates<T> {
+    /// The `from_addresses` method pairs addresses in `addresses` with a similarity score of at
+    /// least `threshold`.  Candidate pairs are blocked by address number, so only addresses with
+    /// the same number are compared.  Pairs are sorted by descending score.
+    pub fn from_addresses(addresses: &[T], threshold: f64) -> Self {
+        let mut blocks: HashMap<i64, Vec<usize>> = HashMap::new();
+        for (i, address) in addresses.iter().enumerate() {
+            blocks.entry(address.number()).or_default().push(i);
+        }
+        let mut blocks = blocks.into_values().collect::<Vec<Vec<usize>>>();
+        blocks.sort();
+        let mut pairs = Vec::new();
+        for block in blocks {
+            for (i, left) in block.iter().enumerate() {
+                for right in &block[i + 1..] {
+                    if let Some(pair) =
+                        NearDuplicate::compare(&addresses[*left], &addresses[*right], threshold)
+                    {
+                        pairs.push(pair);
+                    }
+                }
+            }
+        }
+        pairs.sort_by(|a, b| b.score.total.total_cmp(&a.score.total));
+        Self(pairs)
+    }
+
+    /// The `within` method pairs addresses as in [`NearDuplicates::from_addresses`], retaining
+    /// only pairs where the address points are no more than `distance` apart.  The distance is in
+    /// the units of the address coordinates, and is recorded on each pair.
+    pub fn within(addresses: &[T], threshold: f64, distance: f64) -> Self
+    where
+        T: Cartesian,
+    {
+        let mut pairs = Self::from_addresses(addresses, threshold);
+        pairs.retain_mut(|pair| {
+            let delta = pair.left.distance(&pair.right);
+            if delta <= distance {
+                pair.distance = Some(delta);
+                pair.reasons
+                    .push(format!("within {:.0} of each other", delta));
+                true
+            } else {
+                false
+            }
+        });
+        pairs
+    }
+}
+
+/// The `NearDuplicateRecord` struct represents a single [`NearDuplicate`] pair, designed to
+/// export to csv.
+#[derive(Debug, Default, Clone, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct NearDuplicateRecord {
+    /// The `left_label` field is the label of the first address of the pair.
+    pub left_label: String,
+    /// The `right_label` field is the label of the second address of the pair.
+    pub right_label: String,
+    /// The `score` field is the total similarity score of the pair.
+    pub score: f64,
+    /// The `number` field is the address number component of the similarity score.
+    pub number: f64,
+    /// The `directional` field is the directional component of the similarity score.
+    pub directional: f64,
+    /// The `street_name` field is the street name component of the similarity score.
+    pub street_name: f64,
+    /// The `phonetic` field is the phonetic street name component of the similarity score.
+    pub phonetic: f64,
+    /// The `post_type` field is the post type component of the similarity score.
+    pub post_type: f64,
+    /// The `subaddress` field is the subaddress component of the similarity score.
+    pub subaddress: f64,
+    /// The `distance` field is the distance between the address points, if available.
+    pub distance: Option<f64>,
+    /// The `reasons` field lists the reasons the addresses were paired, separated by semicolons.
+    pub reasons: String,
+}
+
+impl<T: Address> From<&NearDuplicate<T>> for NearDuplicateRecord {
+    fn from(pair: &NearDuplicate<T>) -> Self {
+        Self {
+            left_label: pair.left.label(),
+            right_label: pair.right.label(),
+            score: pair.score.total,
+            number: pair.score.number,
+            directional: pair.score.directional,
+            street_name: pair.score.street_name,
+            phonetic: pair.score.phonetic,
+            post_type: pair.score.post_type,
+            subaddress: pair.score.subaddress,
+            distance: pair.distance,
+            reasons: pair.reasons.join("; "),
+        }
+    }
+}
+
+/// The `NearDuplicateRecords` struct holds a vector of type [`NearDuplicateRecord`].
+#[derive(Debug, Default, Clone, PartialEq, PartialOrd, Serialize, Deserialize, Deref, DerefMut)]
+pub struct NearDuplicateRecords(Vec<NearDuplicateRecord>);
+
+impl<T: Address> From<&NearDuplicates<T>> for NearDuplicateRecords {
+    fn from(pairs: &NearDuplicates<T>) -> Self {
+        Self(pairs.iter().map(NearDuplicateRecord::from).collect())
+    }
+}
+
+impl IntoCsv<NearDuplicateRecords> for NearDuplicateRecords {
+    fn from_csv<P: AsRef<std::path::Path>>(path: P) -> Result<Self, Io> {
+        let records = from_csv(path)?;
+        Ok(Self(records))
+    }
+
+    fn to_csv<P: AsRef<std::path::Path>>(&mut self, path: P) -> Result<(), AddressErrorKind> {
+        to_csv(&mut self.0, path.as_ref().into())
+    }
+}
+
+/// The `FuzzyIndex` struct indexes a set of comparison addresses by address number and by
+/// phonetic street name, so that fuzzy candidates for an address are only scored against
+/// addresses sharing either one.  An address with a typo in the number is found through the
+/// street name, and an address with a typo in the street name through the number.
+#[derive(Debug, Clone)]
+pub struct FuzzyIndex<'a, U> {
+    addresses: &'a [U],
+    numbers: HashMap<i64, Vec<usize>>,
+    streets: HashMap<String, Vec<usize>>,
+}
+
+impl<'a, U: Address> FuzzyIndex<'a, U> {
+    /// Creates a new `FuzzyIndex` over the comparison addresses in `addresses`.
+    pub fn new(addresses: &'a [U]) -> Self {
+        let mut numbers: HashMap<i64, Vec<usize>> = HashMap::new();
+        let mut streets: HashMap<String, Vec<usize>> = HashMap::new();
+        for (i, address) in addresses.iter().enumerate() {
+            numbers.entry(address.number()).or_default().push(i);
+            streets
+                .entry(address.phonetic_common_street_name())
+                .or_default()
+                .push(i);
+        }
+        Self {
+            addresses,
+            numbers,
+            streets,
+        }
+    }
+
+    /// The `coincident` method returns true if an address in the index coincides with `address`
+    /// under `policy`.  When the policy requires the address number to agree, only addresses
+    /// with the same address number are compared.
+    pub fn coincident<T: Address>(&self, address: &T, policy: &MatchPolicy) -> bool {
+        let coincides = |other: &U| address.coincident_with(other, policy).coincident;
+        if policy.rule(AddressComponent::Number) == MatchRule::Required {
+            self.numbers
+                .get(&address.number())
+                .is_some_and(|block| block.iter().any(|&i| coincides(&self.addresses[i])))
+        } else {
+            self.addresses.iter().any(coincides)
+        }
+    }
+
+    /// The `candidates` method returns up to `k` addresses from the index sharing the address
+    /// number or phonetic street name of `address`, with a total similarity score of at least
+    /// `threshold`, sorted by descending score.
+    pub fn candidates<T: Address>(
+        &self,
+        address: &T,
+        k: usize,
+        threshold: f64,
+    ) -> Vec<(&'a U, SimilarityScore)> {
+        let mut block = [
+            self.numbers.get(&address.number()),
+            self.streets.get(&address.phonetic_common_street_name()),
+        ]
+        .into_iter()
+        .flatten()
+        .flatten()
+        .copied()
+        .collect::<Vec<usize>>();
+        block.sort_unstable();
+        block.dedup();
+        let mut candidates = block
+            .into_iter()
+            .map(|i| &self.addresses[i])
+            .map(|other| (other, SimilarityScore::new(address, other)))
+            .filter(|(_, score)| score.total >= threshold)
+            .collect::<Vec<(&'a U, SimilarityScore)>>();
+        candidates.sort_by(|a, b| b.1.total.total_cmp(&a.1.total));
+        candidates.truncate(k);
+        candidates
+    }
+}
+
+/// The `FuzzyMatchRecord` struct reports a ranked fuzzy candidate for an address that is missing
+/// from the comparison set, with the similarity score broken down by component.  Designed to
+/// export to csv.
+#[derive(Debug, Default, Clone, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct FuzzyMatchRecord {
+    /// The `address_label` field is the text representation of the subject address.
+    pub address_label: String,
+    /// The `rank` field is the rank of the candidate, starting from 1 for the most similar
+    /// address.  A rank of 0 indicates no candidate met the threshold.
+    pub rank: usize,
+    /// The `candidate_label` field is the text representation of the candidate address.
+    pub candidate_label: Option<String>,
+    /// The `score` field is the total similarity score of the candidate.
+    pub score: Option<f64>,
+    /// The `number` field is the address number component of the similarity score.
+    pub number: Option<f64>,
+    /// The `directional` field is the directional component of the similarity score.
+    pub directional: Option<f64>,
+    /// The `street_name` field is the street name component of the similarity score.
+    pub street_name: Option<f64>,
+    /// The `phonetic` field is the phonetic street name component of the similarity score.
+    pub phonetic: Option<f64>,
+    /// The `post_type` field is the post type component of the similarity score.
+    pub post_type: Option<f64>,
+    /// The `subaddress` field is the subaddress component of the similarity score.
+    pub subaddress: Option<f64>,
+    /// The `longitude` field represents the 'x' value of the subject address point.
+    pub longitude: f64,
+    /// The `latitude` field represents the 'y' value of the subject address point.
+    pub latitude: f64,
+}
+
+impl FuzzyMatchRecord {
+    /// Creates a new `FuzzyMatchRecord` for the subject `address`, with the candidate `other` at
+    /// position `rank` scoring `score`.
+    pub fn new<T: Address + Geographic, U: Address>(
+        address: &T,
+        rank: usize,
+        other: &U,
+        score: &SimilarityScore,
+    ) -> Self {
+        Self {
+            address_label: address.label(),
+            rank,
+            candidate_label: Some(other.label()),
+            score: Some(score.total),
+            number: Some(score.number),
+            directional: Some(score.directional),
+            street_name: Some(score.street_name),
+            phonetic: Some(score.phonetic),
+            post_type: Some(score.post_type),
+            subaddress: Some(score.subaddress),
+            longitude: address.longitude(),
+            latitude: address.latitude(),
+        }
+    }
+
+    /// The `unmatched` method creates a `FuzzyMatchRecord` for a subject `address` with no
+    /// candidates.
+    pub fn unmatched<T: Address + Geographic>(address: &T) -> Self {
+        Self {
+            address_label: address.label(),
+            longitude: address.longitude(),
+            latitude: address.latitude(),
+            ..Default::default()
+        }
+    }
+}
+
+/// The `FuzzyMatchRecords` struct holds a vector of type [`FuzzyMatchRecord`].
+#[derive(Debug, Default, Clone, PartialEq, PartialOrd, Serialize, Deserialize, Deref, DerefMut)]
+pub struct FuzzyMatchRecords(Vec<FuzzyMatchRecord>);
+
+impl FuzzyMatchRecords {
+    /// The `compare` method finds each address in `self_addresses` without a coincident address
+    /// in `other_addresses` under `policy`, and reports the top `k` candidates from
+    /// `other_addresses` with a total similarity score of at least `threshold`.  Addresses with no
+    /// candidates are reported with a rank of 0.  See [`FuzzyIndex::coincident`].
+    pub fn compare<T: Address + Geographic + Send + Sync, U: Address + Send + Sync>(
+        self_addresses: &[T],
+        other_addresses: &[U],
+        policy: &MatchPolicy,
+        k: usize,
+        threshold: f64,
+    ) -> Self {
+        let index = FuzzyIndex::new(other_addresses);
+        let style = indicatif::ProgressStyle::with_template(
+            "[{elapsed_precise}] {bar:40.cyan/blue} {pos:>7}/{len:7} {'Comparing addresses.'}",
+        )
+        .unwrap();
+        let records = self_addresses
+            .par_iter()
+            .map(|address| {
+                if index.coincident(address, policy) {
+                    return Vec::new();
+                }
+                let candidates = index.candidates(address, k, threshold);
+                if candidates.is_empty() {
+                    vec![FuzzyMatchRecord::unmatched(address)]
+                } else {
+                    candidates
+                        .iter()
+                        .enumerate()
+                        .map(|(i, (other, score))| {
+                            FuzzyMatchRecord::new(address, i + 1, *other, score)
+                        })
+                        .collect::<Vec<FuzzyMatchRecord>>()
+                }
+            })
+            .progress_with_style(style)
+            .flatten()
+            .collect::<Vec<FuzzyMatchRecord>>();
+        Self(records)
+    }
+}
+
+impl IntoCsv<FuzzyMatchRecords> for FuzzyMatchRecords {
+    fn from_csv<P: AsRef<std::path::Path>>(path: P) -> Result<Self, Io> {
+        let records = from_csv(path)?;
+        Ok(Self(records))
+    }
+
+    fn to_csv<P: AsRef<std::path::Path>>(&mut self, path: P) -> Result<(), AddressErrorKind> {
+        to_csv(&mut self.0, path.as_ref().into())
+    }
+}
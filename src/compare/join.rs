@@ -0,0 +1,262 @@
+//! The `join` module contains types for joining two sets of records by address, such as fire
+//! inspections to business licenses.
+use crate::{
+    Address, AddressComponent, CommonAddress, MatchPolicy, MatchRule, MatchStatus, PartialAddress,
+};
+use derive_more::{Deref, DerefMut};
+use indicatif::ParallelProgressIterator;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use strum::IntoEnumIterator;
+use tracing::info;
+
+/// The `Addressable` trait indicates the record holds an address, so that two sets of records can
+/// be joined by address using [`JoinPairs::join`].  Every type implementing [`Address`] is
+/// addressable, along with records holding a [`PartialAddress`].
+pub trait Addressable {
+    /// The `partial_address` method returns the address of the record as a [`PartialAddress`].
+    fn partial_address(&self) -> PartialAddress;
+}
+
+impl<T: Address> Addressable for T {
+    fn partial_address(&self) -> PartialAddress {
+        PartialAddress::from(self)
+    }
+}
+
+impl Addressable for PartialAddress {
+    fn partial_address(&self) -> PartialAddress {
+        self.clone()
+    }
+}
+
+/// The `JoinKind` enum determines which records a join returns.
+#[derive(
+    Debug,
+    Default,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    strum::Display,
+    strum::EnumString,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum JoinKind {
+    /// Returns a pair for each left record and each right record with a coincident address.
+    #[default]
+    Inner,
+    /// Returns the pairs of an inner join, and a pair with no right record for each left record
+    /// without a coincident address.
+    Left,
+    /// Returns a pair with no right record for each left record without a coincident address.
+    Anti,
+}
+
+/// The `JoinPair` struct pairs a record from the left set of a join with a record from the right
+/// set that shares its address.
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct JoinPair<'a, L, R> {
+    /// The `left_index` field is the position of the left record in the left set.
+    pub left_index: usize,
+    /// The `left` field is the left record.
+    pub left: &'a L,
+    /// The `right_index` field is the position of the right record in the right set, or `None`
+    /// if the left record has no coincident address.
+    pub right_index: Option<usize>,
+    /// The `right` field is the right record, or `None` if the left record has no coincident
+    /// address.
+    pub right: Option<&'a R>,
+    /// The `status` field is the match status of the pair, either matching, divergent, or
+    /// missing when there is no right record.
+    pub status: MatchStatus,
+}
+
+/// The `JoinPairs` struct holds a vector of type [`JoinPair`].
+#[derive(Debug, Clone, PartialEq, PartialOrd, Deref, DerefMut)]
+pub struct JoinPairs<'a, L, R>(Vec<JoinPair<'a, L, R>>);
+
+impl<'a, L: Addressable + Sync, R: Addressable + Sync> JoinPairs<'a, L, R> {
+    /// The `join` method joins the records in `left` to the records in `right` by address, using
+    /// `policy` to determine which components must agree.  Components are compared with
+    /// [`AddressComponent::differs_partial`] in both directions, so a component missing from either
+    /// address is treated as agreeing, except for the address number, directional and subaddress
+    /// identifier.  Where a left record has matching right records, divergent right records are
+    /// not paired.  The
+    /// `kind` determines whether pairs, unpaired left records, or both are returned.
+    ///
+    /// For example, the licensed businesses with no fire inspection are
+    /// `JoinPairs::join(&businesses, &inspections, JoinKind::Anti, &MatchPolicy::partial())`.
+    pub fn join(left: &'a [L], right: &'a [R], kind: JoinKind, policy: &MatchPolicy) -> Self {
+        let partials = right
+            .iter()
+            .map(|record| record.partial_address())
+            .collect::<Vec<PartialAddress>>();
+        let completes = partials
+            .iter()
+            .map(complete)
+            .collect::<Vec<CommonAddress>>();
+        let blocked = policy.rule(AddressComponent::Number) == MatchRule::Required;
+        let mut blocks: HashMap<Option<i64>, Vec<usize>> = HashMap::new();
+        if blocked {
+            for (index, address) in partials.iter().enumerate() {
+                blocks
+                    .entry(address.address_number)
+                    .or_default()
+                    .push(index);
+            }
+        }
+        let all = (0..partials.len()).collect::<Vec<usize>>();
+        let style = indicatif::ProgressStyle::with_template(
+            "[{elapsed_precise}] {bar:40.cyan/blue} {pos:>7}/{len:7} {'Joining records.'}",
+        )
+        .unwrap();
+        let pairs = left
+            .par_iter()
+            .enumerate()
+            .map(|(left_index, record)| {
+                let address = record.partial_address();
+                let address_complete = complete(&address);
+                let candidates = if blocked {
+                    blocks
+                        .get(&address.address_number)
+                        .map_or(&[][..], |c| &c[..])
+                } else {
+                    &all[..]
+                };
+                let mut matches = candidates
+                    .iter()
+                    .filter_map(|&index| {
+                        coincident_complete(
+                            (&address, &address_complete),
+                            (&partials[index], &completes[index]),
+                            policy,
+                        )
+                        .map(|status| (index, status))
+                    })
+                    .collect::<Vec<(usize, MatchStatus)>>();
+                if matches
+                    .iter()
+                    .any(|(_, status)| *status == MatchStatus::Matching)
+                {
+                    matches.retain(|(_, status)| *status == MatchStatus::Matching);
+                }
+                let mut pairs = Vec::new();
+                if matches.is_empty() {
+                    if kind != JoinKind::Inner {
+                        pairs.push(JoinPair {
+                            left_index,
+                            left: record,
+                            right_index: None,
+                            right: None,
+                            status: MatchStatus::Missing,
+                        });
+                    }
+                } else if kind != JoinKind::Anti {
+                    for (right_index, status) in matches {
+                        pairs.push(JoinPair {
+                            left_index,
+                            left: record,
+                            right_index: Some(right_index),
+                            right: Some(&right[right_index]),
+                            status,
+                        });
+                    }
+                }
+                pairs
+            })
+            .progress_with_style(style)
+            .collect::<Vec<Vec<JoinPair<'a, L, R>>>>();
+        let pairs = pairs
+            .into_iter()
+            .flatten()
+            .collect::<Vec<JoinPair<'a, L, R>>>();
+        info!("{} pairs joined.", pairs.len());
+        Self(pairs)
+    }
+
+    /// The `filter` method returns the subset of pairs with a match status of "matching",
+    /// "divergent" or "missing", as given by `filter`.
+    pub fn filter(mut self, filter: &str) -> Self {
+        match filter {
+            "matching" => self.retain(|r| r.status == MatchStatus::Matching),
+            "divergent" => self.retain(|r| r.status == MatchStatus::Divergent),
+            "missing" => self.retain(|r| r.status == MatchStatus::Missing),
+            _ => info!("Invalid filter provided."),
+        }
+        self
+    }
+
+    /// The `lefts` method returns the left record of each pair.
+    pub fn lefts(&self) -> Vec<&'a L> {
+        self.iter().map(|pair| pair.left).collect()
+    }
+}
+
+/// The `complete` function fills the components missing from `partial` with default values, so
+/// that the partial address can stand in as the comparison address of
+/// [`AddressComponent::differs_partial`].
+fn complete(partial: &PartialAddress) -> CommonAddress {
+    CommonAddress {
+        number: partial.address_number.unwrap_or_default(),
+        number_suffix: partial.address_number_suffix.clone(),
+        directional: partial.street_name_pre_directional,
+        pre_modifier: partial.pre_modifier,
+        pre_type: partial.pre_type,
+        separator: partial.separator,
+        street_name: partial.street_name.clone().unwrap_or_default(),
+        street_type: partial.street_name_post_type,
+        subaddress_type: partial.subaddress_type,
+        subaddress_id: partial.subaddress_identifier.clone(),
+        floor: partial.floor,
+        building: partial.building.clone(),
+        zip: partial.zip_code.unwrap_or_default(),
+        postal_community: partial
+            .postal_community
+            .map(|community| community.label())
+            .unwrap_or_default(),
+        state: partial.state_name.unwrap_or_default(),
+        status: partial.status.unwrap_or_default(),
+    }
+}
+
+/// The `coincident` function returns the match status of partial addresses `a` and `b` under
+/// `policy`, or `None` if a required component differs.
+pub(crate) fn coincident(
+    a: &PartialAddress,
+    b: &PartialAddress,
+    policy: &MatchPolicy,
+) -> Option<MatchStatus> {
+    coincident_complete((a, &complete(a)), (b, &complete(b)), policy)
+}
+
+/// The `coincident_complete` function compares partial addresses as in [`coincident`], with each
+/// address paired with its [`complete`] form so that callers comparing many pairs only fill each
+/// address once.  A component differs only if [`AddressComponent::differs_partial`] reports a
+/// difference in both directions, so a default value filling a missing component never counts as
+/// a difference.
+fn coincident_complete(
+    a: (&PartialAddress, &CommonAddress),
+    b: (&PartialAddress, &CommonAddress),
+    policy: &MatchPolicy,
+) -> Option<MatchStatus> {
+    let mut status = MatchStatus::Matching;
+    for component in AddressComponent::iter() {
+        let differs = component.differs_partial(a.0, b.1) && component.differs_partial(b.0, a.1);
+        if differs {
+            match policy.rule(component) {
+                MatchRule::Required => return None,
+                MatchRule::Mismatch => status = MatchStatus::Divergent,
+                MatchRule::Ignored => {}
+            }
+        }
+    }
+    Some(status)
+}
@@ -0,0 +1,284 @@
+//! The `matrix` module contains types for comparing any number of named address collections,
+//! reporting the presence of each canonical address across the collections.
+use crate::{
+    Address, AddressComponent, AddressErrorKind, Csv, Geographic, IntoCsv, Io, MatchPolicy,
+    MatchRule,
+};
+use derive_more::{Deref, DerefMut};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tracing::info;
+
+/// The `Presence` enum describes the state of a canonical address in a single collection.
+#[derive(
+    Debug,
+    Default,
+    Copy,
+    Clone,
+    PartialEq,
+    PartialOrd,
+    Eq,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    strum::Display,
+    strum::EnumString,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum Presence {
+    /// The collection contains a matching address.
+    Present,
+    /// The collection contains a coincident address that differs in components reported as a
+    /// [`crate::Mismatch`] by the match policy.
+    Divergent,
+    /// The collection does not contain the address.
+    #[default]
+    Missing,
+}
+
+/// The `MatrixRecord` struct reports the [`Presence`] of a canonical address in each collection
+/// of an N-way comparison.
+#[derive(Debug, Default, Clone, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct MatrixRecord {
+    /// The `label` field is the label of the canonical address, taken from the first collection
+    /// containing the address.
+    pub label: String,
+    /// The `longitude` field represents the 'x' value of the canonical address point.
+    pub longitude: f64,
+    /// The `latitude` field represents the 'y' value of the canonical address point.
+    pub latitude: f64,
+    /// The `presence` field holds the [`Presence`] of the address in each collection, in the
+    /// order of the collections in the comparison.
+    pub presence: Vec<Presence>,
+}
+
+impl MatrixRecord {
+    /// The `count` method returns the number of collections where the address has the presence
+    /// `presence`.
+    pub fn count(&self, presence: Presence) -> usize {
+        self.presence.iter().filter(|p| **p == presence).count()
+    }
+
+    /// The `out_of_step` method returns the names from `sources` of the collections where the
+    /// address is divergent or missing.
+    pub fn out_of_step(&self, sources: &[String]) -> Vec<String> {
+        sources
+            .iter()
+            .zip(self.presence.iter())
+            .filter(|(_, presence)| **presence != Presence::Present)
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+}
+
+/// The `MatrixRecords` struct holds the names of the collections in an N-way comparison, and a
+/// [`MatrixRecord`] for each canonical address.
+#[derive(Debug, Default, Clone, PartialEq, PartialOrd, Serialize, Deserialize, Deref, DerefMut)]
+pub struct MatrixRecords {
+    /// The `sources` field holds the names of the collections.
+    pub sources: Vec<String>,
+    /// The `records` field holds a [`MatrixRecord`] for each canonical address.
+    #[deref]
+    #[deref_mut]
+    pub records: Vec<MatrixRecord>,
+}
+
+impl MatrixRecords {
+    /// The `compare` method compares the named collections in `sources`, using `policy` to
+    /// determine which components must agree.  The collections are read in order, and each
+    /// address either joins the first canonical address it is coincident with, or becomes a new
+    /// canonical address.  Where a collection holds several coincident addresses, the best
+    /// presence is reported.  When the policy requires the address number to agree, candidates
+    /// are limited to canonical addresses with the same address number.
+    pub fn compare<T: Address + Geographic>(
+        sources: &[(&str, &[T])],
+        policy: &MatchPolicy,
+    ) -> Self {
+        let names = sources
+            .iter()
+            .map(|(name, _)| name.to_string())
+            .collect::<Vec<String>>();
+        let records = canonical_groups(sources, policy)
+            .into_iter()
+            .map(|group| {
+                let (source, index, _) = group
+                    .iter()
+                    .enumerate()
+                    .find_map(|(source, member)| member.map(|(index, p)| (source, index, p)))
+                    .unwrap_or_default();
+                let address = &sources[source].1[index];
+                MatrixRecord {
+                    label: address.label(),
+                    longitude: address.longitude(),
+                    latitude: address.latitude(),
+                    presence: group
+                        .iter()
+                        .map(|member| member.map_or(Presence::Missing, |(_, p)| p))
+                        .collect(),
+                }
+            })
+            .collect();
+        Self {
+            sources: names,
+            records,
+        }
+    }
+
+    /// The `filter` method returns the subset of records that meet the filter requirement.  The
+    /// `filter` parameter can take the values "complete", returning the addresses present in
+    /// every collection, "incomplete", returning the addresses divergent or missing in any
+    /// collection, or the name of a collection, returning the addresses divergent or missing in
+    /// that collection.
+    pub fn filter(mut self, filter: &str) -> Self {
+        match filter {
+            "complete" => self.retain(|r| r.count(Presence::Present) == r.presence.len()),
+            "incomplete" => self.retain(|r| r.count(Presence::Present) < r.presence.len()),
+            _ => match self.sources.iter().position(|name| name == filter) {
+                Some(index) => self.retain(|r| r.presence[index] != Presence::Present),
+                None => info!("Invalid filter provided."),
+            },
+        }
+        self
+    }
+
+    /// The `headers` method returns the column names of the csv output: the label and
+    /// coordinates, a column for each collection, and summary columns.
+    fn headers(&self) -> Vec<String> {
+        let mut headers = vec![
+            "label".to_string(),
+            "longitude".to_string(),
+            "latitude".to_string(),
+        ];
+        headers.extend(self.sources.iter().cloned());
+        for column in ["present", "divergent", "missing", "out_of_step"] {
+            headers.push(column.to_string());
+        }
+        headers
+    }
+}
+
+impl IntoCsv<MatrixRecords> for MatrixRecords {
+    fn from_csv<P: AsRef<std::path::Path>>(path: P) -> Result<Self, Io> {
+        let path = path.as_ref();
+        let file = std::fs::File::open(path)
+            .map_err(|source| Io::new(path.into(), source, line!(), file!().into()))?;
+        let mut rdr = csv::Reader::from_reader(file);
+        let headers = rdr.headers().cloned().unwrap_or_default();
+        // Skip the label and coordinates, and the four summary columns.
+        let sources = headers
+            .iter()
+            .skip(3)
+            .take(headers.len().saturating_sub(7))
+            .map(|name| name.to_string())
+            .collect::<Vec<String>>();
+        let mut records = Vec::new();
+        let mut dropped = 0;
+        for result in rdr.records() {
+            let parsed = result.ok().and_then(|row| {
+                let presence = row
+                    .iter()
+                    .skip(3)
+                    .take(sources.len())
+                    .map(|value| value.parse::<Presence>().ok())
+                    .collect::<Option<Vec<Presence>>>()?;
+                Some(MatrixRecord {
+                    label: row.get(0)?.to_string(),
+                    longitude: row.get(1)?.parse().ok()?,
+                    latitude: row.get(2)?.parse().ok()?,
+                    presence,
+                })
+            });
+            match parsed {
+                Some(record) => records.push(record),
+                None => dropped += 1,
+            }
+        }
+        info!("{} records dropped.", dropped);
+        Ok(Self { sources, records })
+    }
+
+    fn to_csv<P: AsRef<std::path::Path>>(&mut self, path: P) -> Result<(), AddressErrorKind> {
+        let path = path.as_ref().to_path_buf();
+        let mut wtr = csv::Writer::from_path(&path)
+            .map_err(|source| Csv::new(path.clone(), source, line!(), file!().into()))?;
+        wtr.write_record(self.headers())
+            .map_err(|source| Csv::new(path.clone(), source, line!(), file!().into()))?;
+        for record in self.records.iter() {
+            let mut row = vec![
+                record.label.clone(),
+                record.longitude.to_string(),
+                record.latitude.to_string(),
+            ];
+            row.extend(record.presence.iter().map(|p| p.to_string()));
+            row.push(record.count(Presence::Present).to_string());
+            row.push(record.count(Presence::Divergent).to_string());
+            row.push(record.count(Presence::Missing).to_string());
+            row.push(record.out_of_step(&self.sources).join("; "));
+            wtr.write_record(row)
+                .map_err(|source| Csv::new(path.clone(), source, line!(), file!().into()))?;
+        }
+        wtr.flush()
+            .map_err(|source| Io::new(path.clone(), source, line!(), file!().into()))?;
+        Ok(())
+    }
+}
+
+/// The `canonical_groups` function groups the addresses of the named collections in `sources`
+/// into canonical addresses, as described in [`MatrixRecords::compare`].  Each group holds an
+/// entry for each collection, containing the position of the best matching address in the
+/// collection and its [`Presence`], or `None` if the collection does not contain the address.
+/// The first entry present in each group is the address that founded the group.
+pub(crate) fn canonical_groups<T: Address + Geographic>(
+    sources: &[(&str, &[T])],
+    policy: &MatchPolicy,
+) -> Vec<Vec<Option<(usize, Presence)>>> {
+    let blocked = policy.rule(AddressComponent::Number) == MatchRule::Required;
+    let mut canonical: Vec<&T> = Vec::new();
+    let mut blocks: HashMap<i64, Vec<usize>> = HashMap::new();
+    let mut groups: Vec<Vec<Option<(usize, Presence)>>> = Vec::new();
+    for (index, (name, addresses)) in sources.iter().enumerate() {
+        info!("Comparing {} addresses from {}.", addresses.len(), name);
+        for (position, address) in addresses.iter().enumerate() {
+            let all;
+            let candidates = if blocked {
+                blocks.get(&address.number()).map_or(&[][..], |c| &c[..])
+            } else {
+                all = (0..canonical.len()).collect::<Vec<usize>>();
+                &all[..]
+            };
+            let mut found = None;
+            for &row in candidates {
+                let address_match = canonical[row].coincident_with(address, policy);
+                if address_match.coincident {
+                    let presence = match address_match.mismatches {
+                        None => Presence::Present,
+                        Some(_) => Presence::Divergent,
+                    };
+                    found = Some((row, presence));
+                    break;
+                }
+            }
+            match found {
+                Some((row, presence)) => {
+                    let member = &mut groups[row][index];
+                    if member.is_none_or(|(_, current)| presence < current) {
+                        *member = Some((position, presence));
+                    }
+                }
+                None => {
+                    let mut group = vec![None; sources.len()];
+                    group[index] = Some((position, Presence::Present));
+                    blocks
+                        .entry(address.number())
+                        .or_default()
+                        .push(canonical.len());
+                    canonical.push(address);
+                    groups.push(group);
+                }
+            }
+        }
+    }
+    groups
+}
@@ -1,6 +1,32 @@
 //! The `compare` module contains data types and methods for comparing addresses.
 mod compare_fire;
+mod compare_locatable;
+mod conflate;
+mod correct;
+mod diff;
+mod duplicates;
 mod eponym;
+mod fuzzy;
+mod join;
+mod matrix;
+mod phonetic;
+mod policy;
+mod progress;
+mod reconcile;
+mod sync;
 
 pub use compare_fire::*;
+pub use compare_locatable::*;
+pub use conflate::*;
+pub use correct::*;
+pub use diff::*;
+pub use duplicates::*;
 pub use eponym::*;
+pub use fuzzy::*;
+pub use join::*;
+pub use matrix::*;
+pub use phonetic::*;
+pub use policy::*;
+pub use progress::*;
+pub use reconcile::*;
+pub use sync::*;
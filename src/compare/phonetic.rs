@@ -0,0 +1,270 @@
+//! The `phonetic` module contains a phonetic encoding for street names, and types for grouping
+//! street names that sound alike.
+use crate::{AddressErrorKind, IntoCsv, Io, from_csv, to_csv};
+use derive_more::{Deref, DerefMut};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// The `metaphone` function returns a phonetic code for `name`, following the rules of the
+/// original Metaphone algorithm.  Each word of the name is encoded separately, and the codes are
+/// joined by a space.  A trailing "S" is dropped from the code of words longer than three letters,
+/// so that plural and possessive forms share a code with the singular (e.g. "BRIDGE" and
+/// "BRIDGES").
+pub fn metaphone(name: &str) -> String {
+    name.split_whitespace()
+        .map(metaphone_word)
+        .filter(|code| !code.is_empty())
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// The `is_vowel` function returns true if `c` is a vowel.
+fn is_vowel(c: Option<char>) -> bool {
+    matches!(c, Some('A' | 'E' | 'I' | 'O' | 'U'))
+}
+
+/// The `is_front_vowel` function returns true if `c` softens a preceding "C" or "G".
+fn is_front_vowel(c: Option<char>) -> bool {
+    matches!(c, Some('E' | 'I' | 'Y'))
+}
+
+/// The `metaphone_word` function returns the phonetic code for a single word.
+fn metaphone_word(word: &str) -> String {
+    let mut chars = word
+        .to_uppercase()
+        .chars()
+        .filter(|c| c.is_ascii_alphabetic())
+        .collect::<Vec<char>>();
+    if chars.is_empty() {
+        return String::new();
+    }
+    let letters = chars.len();
+    let double_s = chars.ends_with(&['S', 'S']);
+    // Initial letter exceptions.
+    match (chars.first(), chars.get(1)) {
+        (Some('A'), Some('E')) | (Some('G' | 'K' | 'P'), Some('N')) | (Some('W'), Some('R')) => {
+            chars.remove(0);
+        }
+        (Some('X'), _) => chars[0] = 'S',
+        (Some('W'), Some('H')) => {
+            chars.remove(1);
+        }
+        _ => {}
+    }
+
+    let at = |i: usize| chars.get(i).copied();
+    let mut code = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        let prev = if i > 0 { at(i - 1) } else { None };
+        let next = at(i + 1);
+        // Skip duplicate adjacent letters, except for "C".
+        if prev == Some(c) && c != 'C' {
+            continue;
+        }
+        match c {
+            'A' | 'E' | 'I' | 'O' | 'U' => {
+                if i == 0 {
+                    code.push(c);
+                }
+            }
+            'B' => {
+                if !(prev == Some('M') && next.is_none()) {
+                    code.push('B');
+                }
+            }
+            'C' => {
+                if next == Some('I') && at(i + 2) == Some('A') {
+                    code.push('X');
+                } else if next == Some('H') {
+                    if prev == Some('S') {
+                        code.push('K');
+                    } else {
+                        code.push('X');
+                    }
+                } else if is_front_vowel(next) {
+                    if prev != Some('S') {
+                        code.push('S');
+                    }
+                } else {
+                    code.push('K');
+                }
+            }
+            'D' => {
+                if next == Some('G') && is_front_vowel(at(i + 2)) {
+                    code.push('J');
+                } else {
+                    code.push('T');
+                }
+            }
+            'G' => {
+                if next == Some('H') && !(at(i + 2).is_none() || is_vowel(at(i + 2))) {
+                    continue;
+                }
+                if next == Some('N')
+                    && (at(i + 2).is_none()
+                        || (at(i + 2) == Some('E')
+                            && at(i + 3) == Some('D')
+                            && at(i + 4).is_none()))
+                {
+                    continue;
+                }
+                if prev == Some('D') && is_front_vowel(next) {
+                    continue;
+                }
+                if is_front_vowel(next) {
+                    code.push('J');
+                } else {
+                    code.push('K');
+                }
+            }
+            'H' => {
+                if matches!(prev, Some('C' | 'S' | 'P' | 'T' | 'G')) {
+                    continue;
+                }
+                if is_vowel(prev) && !is_vowel(next) {
+                    continue;
+                }
+                code.push('H');
+            }
+            'K' => {
+                if prev != Some('C') {
+                    code.push('K');
+                }
+            }
+            'P' => {
+                if next == Some('H') {
+                    code.push('F');
+                } else {
+                    code.push('P');
+                }
+            }
+            'Q' => code.push('K'),
+            'S' => {
+                if next == Some('H') || (next == Some('I') && matches!(at(i + 2), Some('O' | 'A')))
+                {
+                    code.push('X');
+                } else {
+                    code.push('S');
+                }
+            }
+            'T' => {
+                if next == Some('I') && matches!(at(i + 2), Some('O' | 'A')) {
+                    code.push('X');
+                } else if next == Some('H') {
+                    code.push('0');
+                } else if !(next == Some('C') && at(i + 2) == Some('H')) {
+                    code.push('T');
+                }
+            }
+            'V' => code.push('F'),
+            'W' | 'Y' => {
+                if is_vowel(next) {
+                    code.push(c);
+                }
+            }
+            'X' => code.push_str("KS"),
+            'Z' => code.push('S'),
+            _ => code.push(c),
+        }
+    }
+    if letters > 3 && code.len() > 1 && code.ends_with('S') && !double_s {
+        code.pop();
+    }
+    code
+}
+
+/// The `PhoneticGroup` struct holds the distinct street names in a set of addresses that share a
+/// phonetic code.
+#[derive(Debug, Default, Clone, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct PhoneticGroup {
+    /// The `code` field is the phonetic code shared by the street names.
+    pub code: String,
+    /// The `names` field holds the distinct street names with the phonetic code, in sorted order.
+    pub names: Vec<String>,
+}
+
+/// The `PhoneticGroups` struct holds a vector of type [`PhoneticGroup`], sorted by phonetic code.
+#[derive(Debug, Default, Clone, PartialEq, PartialOrd, Serialize, Deserialize, Deref, DerefMut)]
+pub struct PhoneticGroups(Vec<PhoneticGroup>);
+
+impl PhoneticGroups {
+    /// The `from_names` method groups the distinct street names in `names` by their
+    /// [`metaphone`] code.
+    pub fn from_names<I: IntoIterator<Item = String>>(names: I) -> Self {
+        let mut groups: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+        for name in names {
+            groups.entry(metaphone(&name)).or_default().insert(name);
+        }
+        let groups = groups
+            .into_iter()
+            .map(|(code, names)| PhoneticGroup {
+                code,
+                names: names.into_iter().collect(),
+            })
+            .collect::<Vec<PhoneticGroup>>();
+        Self(groups)
+    }
+
+    /// The `sound_alike` method returns the subset of groups containing more than one street
+    /// name.
+    pub fn sound_alike(&self) -> Self {
+        let groups = self
+            .iter()
+            .filter(|group| group.names.len() > 1)
+            .cloned()
+            .collect::<Vec<PhoneticGroup>>();
+        Self(groups)
+    }
+
+    /// The `similar` method returns the street names that sound like `name`, excluding `name`
+    /// itself.  Used to screen a proposed street name against existing names.
+    pub fn similar(&self, name: &str) -> Vec<String> {
+        let code = metaphone(name);
+        self.iter()
+            .filter(|group| group.code == code)
+            .flat_map(|group| group.names.iter())
+            .filter(|other| other.as_str() != name)
+            .cloned()
+            .collect()
+    }
+}
+
+/// The `PhoneticRecord` struct represents a single [`PhoneticGroup`], designed to export to csv.
+#[derive(Debug, Default, Clone, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct PhoneticRecord {
+    /// The `code` field is the phonetic code shared by the street names.
+    pub code: String,
+    /// The `count` field is the number of distinct street names in the group.
+    pub count: usize,
+    /// The `names` field lists the street names in the group, separated by semicolons.
+    pub names: String,
+}
+
+/// The `PhoneticRecords` struct holds a vector of type [`PhoneticRecord`].
+#[derive(Debug, Default, Clone, PartialEq, PartialOrd, Serialize, Deserialize, Deref, DerefMut)]
+pub struct PhoneticRecords(Vec<PhoneticRecord>);
+
+impl From<&PhoneticGroups> for PhoneticRecords {
+    fn from(groups: &PhoneticGroups) -> Self {
+        let records = groups
+            .iter()
+            .map(|group| PhoneticRecord {
+                code: group.code.clone(),
+                count: group.names.len(),
+                names: group.names.join("; "),
+            })
+            .collect::<Vec<PhoneticRecord>>();
+        Self(records)
+    }
+}
+
+impl IntoCsv<PhoneticRecords> for PhoneticRecords {
+    fn from_csv<P: AsRef<std::path::Path>>(path: P) -> Result<Self, Io> {
+        let records = from_csv(path)?;
+        Ok(Self(records))
+    }
+
+    fn to_csv<P: AsRef<std::path::Path>>(&mut self, path: P) -> Result<(), AddressErrorKind> {
+        to_csv(&mut self.0, path.as_ref().into())
+    }
+}
@@ -0,0 +1,448 @@
+//! The `policy` module contains types for configuring which address components must agree for
+//! two addresses to match, and which components are reported as a [`Mismatch`] or ignored.
+use crate::{
+    Address, AddressErrorKind, IntoCsv, Io, Mismatch, PartialAddress, from_csv_with, to_csv,
+};
+use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
+
+/// The `AddressComponent` enum lists the components of an address that can be compared.  The
+/// variants follow the order of the components in the address label.
+#[derive(
+    Debug,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    strum::Display,
+    strum::EnumIter,
+    strum::EnumString,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum AddressComponent {
+    /// The address number component of the complete address number.
+    Number,
+    /// The address number suffix component of the complete address number.
+    NumberSuffix,
+    /// The street name pre directional component of the complete street name.
+    Directional,
+    /// The street name pre modifier component of the complete street name.
+    PreModifier,
+    /// The street name pre type component of the complete street name.
+    PreType,
+    /// The separator element component of the complete street name.
+    Separator,
+    /// The street name component of the complete street name.
+    StreetName,
+    /// The street name post type component of the complete street name.
+    StreetType,
+    /// The subaddress type component of the complete subaddress.
+    SubaddressType,
+    /// The subaddress identifier component of the complete subaddress.
+    SubaddressId,
+    /// The floor identifier.
+    Floor,
+    /// The building identifier.
+    Building,
+    /// The postal zip code.
+    Zip,
+    /// The postal community.
+    PostalCommunity,
+    /// The state name.
+    State,
+    /// The local address status.
+    Status,
+}
+
+impl AddressComponent {
+    /// The `differs` method returns true if address `a` and address `b` hold different values for
+    /// the component.
+    pub fn differs<T: Address + ?Sized, U: Address + ?Sized>(&self, a: &T, b: &U) -> bool {
+        match self {
+            Self::Number => a.number() != b.number(),
+            Self::NumberSuffix => a.number_suffix() != b.number_suffix(),
+            Self::Directional => a.directional() != b.directional(),
+            Self::PreModifier => a.street_name_pre_modifier() != b.street_name_pre_modifier(),
+            Self::PreType => a.street_name_pre_type() != b.street_name_pre_type(),
+            Self::Separator => a.street_name_separator() != b.street_name_separator(),
+            Self::StreetName => a.street_name() != b.street_name(),
+            Self::StreetType => a.street_type() != b.street_type(),
+            Self::SubaddressType => a.subaddress_type() != b.subaddress_type(),
+            Self::SubaddressId => a.subaddress_id() != b.subaddress_id(),
+            Self::Floor => a.floor() != b.floor(),
+            Self::Building => a.building() != b.building(),
+            Self::Zip => a.zip() != b.zip(),
+            Self::PostalCommunity => a.postal_community() != b.postal_community(),
+            Self::State => a.state() != b.state(),
+            Self::Status => a.status() != b.status(),
+        }
+    }
+
+    /// The `differs_partial` method returns true if the `partial` address and `address` hold
+    /// different values for the component.  Components missing from the partial address are
+    /// treated as agreeing, except for the address number, directional and subaddress
+    /// identifier.  The building is only compared when `address` has no subaddress identifier,
+    /// and the floor only when `address` has neither a subaddress identifier nor a building.
+    pub fn differs_partial<T: Address + ?Sized>(
+        &self,
+        partial: &PartialAddress,
+        address: &T,
+    ) -> bool {
+        match self {
+            Self::Number => partial.address_number != Some(address.number()),
+            Self::NumberSuffix => partial
+                .address_number_suffix
+                .as_ref()
+                .is_some_and(|value| Some(value) != address.number_suffix().as_ref()),
+            Self::Directional => &partial.street_name_pre_directional != address.directional(),
+            Self::PreModifier => partial
+                .pre_modifier
+                .is_some_and(|value| Some(value) != *address.street_name_pre_modifier()),
+            Self::PreType => partial
+                .pre_type
+                .is_some_and(|value| Some(value) != *address.street_name_pre_type()),
+            Self::Separator => partial
+                .separator
+                .is_some_and(|value| Some(value) != *address.street_name_separator()),
+            Self::StreetName => partial
+                .street_name
+                .as_ref()
+                .is_some_and(|value| value != address.street_name()),
+            Self::StreetType => match (partial.street_name_post_type, address.street_type()) {
+                (Some(value), Some(street_type)) => value != *street_type,
+                _ => false,
+            },
+            Self::SubaddressType => partial
+                .subaddress_type
+                .is_some_and(|value| Some(value) != *address.subaddress_type()),
+            Self::SubaddressId => &partial.subaddress_identifier != address.subaddress_id(),
+            Self::Floor => {
+                address.subaddress_id().is_none()
+                    && address.building().is_none()
+                    && &partial.floor != address.floor()
+            }
+            Self::Building => {
+                address.subaddress_id().is_none() && &partial.building != address.building()
+            }
+            Self::Zip => partial.zip_code.is_some_and(|value| value != address.zip()),
+            Self::PostalCommunity => partial
+                .postal_community
+                .is_some_and(|value| &value.label() != address.postal_community()),
+            Self::State => partial
+                .state_name
+                .is_some_and(|value| &value != address.state()),
+            Self::Status => partial
+                .status
+                .is_some_and(|value| &value != address.status()),
+        }
+    }
+
+    /// The `assign` method copies the value of the component from address `from` to address `to`.
+    pub fn assign<T: Address + ?Sized, U: Address + ?Sized>(&self, from: &T, to: &mut U) {
+        match self {
+            Self::Number => *to.number_mut() = from.number(),
+            Self::NumberSuffix => *to.number_suffix_mut() = from.number_suffix().clone(),
+            Self::Directional => *to.directional_mut() = *from.directional(),
+            Self::PreModifier => {
+                *to.street_name_pre_modifier_mut() = *from.street_name_pre_modifier()
+            }
+            Self::PreType => *to.street_name_pre_type_mut() = *from.street_name_pre_type(),
+            Self::Separator => *to.street_name_separator_mut() = *from.street_name_separator(),
+            Self::StreetName => *to.street_name_mut() = from.street_name().clone(),
+            Self::StreetType => *to.street_type_mut() = *from.street_type(),
+            Self::SubaddressType => *to.subaddress_type_mut() = *from.subaddress_type(),
+            Self::SubaddressId => *to.subaddress_id_mut() = from.subaddress_id().clone(),
+            Self::Floor => *to.floor_mut() = *from.floor(),
+            Self::Building => *to.building_mut() = from.building().clone(),
+            Self::Zip => *to.zip_mut() = from.zip(),
+            Self::PostalCommunity => *to.postal_community_mut() = from.postal_community().clone(),
+            Self::State => *to.state_mut() = *from.state(),
+            Self::Status => *to.status_mut() = *from.status(),
+        }
+    }
+
+    /// The `differs_partials` method returns true if partial addresses `a` and `b` hold different
+    /// values for the component.  A component missing from one address and present in the other
+    /// is treated as a difference.
+    pub fn differs_partials(&self, a: &PartialAddress, b: &PartialAddress) -> bool {
+        match self {
+            Self::Number => a.address_number != b.address_number,
+            Self::NumberSuffix => a.address_number_suffix != b.address_number_suffix,
+            Self::Directional => a.street_name_pre_directional != b.street_name_pre_directional,
+            Self::PreModifier => a.pre_modifier != b.pre_modifier,
+            Self::PreType => a.pre_type != b.pre_type,
+            Self::Separator => a.separator != b.separator,
+            Self::StreetName => a.street_name != b.street_name,
+            Self::StreetType => a.street_name_post_type != b.street_name_post_type,
+            Self::SubaddressType => a.subaddress_type != b.subaddress_type,
+            Self::SubaddressId => a.subaddress_identifier != b.subaddress_identifier,
+            Self::Floor => a.floor != b.floor,
+            Self::Building => a.building != b.building,
+            Self::Zip => a.zip_code != b.zip_code,
+            Self::PostalCommunity => a.postal_community != b.postal_community,
+            Self::State => a.state_name != b.state_name,
+            Self::Status => a.status != b.status,
+        }
+    }
+
+    /// The `mismatch` method returns the [`Mismatch`] between address `a` and address `b` for the
+    /// component, holding the value from `a` as the `from` field and the value from `b` as the
+    /// `to` field.
+    pub fn mismatch<T: Address + ?Sized, U: Address + ?Sized>(&self, a: &T, b: &U) -> Mismatch {
+        match self {
+            Self::Number => Mismatch::Number {
+                from: a.number(),
+                to: b.number(),
+            },
+            Self::NumberSuffix => Mismatch::NumberSuffix {
+                from: a.number_suffix().clone(),
+                to: b.number_suffix().clone(),
+            },
+            Self::Directional => Mismatch::Directional {
+                from: *a.directional(),
+                to: *b.directional(),
+            },
+            Self::PreModifier => Mismatch::PreModifier {
+                from: *a.street_name_pre_modifier(),
+                to: *b.street_name_pre_modifier(),
+            },
+            Self::PreType => Mismatch::PreType {
+                from: *a.street_name_pre_type(),
+                to: *b.street_name_pre_type(),
+            },
+            Self::Separator => Mismatch::Separator {
+                from: *a.street_name_separator(),
+                to: *b.street_name_separator(),
+            },
+            Self::StreetName => Mismatch::StreetName {
+                from: a.street_name().clone(),
+                to: b.street_name().clone(),
+            },
+            Self::StreetType => Mismatch::StreetType {
+                from: *a.street_type(),
+                to: *b.street_type(),
+            },
+            Self::SubaddressType => {
+                Mismatch::subaddress_type(*a.subaddress_type(), *b.subaddress_type())
+            }
+            Self::SubaddressId => Mismatch::SubaddressId {
+                from: a.subaddress_id().clone(),
+                to: b.subaddress_id().clone(),
+            },
+            Self::Floor => Mismatch::floor(*a.floor(), *b.floor()),
+            Self::Building => Mismatch::building(a.building().clone(), b.building().clone()),
+            Self::Zip => Mismatch::Zip {
+                from: a.zip(),
+                to: b.zip(),
+            },
+            Self::PostalCommunity => Mismatch::PostalCommunity {
+                from: a.postal_community().clone(),
+                to: b.postal_community().clone(),
+            },
+            Self::State => Mismatch::State {
+                from: *a.state(),
+                to: *b.state(),
+            },
+            Self::Status => Mismatch::status(*a.status(), *b.status()),
+        }
+    }
+}
+
+/// The `MatchRule` enum determines how a difference in an address component affects the match
+/// between two addresses.
+#[derive(
+    Debug,
+    Default,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    strum::Display,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum MatchRule {
+    /// The component must agree for the addresses to match.
+    Required,
+    /// The addresses can match when the component differs, but the difference is reported as a
+    /// [`Mismatch`].
+    Mismatch,
+    /// The component is not compared.
+    #[default]
+    Ignored,
+}
+
+/// The `MatchPolicyRule` struct represents the rule for a single component, used to read and
+/// write a [`MatchPolicy`] as csv.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct MatchPolicyRule {
+    component: AddressComponent,
+    rule: MatchRule,
+}
+
+/// The `MatchPolicy` struct holds a [`MatchRule`] for each [`AddressComponent`], determining
+/// which components must agree for two addresses to match, which are reported as a [`Mismatch`],
+/// and which are ignored.
+///
+/// Policies are available as named presets through [`MatchPolicy::preset`], or can be read from
+/// a csv file with `component` and `rule` columns, such as:
+///
+/// ```text
+/// component,rule
+/// number,required
+/// street_name,required
+/// zip,mismatch
+/// ```
+///
+/// Components not listed in the file are ignored.  The file fails to load if any row names an
+/// unknown component or rule, or if the address number is ignored, since a policy that ignores
+/// the number matches nearly every address on the street.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MatchPolicy {
+    rules: [MatchRule; 16],
+}
+
+impl Default for MatchPolicy {
+    fn default() -> Self {
+        Self::strict()
+    }
+}
+
+impl MatchPolicy {
+    /// Creates a new `MatchPolicy` where every component is ignored.
+    pub fn new() -> Self {
+        Self {
+            rules: [MatchRule::Ignored; 16],
+        }
+    }
+
+    /// The `strict` preset requires every component to agree except the subaddress type, floor,
+    /// building and status, which are reported as mismatches.  This is the default policy used
+    /// by [`Address::coincident`].
+    pub fn strict() -> Self {
+        let mut policy = Self::new();
+        for component in AddressComponent::iter() {
+            policy.set_rule(component, MatchRule::Required);
+        }
+        policy
+            .with_rule(AddressComponent::SubaddressType, MatchRule::Mismatch)
+            .with_rule(AddressComponent::Floor, MatchRule::Mismatch)
+            .with_rule(AddressComponent::Building, MatchRule::Mismatch)
+            .with_rule(AddressComponent::Status, MatchRule::Mismatch)
+    }
+
+    /// The `loose` preset requires the address number, directional, street name with any pre
+    /// modifier, pre type or separator, and subaddress identifier to agree, reporting differences
+    /// in the number suffix, post type, subaddress type, floor, building, zip, postal community
+    /// and status as mismatches.  The state is ignored.
+    pub fn loose() -> Self {
+        Self::strict()
+            .with_rule(AddressComponent::NumberSuffix, MatchRule::Mismatch)
+            .with_rule(AddressComponent::StreetType, MatchRule::Mismatch)
+            .with_rule(AddressComponent::Zip, MatchRule::Mismatch)
+            .with_rule(AddressComponent::PostalCommunity, MatchRule::Mismatch)
+            .with_rule(AddressComponent::State, MatchRule::Ignored)
+    }
+
+    /// The `partial` preset requires the address number, directional, street name and post type
+    /// to agree, reporting differences in the subaddress identifier, building and floor as
+    /// mismatches.  Other components are ignored.  This is the default policy for matching
+    /// partial addresses and business licenses.
+    pub fn partial() -> Self {
+        Self::new()
+            .with_rule(AddressComponent::Number, MatchRule::Required)
+            .with_rule(AddressComponent::Directional, MatchRule::Required)
+            .with_rule(AddressComponent::StreetName, MatchRule::Required)
+            .with_rule(AddressComponent::StreetType, MatchRule::Required)
+            .with_rule(AddressComponent::SubaddressId, MatchRule::Mismatch)
+            .with_rule(AddressComponent::Building, MatchRule::Mismatch)
+            .with_rule(AddressComponent::Floor, MatchRule::Mismatch)
+    }
+
+    /// The `preset` method returns the policy with the given `name`.  Currently accepts the
+    /// values "strict", "loose" and "partial".
+    pub fn preset(name: &str) -> Option<Self> {
+        match name {
+            "strict" => Some(Self::strict()),
+            "loose" => Some(Self::loose()),
+            "partial" => Some(Self::partial()),
+            _ => None,
+        }
+    }
+
+    /// The `load` method returns the preset named `value` if one exists, otherwise reads the
+    /// policy from a csv file at the path in `value`.
+    pub fn load(value: &str) -> Result<Self, Io> {
+        match Self::preset(value) {
+            Some(policy) => Ok(policy),
+            None => Self::from_csv(value),
+        }
+    }
+
+    /// The `rule` method returns the [`MatchRule`] for `component`.
+    pub fn rule(&self, component: AddressComponent) -> MatchRule {
+        self.rules[component as usize]
+    }
+
+    /// The `set_rule` method sets the [`MatchRule`] for `component` to `rule`.
+    pub fn set_rule(&mut self, component: AddressComponent, rule: MatchRule) {
+        self.rules[component as usize] = rule;
+    }
+
+    /// The `with_rule` method returns the policy with the [`MatchRule`] for `component` set to
+    /// `rule`.
+    pub fn with_rule(mut self, component: AddressComponent, rule: MatchRule) -> Self {
+        self.set_rule(component, rule);
+        self
+    }
+}
+
+impl IntoCsv<MatchPolicy> for MatchPolicy {
+    fn from_csv<P: AsRef<std::path::Path>>(path: P) -> Result<Self, Io> {
+        let invalid = |message: String| {
+            let source = std::io::Error::new(std::io::ErrorKind::InvalidData, message);
+            Io::new(path.as_ref().into(), source, line!(), file!().into())
+        };
+        let (records, rejections) =
+            from_csv_with(&path, Ok::<MatchPolicyRule, std::convert::Infallible>)?;
+        if let Some(rejection) = rejections.first() {
+            return Err(invalid(format!(
+                "{} invalid policy rows, first at row {}: {}",
+                rejections.len(),
+                rejection.row,
+                rejection.error
+            )));
+        }
+        let mut policy = Self::new();
+        for record in records {
+            policy.set_rule(record.component, record.rule);
+        }
+        if policy.rule(AddressComponent::Number) == MatchRule::Ignored {
+            return Err(invalid(
+                "policy does not compare the address number".to_string(),
+            ));
+        }
+        Ok(policy)
+    }
+
+    fn to_csv<P: AsRef<std::path::Path>>(&mut self, path: P) -> Result<(), AddressErrorKind> {
+        let mut records = AddressComponent::iter()
+            .map(|component| MatchPolicyRule {
+                component,
+                rule: self.rule(component),
+            })
+            .collect::<Vec<MatchPolicyRule>>();
+        to_csv(&mut records, path.as_ref().into())
+    }
+}
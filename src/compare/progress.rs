@@ -0,0 +1,236 @@
+//! The `progress` module contains types for comparing the output of two match runs, tracking
+//! which open records were resolved between the runs.
+use crate::{
+    AddressErrorKind, BusinessMatchRecord, IntoCsv, Io, MatchRecord, MatchStatus, from_csv, to_csv,
+};
+use derive_more::{Deref, DerefMut};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tracing::info;
+
+/// The `ProgressStatus` enum describes the change to an address between a previous and current
+/// match run.  An address is open when its best [`MatchStatus`] is anything but
+/// [`MatchStatus::Matching`].
+#[derive(
+    Debug,
+    Default,
+    Copy,
+    Clone,
+    PartialEq,
+    PartialOrd,
+    Eq,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    strum::Display,
+    strum::EnumString,
+    strum::EnumIter,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum ProgressStatus {
+    /// The address was open in the previous run, and is matching in the current run.
+    Resolved,
+    /// The address was open in the previous run, and is no longer reported in the current run.
+    /// The address may have been retired or dropped from the source, so it is not counted as
+    /// resolved.
+    Removed,
+    /// The address is open in the current run, and was matching or not reported in the
+    /// previous run.
+    NewlyBroken,
+    /// The address is open in both runs with the same status.
+    StillOpen,
+    /// The address is open in both runs, but the status changed, such as from divergent to
+    /// missing.
+    ChangedCategory,
+    /// The address is not open in either run.
+    #[default]
+    Unchanged,
+}
+
+impl ProgressStatus {
+    /// The `classify` method returns the progress of an address with the best status `previous`
+    /// in the previous run and `current` in the current run, where `None` means the address was
+    /// not reported in the run.
+    pub fn classify(previous: Option<&MatchStatus>, current: Option<&MatchStatus>) -> Self {
+        fn open(status: Option<&MatchStatus>) -> Option<&MatchStatus> {
+            status.filter(|status| **status != MatchStatus::Matching)
+        }
+        match (open(previous), open(current)) {
+            (Some(_), None) if current.is_none() => Self::Removed,
+            (Some(_), None) => Self::Resolved,
+            (None, Some(_)) => Self::NewlyBroken,
+            (Some(previous), Some(current)) if previous == current => Self::StillOpen,
+            (Some(_), Some(_)) => Self::ChangedCategory,
+            (None, None) => Self::Unchanged,
+        }
+    }
+}
+
+/// The `ProgressRecord` struct reports the progress of a single address between a previous and
+/// current match run.
+#[derive(Debug, Default, Clone, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct ProgressRecord {
+    /// The `key` field is the stable key of the address used to pair records between runs.
+    pub key: String,
+    /// The `label` field is the address label, taken from the current run when present.
+    pub label: String,
+    /// The `previous` field is the best match status of the address in the previous run, if
+    /// reported.
+    pub previous: Option<MatchStatus>,
+    /// The `current` field is the best match status of the address in the current run, if
+    /// reported.
+    pub current: Option<MatchStatus>,
+    /// The `progress` field is the change between the runs.
+    pub progress: ProgressStatus,
+}
+
+/// The `ProgressSummary` struct holds the trend counts of a comparison between match runs, for
+/// reporting the number of open records over time.
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct ProgressSummary {
+    /// The `previous_open` field is the number of open addresses in the previous run.
+    pub previous_open: usize,
+    /// The `current_open` field is the number of open addresses in the current run.
+    pub current_open: usize,
+    /// The `resolved` field is the number of resolved addresses.
+    pub resolved: usize,
+    /// The `removed` field is the number of open addresses no longer reported in the current run.
+    pub removed: usize,
+    /// The `newly_broken` field is the number of newly broken addresses.
+    pub newly_broken: usize,
+    /// The `still_open` field is the number of addresses open in both runs with the same status.
+    pub still_open: usize,
+    /// The `changed_category` field is the number of addresses open in both runs with a
+    /// different status.
+    pub changed_category: usize,
+    /// The `unchanged` field is the number of addresses open in neither run.
+    pub unchanged: usize,
+}
+
+/// The `ProgressRecords` struct holds a vector of type [`ProgressRecord`].
+#[derive(Debug, Default, Clone, PartialEq, PartialOrd, Serialize, Deserialize, Deref, DerefMut)]
+pub struct ProgressRecords(Vec<ProgressRecord>);
+
+impl ProgressRecords {
+    /// The `compare_matches` method compares the [`MatchRecord`] output of a `previous` and
+    /// `current` match run.  Records are keyed on the native identifier of the source address,
+    /// falling back to the address label.  Where an address has several records in a run, the
+    /// best status is used.
+    pub fn compare_matches(previous: &[MatchRecord], current: &[MatchRecord]) -> Self {
+        let key = |record: &MatchRecord| {
+            (
+                record
+                    .source_id
+                    .clone()
+                    .unwrap_or_else(|| record.address_label.clone()),
+                record.address_label.clone(),
+                record.match_status.clone(),
+            )
+        };
+        Self::compare(
+            previous.iter().map(key).collect(),
+            current.iter().map(key).collect(),
+        )
+    }
+
+    /// The `compare_businesses` method compares the [`BusinessMatchRecord`] output of a
+    /// `previous` and `current` match run, keyed on the business license.
+    pub fn compare_businesses(
+        previous: &[BusinessMatchRecord],
+        current: &[BusinessMatchRecord],
+    ) -> Self {
+        let key = |record: &BusinessMatchRecord| {
+            (
+                record.license(),
+                record.business_address_label(),
+                record.match_status(),
+            )
+        };
+        Self::compare(
+            previous.iter().map(key).collect(),
+            current.iter().map(key).collect(),
+        )
+    }
+
+    /// The `compare` method pairs the `(key, label, status)` entries of the `previous` and
+    /// `current` runs on the key, sorting the records by key.
+    fn compare(
+        previous: Vec<(String, String, MatchStatus)>,
+        current: Vec<(String, String, MatchStatus)>,
+    ) -> Self {
+        let mut keys: BTreeMap<String, ProgressRecord> = BTreeMap::new();
+        for (key, label, status) in previous {
+            let record = keys.entry(key.clone()).or_insert_with(|| ProgressRecord {
+                key,
+                label,
+                ..Default::default()
+            });
+            if record.previous.as_ref().is_none_or(|best| status < *best) {
+                record.previous = Some(status);
+            }
+        }
+        for (key, label, status) in current {
+            let record = keys.entry(key.clone()).or_insert_with(|| ProgressRecord {
+                key,
+                ..Default::default()
+            });
+            record.label = label;
+            if record.current.as_ref().is_none_or(|best| status < *best) {
+                record.current = Some(status);
+            }
+        }
+        let records = keys
+            .into_values()
+            .map(|mut record| {
+                record.progress =
+                    ProgressStatus::classify(record.previous.as_ref(), record.current.as_ref());
+                record
+            })
+            .collect::<Vec<ProgressRecord>>();
+        info!("{} addresses compared between runs.", records.len());
+        Self(records)
+    }
+
+    /// The `filter` method returns the subset of records with a [`ProgressStatus`] matching
+    /// `filter`, which can take the values "resolved", "removed", "newly_broken", "still_open",
+    /// "changed_category" and "unchanged".
+    pub fn filter(mut self, filter: &str) -> Self {
+        match filter.parse::<ProgressStatus>() {
+            Ok(status) => self.retain(|r| r.progress == status),
+            Err(_) => info!("Invalid filter provided."),
+        }
+        self
+    }
+
+    /// The `summary` method returns the trend counts of the records.
+    pub fn summary(&self) -> ProgressSummary {
+        let mut summary = ProgressSummary::default();
+        for record in self.iter() {
+            match record.progress {
+                ProgressStatus::Resolved => summary.resolved += 1,
+                ProgressStatus::Removed => summary.removed += 1,
+                ProgressStatus::NewlyBroken => summary.newly_broken += 1,
+                ProgressStatus::StillOpen => summary.still_open += 1,
+                ProgressStatus::ChangedCategory => summary.changed_category += 1,
+                ProgressStatus::Unchanged => summary.unchanged += 1,
+            }
+        }
+        summary.previous_open =
+            summary.resolved + summary.removed + summary.still_open + summary.changed_category;
+        summary.current_open = summary.newly_broken + summary.still_open + summary.changed_category;
+        summary
+    }
+}
+
+impl IntoCsv<ProgressRecords> for ProgressRecords {
+    fn from_csv<P: AsRef<std::path::Path>>(path: P) -> Result<Self, Io> {
+        let records = from_csv(path)?;
+        Ok(Self(records))
+    }
+
+    fn to_csv<P: AsRef<std::path::Path>>(&mut self, path: P) -> Result<(), AddressErrorKind> {
+        to_csv(&mut self.0, path.as_ref().into())
+    }
+}
@@ -0,0 +1,348 @@
+//! The `reconcile` module contains types for comparing a source and target set of addresses in
+//! both directions, reporting addresses present only in the source, only in the target, and
+//! addresses present in both.
+use crate::{
+    Address, AddressComponent, AddressErrorKind, Geographic, IntoCsv, Io, MatchPolicy, MatchRule,
+    Mismatch, MismatchSeverity, from_csv, to_csv,
+};
+use derive_more::{Deref, DerefMut};
+use indicatif::ParallelProgressIterator;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use tracing::info;
+
+/// The `ReconcileStatus` enum describes the outcome of reconciling an address between a source
+/// and target set of addresses.
+#[derive(
+    Debug,
+    Default,
+    Copy,
+    Clone,
+    PartialEq,
+    PartialOrd,
+    Eq,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    strum::Display,
+    strum::EnumString,
+    strum::EnumIter,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum ReconcileStatus {
+    /// The address is present in the source, but has no match in the target.
+    #[default]
+    OnlySource,
+    /// The address is present in the target, but has no match in the source.
+    OnlyTarget,
+    /// The address is present in both sets, with no mismatched components.
+    Matched,
+    /// The address is present in both sets, but differs in components reported as a
+    /// [`Mismatch`] by the match policy.
+    Divergent,
+}
+
+/// The `ReconcileRecord` struct reports a single row of a reconciliation between a source and
+/// target set of addresses.  Fields for the side missing the address are empty.
+#[derive(Debug, Default, Clone, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct ReconcileRecord {
+    /// The `status` field is the outcome of the reconciliation.
+    pub status: ReconcileStatus,
+    /// The `source_label` field is the label of the source address, if present.
+    pub source_label: Option<String>,
+    /// The `target_label` field is the label of the target address, if present.
+    pub target_label: Option<String>,
+    /// The `source_index` field is the position of the source address in the source set, used
+    /// to join the row back to the source records.
+    pub source_index: Option<usize>,
+    /// The `target_index` field is the position of the target address in the target set, used
+    /// to join the row back to the target records.
+    pub target_index: Option<usize>,
+    /// The `source_id` field is the native identifier of the source address, if any.  See
+    /// [`Address::identifier`].
+    pub source_id: Option<String>,
+    /// The `target_id` field is the native identifier of the target address, if any.
+    pub target_id: Option<String>,
+    /// The `source_longitude` field is the 'x' value of the source address point.
+    pub source_longitude: Option<f64>,
+    /// The `source_latitude` field is the 'y' value of the source address point.
+    pub source_latitude: Option<f64>,
+    /// The `target_longitude` field is the 'x' value of the target address point.
+    pub target_longitude: Option<f64>,
+    /// The `target_latitude` field is the 'y' value of the target address point.
+    pub target_latitude: Option<f64>,
+    /// The `street` field is the complete street name of the address, taken from the source
+    /// address when present.
+    pub street: String,
+    /// The `zip` field is the postal zip code of the address, taken from the source address when
+    /// present.
+    pub zip: i64,
+    /// The `mismatches` field lists the mismatched components of a divergent record, separated
+    /// by semicolons.
+    pub mismatches: Option<String>,
+    /// The `severity` field is the highest [`MismatchSeverity`] among the mismatches of a
+    /// divergent record.
+    pub severity: Option<MismatchSeverity>,
+}
+
+impl ReconcileRecord {
+    /// The `source` method creates a record for the source address `address` at position
+    /// `index`, with no match in the target set.
+    pub fn source<T: Address + Geographic>(index: usize, address: &T) -> Self {
+        Self {
+            status: ReconcileStatus::OnlySource,
+            source_label: Some(address.label()),
+            source_index: Some(index),
+            source_id: address.identifier(),
+            source_longitude: Some(address.longitude()),
+            source_latitude: Some(address.latitude()),
+            street: address.complete_street_name(true),
+            zip: address.zip(),
+            ..Default::default()
+        }
+    }
+
+    /// The `target` method creates a record for the target address `address` at position
+    /// `index`, with no match in the source set.
+    pub fn target<T: Address + Geographic>(index: usize, address: &T) -> Self {
+        Self {
+            status: ReconcileStatus::OnlyTarget,
+            target_label: Some(address.label()),
+            target_index: Some(index),
+            target_id: address.identifier(),
+            target_longitude: Some(address.longitude()),
+            target_latitude: Some(address.latitude()),
+            street: address.complete_street_name(true),
+            zip: address.zip(),
+            ..Default::default()
+        }
+    }
+
+    /// The `matched` method creates a record for the source address at `source_index` matched to
+    /// the target address at `target_index`.  The record is divergent if `mismatches` is not
+    /// empty.
+    pub fn matched<T: Address + Geographic, U: Address + Geographic>(
+        source_index: usize,
+        source: &T,
+        target_index: usize,
+        target: &U,
+        mismatches: &[Mismatch],
+    ) -> Self {
+        let mut record = Self::source(source_index, source);
+        record.target_label = Some(target.label());
+        record.target_index = Some(target_index);
+        record.target_id = target.identifier();
+        record.target_longitude = Some(target.longitude());
+        record.target_latitude = Some(target.latitude());
+        if mismatches.is_empty() {
+            record.status = ReconcileStatus::Matched;
+        } else {
+            record.status = ReconcileStatus::Divergent;
+            record.mismatches = Some(
+                mismatches
+                    .iter()
+                    .map(|mismatch| mismatch.to_string())
+                    .collect::<Vec<String>>()
+                    .join("; "),
+            );
+            record.severity = mismatches.iter().map(|mismatch| mismatch.severity()).max();
+        }
+        record
+    }
+}
+
+/// The `ReconcileRecords` struct holds a vector of type [`ReconcileRecord`].
+#[derive(Debug, Default, Clone, PartialEq, PartialOrd, Serialize, Deserialize, Deref, DerefMut)]
+pub struct ReconcileRecords(Vec<ReconcileRecord>);
+
+impl ReconcileRecords {
+    /// The `compare` method reconciles `source` against `target` in a single pass, using
+    /// `policy` to determine which components must agree.  Each source address produces a row
+    /// for every coincident target address, or a single "only_source" row if it has no match.
+    /// Target addresses that match no source address produce an "only_target" row.  When the
+    /// policy requires the address number to agree, candidates are limited to target addresses
+    /// with the same address number.
+    pub fn compare<T: Address + Geographic + Send + Sync, U: Address + Geographic + Send + Sync>(
+        source: &[T],
+        target: &[U],
+        policy: &MatchPolicy,
+    ) -> Self {
+        let blocked = policy.rule(AddressComponent::Number) == MatchRule::Required;
+        let mut blocks: HashMap<i64, Vec<usize>> = HashMap::new();
+        if blocked {
+            for (index, address) in target.iter().enumerate() {
+                blocks.entry(address.number()).or_default().push(index);
+            }
+        }
+        let all = (0..target.len()).collect::<Vec<usize>>();
+        let style = indicatif::ProgressStyle::with_template(
+            "[{elapsed_precise}] {bar:40.cyan/blue} {pos:>7}/{len:7} {'Reconciling addresses.'}",
+        )
+        .unwrap();
+        let rows = source
+            .par_iter()
+            .enumerate()
+            .map(|(source_index, address)| {
+                let candidates = if blocked {
+                    match blocks.get(&address.number()) {
+                        Some(candidates) => candidates,
+                        None => return vec![ReconcileRecord::source(source_index, address)],
+                    }
+                } else {
+                    &all
+                };
+                let mut rows = Vec::new();
+                for &target_index in candidates {
+                    let other = &target[target_index];
+                    let address_match = address.coincident_with(other, policy);
+                    if address_match.coincident {
+                        let mismatches = address_match
+                            .mismatches
+                            .map(|mismatches| mismatches.to_vec())
+                            .unwrap_or_default();
+                        rows.push(ReconcileRecord::matched(
+                            source_index,
+                            address,
+                            target_index,
+                            other,
+                            &mismatches,
+                        ));
+                    }
+                }
+                if rows.is_empty() {
+                    rows.push(ReconcileRecord::source(source_index, address));
+                }
+                rows
+            })
+            .progress_with_style(style)
+            .collect::<Vec<Vec<ReconcileRecord>>>();
+        let mut records = rows.into_iter().flatten().collect::<Vec<ReconcileRecord>>();
+        let matched = records
+            .iter()
+            .filter_map(|record| record.target_index)
+            .collect::<HashSet<usize>>();
+        for (index, address) in target.iter().enumerate() {
+            if !matched.contains(&index) {
+                records.push(ReconcileRecord::target(index, address));
+            }
+        }
+        Self(records)
+    }
+
+    /// The `filter` method returns the subset of records with a [`ReconcileStatus`] matching
+    /// `filter`, which can take the values "only_source", "only_target", "matched" and
+    /// "divergent".
+    pub fn filter(mut self, filter: &str) -> Self {
+        match filter.parse::<ReconcileStatus>() {
+            Ok(status) => self.retain(|r| r.status == status),
+            Err(_) => info!("Invalid filter provided."),
+        }
+        self
+    }
+
+    /// The `summary` method counts the records by status, by street and by zip code.
+    pub fn summary(&self) -> ReconcileSummaries {
+        let mut status: BTreeMap<String, ReconcileSummary> = BTreeMap::new();
+        let mut street: BTreeMap<String, ReconcileSummary> = BTreeMap::new();
+        let mut zip: BTreeMap<String, ReconcileSummary> = BTreeMap::new();
+        for record in self.iter() {
+            status
+                .entry(record.status.to_string())
+                .or_insert_with_key(|value| ReconcileSummary::new("status", value))
+                .count(record.status);
+            street
+                .entry(record.street.clone())
+                .or_insert_with_key(|value| ReconcileSummary::new("street", value))
+                .count(record.status);
+            zip.entry(record.zip.to_string())
+                .or_insert_with_key(|value| ReconcileSummary::new("zip", value))
+                .count(record.status);
+        }
+        let summaries = status
+            .into_values()
+            .chain(street.into_values())
+            .chain(zip.into_values())
+            .collect::<Vec<ReconcileSummary>>();
+        ReconcileSummaries(summaries)
+    }
+}
+
+impl IntoCsv<ReconcileRecords> for ReconcileRecords {
+    fn from_csv<P: AsRef<std::path::Path>>(path: P) -> Result<Self, Io> {
+        let records = from_csv(path)?;
+        Ok(Self(records))
+    }
+
+    fn to_csv<P: AsRef<std::path::Path>>(&mut self, path: P) -> Result<(), AddressErrorKind> {
+        to_csv(&mut self.0, path.as_ref().into())
+    }
+}
+
+/// The `ReconcileSummary` struct counts reconciliation records by [`ReconcileStatus`] for a
+/// single value of a grouping field.
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct ReconcileSummary {
+    /// The `group` field names the grouping field, either "status", "street" or "zip".
+    pub group: String,
+    /// The `value` field is the value of the grouping field.
+    pub value: String,
+    /// The `only_source` field counts the records present only in the source.
+    pub only_source: usize,
+    /// The `only_target` field counts the records present only in the target.
+    pub only_target: usize,
+    /// The `matched` field counts the matched records.
+    pub matched: usize,
+    /// The `divergent` field counts the divergent records.
+    pub divergent: usize,
+    /// The `total` field counts all records with the value.
+    pub total: usize,
+}
+
+impl ReconcileSummary {
+    /// Creates an empty `ReconcileSummary` for the grouping field `group` with value `value`.
+    pub fn new(group: &str, value: &str) -> Self {
+        Self {
+            group: group.to_string(),
+            value: value.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// The `count` method adds a record with [`ReconcileStatus`] `status` to the counts.
+    pub fn count(&mut self, status: ReconcileStatus) {
+        match status {
+            ReconcileStatus::OnlySource => self.only_source += 1,
+            ReconcileStatus::OnlyTarget => self.only_target += 1,
+            ReconcileStatus::Matched => self.matched += 1,
+            ReconcileStatus::Divergent => self.divergent += 1,
+        }
+        self.total += 1;
+    }
+}
+
+/// The `ReconcileSummaries` struct holds a vector of type [`ReconcileSummary`].
+#[derive(Debug, Default, Clone, PartialEq, PartialOrd, Serialize, Deserialize, Deref, DerefMut)]
+pub struct ReconcileSummaries(Vec<ReconcileSummary>);
+
+impl ReconcileSummaries {
+    /// The `group` method returns the summaries for the grouping field `group`.
+    pub fn group(&self, group: &str) -> Vec<&ReconcileSummary> {
+        self.iter()
+            .filter(|summary| summary.group == group)
+            .collect()
+    }
+}
+
+impl IntoCsv<ReconcileSummaries> for ReconcileSummaries {
+    fn from_csv<P: AsRef<std::path::Path>>(path: P) -> Result<Self, Io> {
+        let records = from_csv(path)?;
+        Ok(Self(records))
+    }
+
+    fn to_csv<P: AsRef<std::path::Path>>(&mut self, path: P) -> Result<(), AddressErrorKind> {
+        to_csv(&mut self.0, path.as_ref().into())
+    }
+}
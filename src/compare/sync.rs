@@ -0,0 +1,341 @@
+//! The `sync` module synchronizes the published GIS business layer with the current business
+//! license export, reporting the changes needed to bring the layer up to date.
+use super::join::coincident;
+use crate::{
+    Address, AddressErrorKind, Addressable, BoundaryPoint, Business, BusinessLicense, Businesses,
+    Geographic, IntoCsv, Io, MatchPartialRecord, MatchPolicy, MatchStatus, PartialAddress,
+    from_csv, to_csv,
+};
+use derive_more::{Deref, DerefMut};
+use indicatif::ParallelProgressIterator;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tracing::{info, warn};
+
+/// The `SyncStatus` enum describes the change to a business between the GIS layer and the
+/// license export.
+#[derive(
+    Debug,
+    Default,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    strum::Display,
+    strum::EnumString,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum SyncStatus {
+    /// The license is in the export but not the layer, and should be added to the layer.
+    New,
+    /// The license is in the layer but not the export, and should be retired from the layer.
+    Closed,
+    /// The license is in both, but the address of the business changed.
+    Moved,
+    /// The license is in both at the same address, but the company name changed.
+    Renamed,
+    /// The license is in both with the same address and company name.
+    #[default]
+    Unchanged,
+}
+
+/// The `BusinessSyncRecord` struct reports the change to a single business location between the
+/// GIS layer and the license export.
+#[derive(Debug, Default, Clone, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct BusinessSyncRecord {
+    /// The `license` field is the license number of the business.
+    pub license: String,
+    /// The `status` field is the change to the business.
+    pub status: SyncStatus,
+    /// The `company_name` field is the company name, taken from the export when present.
+    pub company_name: String,
+    /// The `previous_company_name` field is the company name in the layer, if it differs from
+    /// the export.  A business that moved may also be renamed.
+    pub previous_company_name: Option<String>,
+    /// The `address_label` field is the address of the business, taken from the export when
+    /// present.
+    pub address_label: String,
+    /// The `previous_address_label` field is the address in the layer of a business that moved.
+    pub previous_address_label: Option<String>,
+    /// The `match_status` field is the status of geocoding the address of a new or moved
+    /// business against the address points.  A moved business that fails to geocode keeps its
+    /// previous location in the updated layer.
+    pub match_status: Option<MatchStatus>,
+    /// The `x_coordinate` field is the geocoded X coordinate in Web Mercator, if matching.
+    pub x_coordinate: Option<f64>,
+    /// The `y_coordinate` field is the geocoded Y coordinate in Web Mercator, if matching.
+    pub y_coordinate: Option<f64>,
+}
+
+impl BusinessSyncRecord {
+    /// The `is_renamed` method returns true if the company name in the export differs from the
+    /// layer, including businesses that also moved.
+    pub fn is_renamed(&self) -> bool {
+        self.previous_company_name.is_some()
+    }
+}
+
+/// The `BusinessSyncRecords` struct holds a vector of type [`BusinessSyncRecord`].
+#[derive(Debug, Default, Clone, PartialEq, PartialOrd, Serialize, Deserialize, Deref, DerefMut)]
+pub struct BusinessSyncRecords(Vec<BusinessSyncRecord>);
+
+impl BusinessSyncRecords {
+    /// The `filter` method returns the subset of records with a [`SyncStatus`] matching
+    /// `filter`, which can take the values "new", "closed", "moved", "renamed" and "unchanged".
+    /// The "renamed" option includes businesses that moved and were renamed.
+    pub fn filter(mut self, filter: &str) -> Self {
+        match filter.parse::<SyncStatus>() {
+            Ok(SyncStatus::Renamed) => self.retain(|r| r.is_renamed()),
+            Ok(status) => self.retain(|r| r.status == status),
+            Err(_) => info!("Invalid filter provided."),
+        }
+        self
+    }
+
+    /// The `count` method returns the number of records with a [`SyncStatus`] of `status`.
+    pub fn count(&self, status: SyncStatus) -> usize {
+        self.iter().filter(|r| r.status == status).count()
+    }
+}
+
+impl IntoCsv<BusinessSyncRecords> for BusinessSyncRecords {
+    fn from_csv<P: AsRef<std::path::Path>>(path: P) -> Result<Self, Io> {
+        let records = from_csv(path)?;
+        Ok(Self(records))
+    }
+
+    fn to_csv<P: AsRef<std::path::Path>>(&mut self, path: P) -> Result<(), AddressErrorKind> {
+        to_csv(&mut self.0, path.as_ref().into())
+    }
+}
+
+/// The `BusinessSync` struct holds the result of synchronizing the GIS business layer with the
+/// license export.
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct BusinessSync {
+    /// The `records` field reports the change to each business location.
+    pub records: BusinessSyncRecords,
+    /// The `layer` field is the updated business layer, with closed businesses retired, moved
+    /// and renamed businesses updated, and new businesses added.
+    pub layer: Businesses,
+}
+
+impl BusinessSync {
+    /// The `compare` method pairs the businesses in the GIS `layer` with the `licenses` in the
+    /// export by license number, then pairs the locations of each license by address, using
+    /// `policy` to determine whether the addresses agree.  Locations in the export without a
+    /// counterpart in the layer are geocoded against `addresses`, taking the coordinates of a
+    /// matching address.  Each row of the export is treated as a separate location, so
+    /// deduplicate the licenses first if the export repeats rows.
+    pub fn compare<T: Address + Geographic + Send + Sync>(
+        layer: &[Business],
+        licenses: &[BusinessLicense],
+        addresses: &[T],
+        policy: &MatchPolicy,
+    ) -> Self {
+        let mut exports: HashMap<String, Vec<usize>> = HashMap::new();
+        for (index, license) in licenses.iter().enumerate() {
+            exports.entry(license.license()).or_default().push(index);
+        }
+        let exported = licenses
+            .iter()
+            .map(|license| standardized(license.partial_address()))
+            .collect::<Vec<PartialAddress>>();
+        // Pair each business in the layer with a location of the license in the export,
+        // preferring a location at the same address.  Identical addresses are the same location
+        // even when the address has no number, which never coincides under the partial rules.
+        let mut paired = vec![false; licenses.len()];
+        let mut steps: Vec<(Option<usize>, Option<usize>, SyncStatus)> = Vec::new();
+        let mut unpaired: Vec<usize> = Vec::new();
+        for (index, business) in layer.iter().enumerate() {
+            let address = standardized(business.address());
+            let same = exports.get(&business.license()).and_then(|rows| {
+                rows.iter().copied().find(|&row| {
+                    !paired[row]
+                        && (address == exported[row]
+                            || coincident(&address, &exported[row], policy)
+                                == Some(MatchStatus::Matching))
+                })
+            });
+            match same {
+                Some(row) => {
+                    paired[row] = true;
+                    steps.push((Some(index), Some(row), SyncStatus::Unchanged));
+                }
+                None => unpaired.push(index),
+            }
+        }
+        for index in unpaired {
+            let moved = exports
+                .get(&layer[index].license())
+                .and_then(|rows| rows.iter().copied().find(|&row| !paired[row]));
+            match moved {
+                Some(row) => {
+                    paired[row] = true;
+                    steps.push((Some(index), Some(row), SyncStatus::Moved));
+                }
+                None => steps.push((Some(index), None, SyncStatus::Closed)),
+            }
+        }
+        steps.extend(
+            (0..licenses.len())
+                .filter(|&row| !paired[row])
+                .map(|row| (None, Some(row), SyncStatus::New)),
+        );
+        // Keep the businesses of the layer in order, followed by the new businesses.
+        steps.sort_by_key(|(index, row, _)| (index.is_none(), *index, *row));
+
+        let style = indicatif::ProgressStyle::with_template(
+            "[{elapsed_precise}] {bar:40.cyan/blue} {pos:>7}/{len:7} {'Synchronizing businesses.'}",
+        )
+        .unwrap();
+        let records = steps
+            .par_iter()
+            .map(|(index, row, status)| {
+                let business = index.map(|index| &layer[index]);
+                let license = row.map(|row| &licenses[row]);
+                sync_business(business, license, *status, layer, addresses, policy)
+            })
+            .progress_with_style(style)
+            .collect::<Vec<(BusinessSyncRecord, Option<Business>)>>();
+        let mut sync = Vec::with_capacity(records.len());
+        let mut updated = Vec::with_capacity(records.len());
+        for (record, business) in records {
+            sync.push(record);
+            updated.extend(business);
+        }
+        let records = BusinessSyncRecords(sync);
+        info!(
+            "{} new, {} closed, {} moved, {} renamed.",
+            records.count(SyncStatus::New),
+            records.count(SyncStatus::Closed),
+            records.count(SyncStatus::Moved),
+            records.iter().filter(|r| r.is_renamed()).count()
+        );
+        Self {
+            records,
+            layer: Businesses::new(updated),
+        }
+    }
+}
+
+/// The `sync_business` function reports the change with `status` between a `business` in the
+/// layer and a location of the `license` in the export, returning the record along with the
+/// updated business, or `None` if the business is closed or the license has an industry code
+/// the layer cannot hold.  New businesses take the industry name and tourism category of a
+/// business in `layer` with the same industry code.
+fn sync_business<T: Address + Geographic>(
+    business: Option<&Business>,
+    license: Option<&BusinessLicense>,
+    status: SyncStatus,
+    layer: &[Business],
+    addresses: &[T],
+    policy: &MatchPolicy,
+) -> (BusinessSyncRecord, Option<Business>) {
+    let mut record = BusinessSyncRecord {
+        status,
+        ..Default::default()
+    };
+    if let Some(business) = business {
+        record.license = business.license();
+        record.company_name = business.company_name();
+        record.address_label = business.address().label();
+    }
+    let Some(license) = license else {
+        return (record, None);
+    };
+    record.license = license.license();
+    record.address_label = license.partial_address().label();
+    if let Some(name) = license.company_name() {
+        if let Some(business) = business
+            && name.trim().to_uppercase() != business.company_name().trim().to_uppercase()
+        {
+            record.previous_company_name = Some(business.company_name());
+        }
+        record.company_name = name;
+    }
+    let mut coordinates = None;
+    if status == SyncStatus::New || status == SyncStatus::Moved {
+        let (match_status, geocoded) = geocode(license, addresses, policy);
+        record.match_status = Some(match_status);
+        record.x_coordinate = geocoded.map(|(x, _)| x);
+        record.y_coordinate = geocoded.map(|(_, y)| y);
+        coordinates = geocoded;
+    }
+    let updated = match business {
+        Some(business) => {
+            let mut updated = business.clone();
+            if record.is_renamed() {
+                updated.rename(license);
+                if status == SyncStatus::Unchanged {
+                    record.status = SyncStatus::Renamed;
+                }
+            }
+            if status == SyncStatus::Moved {
+                record.previous_address_label = Some(business.address().label());
+                if !updated.relocate(license.partial_address(), coordinates) {
+                    warn!(
+                        "License {} moved to {}, which failed to geocode.  Keeping the previous location.",
+                        record.license, record.address_label
+                    );
+                }
+            }
+            updated
+        }
+        None => match Business::from_license(license, layer, coordinates) {
+            Some(business) => business,
+            None => {
+                warn!(
+                    "License {} has invalid industry code {}.  Not added to the layer.",
+                    record.license,
+                    license.industry_code()
+                );
+                return (record, None);
+            }
+        },
+    };
+    (record, Some(updated))
+}
+
+/// The `geocode` function matches the address of `license` against `addresses` using `policy`,
+/// returning the best match status and the coordinates of a matching address in Web Mercator.
+fn geocode<T: Address + Geographic>(
+    license: &BusinessLicense,
+    addresses: &[T],
+    policy: &MatchPolicy,
+) -> (MatchStatus, Option<(f64, f64)>) {
+    let address = standardized(license.partial_address());
+    let records = MatchPartialRecord::compare_with_policy(&address, addresses, policy);
+    let status = records
+        .iter()
+        .map(|record| record.match_status())
+        .min()
+        .unwrap_or_default();
+    let coordinates = records
+        .iter()
+        .filter(|record| record.match_status() == MatchStatus::Matching)
+        .find_map(|record| match (record.longitude(), record.latitude()) {
+            (Some(longitude), Some(latitude)) => Some(
+                BoundaryPoint {
+                    longitude,
+                    latitude,
+                }
+                .web_mercator(),
+            ),
+            _ => None,
+        });
+    (status, coordinates)
+}
+
+/// The `standardized` function returns `address` after [`PartialAddress::standardize`].
+fn standardized(mut address: PartialAddress) -> PartialAddress {
+    address.standardize();
+    address
+}
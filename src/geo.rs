@@ -1,8 +1,9 @@
 //! The `geo` module defines spatial address types, and implements traits from the `galileo` crate for these types.
 use crate::{
     Address, AddressDelta, AddressDeltas, AddressError, AddressErrorKind, AddressStatus, Addresses,
-    CommonAddress, Decode, IntoBin, State, StreetNamePostType, StreetNamePreDirectional,
-    StreetNamePreModifier, StreetNamePreType, StreetSeparator, SubaddressType, from_bin, to_bin,
+    CommonAddress, Decode, IntoBin, IntoCsv, Io, State, StreetNamePostType,
+    StreetNamePreDirectional, StreetNamePreModifier, StreetNamePreType, StreetSeparator,
+    SubaddressType, from_bin, from_csv, to_bin, to_csv,
 };
 use derive_more::{Deref, DerefMut};
 use indicatif::ParallelProgressIterator;
@@ -16,8 +17,38 @@ pub trait Geographic {
     fn latitude(&self) -> f64;
     /// The `longitude` method returns the longitude component of the geographic coordinates.
     fn longitude(&self) -> f64;
+
+    /// The `haversine` method returns the great-circle distance in meters between `self` and
+    /// `other`, treating the coordinates as decimal degrees.
+    fn haversine<T: Geographic + ?Sized>(&self, other: &T) -> f64 {
+        let lat1 = self.latitude().to_radians();
+        let lat2 = other.latitude().to_radians();
+        let dlat = lat2 - lat1;
+        let dlon = (other.longitude() - self.longitude()).to_radians();
+        let a = (dlat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (dlon / 2.0).sin().powi(2);
+        2.0 * EARTH_RADIUS * a.sqrt().asin()
+    }
+
+    /// The `web_mercator` method returns the coordinates projected to Web Mercator (EPSG:3857)
+    /// as an `(x, y)` pair in meters, the projection of the published GIS layers.
+    fn web_mercator(&self) -> (f64, f64) {
+        let x = WEB_MERCATOR_RADIUS * self.longitude().to_radians();
+        let y = WEB_MERCATOR_RADIUS
+            * (std::f64::consts::FRAC_PI_4 + self.latitude().to_radians() / 2.0)
+                .tan()
+                .ln();
+        (x, y)
+    }
 }
 
+/// The `EARTH_RADIUS` constant is the mean radius of the Earth in meters, used by
+/// [`Geographic::haversine`].
+pub const EARTH_RADIUS: f64 = 6_371_008.8;
+
+/// The `WEB_MERCATOR_RADIUS` constant is the semi-major axis of the WGS 84 ellipsoid in meters,
+/// used as the sphere radius of [`Geographic::web_mercator`].
+const WEB_MERCATOR_RADIUS: f64 = 6_378_137.0;
+
 /// The type can produce cartesian coordinates.
 pub trait Cartesian {
     /// The `x` method returns the cartesian X portion of the projected coordinates of the address.
@@ -93,6 +124,10 @@ pub struct GeoAddress {
     pub latitude: f64,
     /// The `longitude` field represents the longitude of the geographic coordinates for the address.
     pub longitude: f64,
+    /// The `id` field holds the native identifier of the source record, if any.  See
+    /// [`Address::identifier`].
+    #[serde(default)]
+    pub id: Option<String>,
 }
 
 impl Address for GeoAddress {
@@ -223,6 +258,10 @@ impl Address for GeoAddress {
     fn status_mut(&mut self) -> &mut AddressStatus {
         &mut self.address.status
     }
+
+    fn identifier(&self) -> Option<String> {
+        self.id.clone()
+    }
 }
 
 impl Geographic for GeoAddress {
@@ -240,10 +279,12 @@ impl<T: Address + Geographic + Clone> From<&T> for GeoAddress {
         let address = CommonAddress::from(data);
         let latitude = data.latitude();
         let longitude = data.longitude();
+        let id = data.identifier();
         Self {
             address,
             latitude,
             longitude,
+            id,
         }
     }
 }
@@ -728,3 +769,75 @@ impl<T: Address + Geographic + Cartesian + Clone + Sized> From<&[T]> for Spatial
         Self(records)
     }
 }
+
+/// The `BoundaryPoint` struct holds a vertex of a [`Boundary`] polygon in geographic
+/// coordinates.
+#[derive(Debug, Default, Copy, Clone, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct BoundaryPoint {
+    /// The `longitude` field represents the 'x' value of the vertex.
+    pub longitude: f64,
+    /// The `latitude` field represents the 'y' value of the vertex.
+    pub latitude: f64,
+}
+
+impl Geographic for BoundaryPoint {
+    fn latitude(&self) -> f64 {
+        self.latitude
+    }
+
+    fn longitude(&self) -> f64 {
+        self.longitude
+    }
+}
+
+/// The `Boundary` struct holds the vertices of a polygon in geographic coordinates, such as
+/// the city limits.  The polygon is closed implicitly, from the last vertex back to the first.
+#[derive(
+    Debug,
+    Clone,
+    Default,
+    PartialEq,
+    PartialOrd,
+    Serialize,
+    Deserialize,
+    derive_new::new,
+    Deref,
+    DerefMut,
+)]
+pub struct Boundary(Vec<BoundaryPoint>);
+
+impl Boundary {
+    /// The `contains` method returns true if `point` lies inside the boundary, using the
+    /// even-odd rule.
+    pub fn contains<T: Geographic + ?Sized>(&self, point: &T) -> bool {
+        let (x, y) = (point.longitude(), point.latitude());
+        let mut inside = false;
+        let mut previous = match self.last() {
+            Some(vertex) => vertex,
+            None => return false,
+        };
+        for vertex in self.iter() {
+            if (vertex.latitude > y) != (previous.latitude > y) {
+                let crossing = (previous.longitude - vertex.longitude) * (y - vertex.latitude)
+                    / (previous.latitude - vertex.latitude)
+                    + vertex.longitude;
+                if x < crossing {
+                    inside = !inside;
+                }
+            }
+            previous = vertex;
+        }
+        inside
+    }
+}
+
+impl IntoCsv<Boundary> for Boundary {
+    fn from_csv<P: AsRef<Path>>(path: P) -> Result<Self, Io> {
+        let records = from_csv(path)?;
+        Ok(Self(records))
+    }
+
+    fn to_csv<P: AsRef<Path>>(&mut self, path: P) -> Result<(), AddressErrorKind> {
+        to_csv(&mut self.0, path.as_ref().into())
+    }
+}
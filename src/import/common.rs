@@ -72,6 +72,9 @@ pub struct SpatialAddressRaw {
     /// The `y` field represents the cartesian Y portion of the projected coordinates of the
     /// address.
     pub y: f64,
+    /// The `id` field holds the native identifier of the source record, if present.
+    #[serde(default, deserialize_with = "deserialize_arcgis_data")]
+    pub id: Option<String>,
 }
 
 impl From<SpatialAddressRaw> for CommonAddress {
@@ -104,6 +107,7 @@ impl From<SpatialAddressRaw> for GeoAddress {
             address,
             longitude: value.longitude,
             latitude: value.latitude,
+            id: value.id,
         }
     }
 }
@@ -1,6 +1,8 @@
 //! The `fire_inspections` module imports data from fire inspections into the library to facilitate
 //! address matching.
-use crate::{AddressErrorKind, Io, Nom, Parse, PartialAddress};
+use crate::{
+    AddressErrorKind, Addressable, Io, Nom, Parse, PartialAddress, Rejections, from_csv_with,
+};
 
 /// The `FireInspectionRaw` struct functions as a builder for a [`FireInspection`] struct.
 /// The fields correspond to the csv of fire inspection data from the fire department.
@@ -20,31 +22,6 @@ pub struct FireInspectionRaw {
     subclass: Option<String>,
 }
 
-/// The `FireInspectionsRaw` struct is a wrapper around a vector of type [`FireInspectionRaw`]
-#[derive(
-    Debug,
-    Clone,
-    PartialEq,
-    Eq,
-    PartialOrd,
-    Ord,
-    Hash,
-    serde::Deserialize,
-    serde::Serialize,
-    derive_more::Deref,
-    derive_more::DerefMut,
-)]
-pub struct FireInspectionsRaw(Vec<FireInspectionRaw>);
-
-impl FireInspectionsRaw {
-    /// Used to read fire inspection data in from the csv source file.
-    #[tracing::instrument(skip_all)]
-    pub fn from_csv<P: AsRef<std::path::Path>>(path: P) -> Result<Self, Io> {
-        let records = crate::from_csv(path)?;
-        Ok(FireInspectionsRaw(records))
-    }
-}
-
 /// The `FireInspection` struct contains fields from a fire inspection record, with the business
 /// address mapped to a [`PartialAddress`].  Built from a [`FireInspectionRaw`].
 #[derive(
@@ -101,6 +78,12 @@ impl TryFrom<FireInspectionRaw> for FireInspection {
     }
 }
 
+impl Addressable for FireInspection {
+    fn partial_address(&self) -> PartialAddress {
+        self.address.clone()
+    }
+}
+
 /// The `FireInspections` struct is a wrapper around a vector of type [`FireInspection`].
 #[derive(
     Debug,
@@ -119,16 +102,25 @@ pub struct FireInspections(Vec<FireInspection>);
 
 impl FireInspections {
     /// Reads in the data as a raw fire inspections, attempts to parse each address, returning a
-    /// `FireInspections` if successful.
+    /// `FireInspections` with the inspections that parse successfully.  Inspections that fail to
+    /// deserialize or parse are dropped and counted in the log.  Use
+    /// [`FireInspections::from_csv_with_rejections`] to report the dropped rows.
     #[tracing::instrument(skip_all)]
     pub fn from_csv<P: AsRef<std::path::Path>>(path: P) -> Result<Self, AddressErrorKind> {
-        // Try to read in as raw.
-        let raw = FireInspectionsRaw::from_csv(path)?;
-        let mut records = Vec::new();
-        for record in raw.iter() {
-            // Parse the raw address.
-            records.push(FireInspection::try_from(record.clone())?);
-        }
-        Ok(FireInspections(records))
+        let (records, rejections) = Self::from_csv_with_rejections(path)?;
+        tracing::info!("{} records dropped.", rejections.len());
+        Ok(records)
+    }
+
+    /// Reads in the data as raw fire inspections, attempts to parse each address, and returns the
+    /// inspections that parse successfully along with a [`Rejections`] report of the rows that
+    /// failed to deserialize or parse.
+    #[tracing::instrument(skip_all)]
+    pub fn from_csv_with_rejections<P: AsRef<std::path::Path>>(
+        path: P,
+    ) -> Result<(Self, Rejections), Io> {
+        let (records, rejections) =
+            from_csv_with(path, |raw: FireInspectionRaw| FireInspection::try_from(raw))?;
+        Ok((FireInspections(records), rejections))
     }
 }
@@ -265,6 +265,10 @@ impl Address for GrantsPassAddress {
     fn status_mut(&mut self) -> &mut AddressStatus {
         &mut self.status
     }
+
+    fn identifier(&self) -> Option<String> {
+        Some(self.global_id.clone())
+    }
 }
 
 /// The `GrantsPassAddresses` struct holds a vector of type
@@ -576,6 +580,10 @@ impl Address for GrantsPassSpatialAddress {
     fn status_mut(&mut self) -> &mut AddressStatus {
         &mut self.status
     }
+
+    fn identifier(&self) -> Option<String> {
+        Some(self.global_id.clone())
+    }
 }
 
 impl Cartesian for GrantsPassSpatialAddress {
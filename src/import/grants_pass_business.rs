@@ -1,11 +1,45 @@
 //! The `grants_pass_business` module contains data types for importing business license reports
 //! for the City of Grants Pass.
 use crate::{
-    AddressError, AddressErrorKind, Decode, IntoBin, IntoCsv, Io, Nom, Parse, PartialAddress,
-    from_bin, from_csv, to_bin, to_csv,
+    AddressError, AddressErrorKind, Addressable, BusinessLicense, Decode, IntoBin, IntoCsv, Io,
+    Nom, Parse, PartialAddress, Rejections, from_bin, from_csv, from_csv_with, to_bin, to_csv,
 };
 use derive_more::{Deref, DerefMut};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// The `Coordinate` struct holds a coordinate of a business point.  Coordinates compare by
+/// [`f64::total_cmp`], so that business types holding them keep their `Eq`, `Ord` and `Hash`
+/// implementations.
+#[derive(Copy, Clone, Debug, Deserialize, Serialize)]
+#[serde(transparent)]
+struct Coordinate(f64);
+
+impl PartialEq for Coordinate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == std::cmp::Ordering::Equal
+    }
+}
+
+impl Eq for Coordinate {}
+
+impl PartialOrd for Coordinate {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Coordinate {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+impl std::hash::Hash for Coordinate {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.0.to_bits().hash(state);
+    }
+}
 
 /// The `BusinessRaw` struct contains business license records. Serves as an intermediary for
 /// creating a [`Business`] struct when reading the data in from a csv.  Mainly this involves
@@ -26,23 +60,14 @@ pub struct BusinessRaw {
     subsector_name: Option<String>,
     tourism: Option<String>,
     district: Option<String>,
-}
-
-/// The `BusinessesRaw` struct is a wrapper for a vector of type [`BusinessRaw`].
-#[derive(
-    Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize, Deref, DerefMut,
-)]
-pub struct BusinessesRaw(Vec<BusinessRaw>);
-
-impl BusinessesRaw {
-    /// Writes the contents of the struct to a csv file at location `path`.
-    pub fn from_csv<P: AsRef<std::path::Path>>(path: P) -> Result<Self, Io> {
-        let records = from_csv(path)?;
-        Ok(Self(records))
-    }
+    x_coordinate: Option<Coordinate>,
+    y_coordinate: Option<Coordinate>,
 }
 
 /// The `Business` struct holds query information for active business licenses, for access in GIS.
+/// The coordinates of the business point are serialized with the business, so binary files of
+/// [`Businesses`] saved before the coordinates were added no longer decode, and must be saved
+/// again from the csv of the GIS layer.
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
 pub struct Business {
     // The official name of the company.
@@ -71,6 +96,12 @@ pub struct Business {
     tourism: Option<String>,
     // The business district name of the GC zone, if in a GC zone.
     district: Option<String>,
+    // The cartesian X coordinate of the business point, in Web Mercator.
+    #[serde(default)]
+    x_coordinate: Option<Coordinate>,
+    // The cartesian Y coordinate of the business point, in Web Mercator.
+    #[serde(default)]
+    y_coordinate: Option<Coordinate>,
 }
 
 impl Business {
@@ -144,6 +175,107 @@ impl Business {
     pub fn district(&self) -> Option<String> {
         self.district.clone()
     }
+
+    /// The `x_coordinate` method returns the cartesian X coordinate of the business point, in
+    /// Web Mercator.
+    pub fn x_coordinate(&self) -> Option<f64> {
+        self.x_coordinate.map(|x| x.0)
+    }
+
+    /// The `y_coordinate` method returns the cartesian Y coordinate of the business point, in
+    /// Web Mercator.
+    pub fn y_coordinate(&self) -> Option<f64> {
+        self.y_coordinate.map(|y| y.0)
+    }
+
+    /// The `from_license` method creates a business point for `license`, located at
+    /// `coordinates` in Web Mercator if geocoded.  The sector and subsector are looked up from
+    /// the industry code.  The industry name and tourism category are copied from a business in
+    /// `templates` with the same industry code, falling back to the subsector description.
+    /// Returns `None` if the industry code of the license does not fit the industry code of the
+    /// layer.
+    pub fn from_license(
+        license: &BusinessLicense,
+        templates: &[Business],
+        coordinates: Option<(f64, f64)>,
+    ) -> Option<Self> {
+        let industry_code = i32::try_from(license.industry_code()).ok()?;
+        let naics = license.naics();
+        let template = templates
+            .iter()
+            .find(|business| business.industry_code == industry_code);
+        Some(Self {
+            company_name: license.company_name().unwrap_or_default(),
+            contact_name: license.contact_name(),
+            dba: license.dba(),
+            address: license.partial_address(),
+            license: license.license(),
+            industry_code,
+            industry_name: match template {
+                Some(business) => business.industry_name.clone(),
+                None => naics
+                    .and_then(|n| n.subsector_name)
+                    .unwrap_or_default()
+                    .to_string(),
+            },
+            sector_code: naics.map_or(0, |n| n.sector_code),
+            sector_name: naics.map_or("", |n| n.sector_name).to_string(),
+            subsector_code: naics.map_or(0, |n| n.subsector_code),
+            subsector_name: naics.and_then(|n| n.subsector_name.map(|s| s.to_string())),
+            tourism: template.and_then(|business| business.tourism.clone()),
+            district: None,
+            x_coordinate: coordinates.map(|(x, _)| Coordinate(x)),
+            y_coordinate: coordinates.map(|(_, y)| Coordinate(y)),
+        })
+    }
+
+    /// The `relocate` method sets the address of the business to `address`, located at
+    /// `coordinates` in Web Mercator if geocoded.  If the new address failed to geocode, the
+    /// business keeps its previous location and the method returns false, so the caller can
+    /// report the failure.
+    pub fn relocate(&mut self, address: PartialAddress, coordinates: Option<(f64, f64)>) -> bool {
+        self.address = address;
+        match coordinates {
+            Some((x, y)) => {
+                self.x_coordinate = Some(Coordinate(x));
+                self.y_coordinate = Some(Coordinate(y));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// The `rename` method sets the company name, contact name and business alias to the values
+    /// of `license`.
+    pub fn rename(&mut self, license: &BusinessLicense) {
+        if let Some(name) = license.company_name() {
+            self.company_name = name;
+        }
+        self.contact_name = license.contact_name();
+        self.dba = license.dba();
+    }
+}
+
+impl From<&Business> for BusinessRaw {
+    fn from(business: &Business) -> Self {
+        Self {
+            company_name: business.company_name.clone(),
+            contact_name: business.contact_name.clone(),
+            dba: business.dba.clone(),
+            street_address_label: business.address.label(),
+            license: business.license.clone(),
+            industry_code: business.industry_code,
+            industry_name: business.industry_name.clone(),
+            sector_code: business.sector_code,
+            sector_name: business.sector_name.clone(),
+            subsector_code: business.subsector_code,
+            subsector_name: business.subsector_name.clone(),
+            tourism: business.tourism.clone(),
+            district: business.district.clone(),
+            x_coordinate: business.x_coordinate,
+            y_coordinate: business.y_coordinate,
+        }
+    }
 }
 
 impl TryFrom<BusinessRaw> for Business {
@@ -169,6 +301,8 @@ impl TryFrom<BusinessRaw> for Business {
                 subsector_name: raw.subsector_name,
                 tourism: raw.tourism,
                 district: raw.district,
+                x_coordinate: raw.x_coordinate,
+                y_coordinate: raw.y_coordinate,
             }),
             // Throw an error if parsing fails.
             Err(source) => Err(Nom::new(
@@ -181,6 +315,12 @@ impl TryFrom<BusinessRaw> for Business {
     }
 }
 
+impl Addressable for Business {
+    fn partial_address(&self) -> PartialAddress {
+        self.address.clone()
+    }
+}
+
 /// The `Businesses` struct is a wrapper around a vector of type [`Business`].
 /// This struct contains business licenses that have mapped to valid addresses.
 #[derive(
@@ -189,14 +329,52 @@ impl TryFrom<BusinessRaw> for Business {
 pub struct Businesses(Vec<Business>);
 
 impl Businesses {
+    /// Creates a new `Businesses` struct from the provided `records`, a vector of [`Business`]
+    /// objects.
+    pub fn new(records: Vec<Business>) -> Self {
+        Self(records)
+    }
+
     /// Writes the contents to a csv file at location `path`.
     pub fn from_raw_csv<P: AsRef<std::path::Path>>(path: P) -> Result<Self, AddressErrorKind> {
-        let raw = BusinessesRaw::from_csv(path)?;
-        let mut records = Vec::new();
-        for record in raw.iter() {
-            records.push(Business::try_from(record.clone())?);
-        }
-        Ok(Businesses(records))
+        let (records, rejections) = Self::from_raw_csv_with_rejections(path)?;
+        tracing::info!("{} records dropped.", rejections.len());
+        Ok(records)
+    }
+
+    /// Reads raw business records from a csv file at location `path`, parsing the address of
+    /// each record.  Returns the businesses that parse successfully along with a [`Rejections`]
+    /// report of the rows that failed to deserialize or parse.
+    pub fn from_raw_csv_with_rejections<P: AsRef<std::path::Path>>(
+        path: P,
+    ) -> Result<(Self, Rejections), Io> {
+        let (records, rejections) =
+            from_csv_with(path, |raw: BusinessRaw| Business::try_from(raw))?;
+        Ok((Businesses(records), rejections))
+    }
+
+    /// Writes the businesses to a csv file at location `path` in the schema of the GIS layer,
+    /// with the address written as the `street_address_label`, so the file can be read by
+    /// [`Businesses::from_raw_csv`].
+    pub fn to_raw_csv<P: AsRef<std::path::Path>>(&self, path: P) -> Result<(), AddressErrorKind> {
+        let mut records = self
+            .iter()
+            .map(BusinessRaw::from)
+            .collect::<Vec<BusinessRaw>>();
+        to_csv(&mut records, path.as_ref().into())
+    }
+
+    /// The `districts` method maps the license number of each business in a business district
+    /// to the name of the district.
+    pub fn districts(&self) -> HashMap<String, String> {
+        self.iter()
+            .filter_map(|business| {
+                business
+                    .district
+                    .as_ref()
+                    .map(|district| (business.license.clone(), district.clone()))
+            })
+            .collect()
     }
 }
 
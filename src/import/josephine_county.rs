@@ -91,6 +91,10 @@ pub struct JosephineCountyAddress2024 {
     /// The `status` field represents the local status of the address as determined by the relevant
     /// addressing authority.
     pub status: AddressStatus,
+    /// The `object_id` field holds the ESRI object id associated with the feature, if present in
+    /// the source data.
+    #[serde(default, rename = "OBJECTID", deserialize_with = "csv::invalid_option")]
+    pub object_id: Option<i64>,
 }
 
 impl Address for JosephineCountyAddress2024 {
@@ -221,6 +225,10 @@ impl Address for JosephineCountyAddress2024 {
     fn status_mut(&mut self) -> &mut AddressStatus {
         &mut self.status
     }
+
+    fn identifier(&self) -> Option<String> {
+        self.object_id.map(|id| id.to_string())
+    }
 }
 
 /// The `JosephineCountyAddresses2024` struct holds a vector of type
@@ -343,6 +351,10 @@ pub struct JosephineCountySpatialAddress2024 {
     /// The `status` field represents the local status of the address as determined by the relevant
     /// addressing authority.
     pub status: AddressStatus,
+    /// The `object_id` field holds the ESRI object id associated with the feature, if present in
+    /// the source data.
+    #[serde(default, rename = "OBJECTID", deserialize_with = "csv::invalid_option")]
+    pub object_id: Option<i64>,
     /// The `x` field represents the cartesian X portion of the projected coordinates of the
     /// address.
     pub x: f64,
@@ -485,6 +497,10 @@ impl Address for JosephineCountySpatialAddress2024 {
     fn status_mut(&mut self) -> &mut AddressStatus {
         &mut self.status
     }
+
+    fn identifier(&self) -> Option<String> {
+        self.object_id.map(|id| id.to_string())
+    }
 }
 
 impl Cartesian for JosephineCountySpatialAddress2024 {
@@ -762,6 +778,10 @@ impl Address for JosephineCountyAddress {
     fn status_mut(&mut self) -> &mut AddressStatus {
         &mut self.status
     }
+
+    fn identifier(&self) -> Option<String> {
+        self.taxlot.clone()
+    }
 }
 
 /// The `JosephineCountyAddresses` struct holds a vector of type
@@ -1033,6 +1053,10 @@ impl Address for JosephineCountySpatialAddress {
     fn status_mut(&mut self) -> &mut AddressStatus {
         &mut self.status
     }
+
+    fn identifier(&self) -> Option<String> {
+        self.taxlot.clone()
+    }
 }
 
 impl Cartesian for JosephineCountySpatialAddress {
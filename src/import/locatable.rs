@@ -0,0 +1,203 @@
+//! The `locatable` module imports records from any csv file with an address, such as building
+//! permits, code enforcement cases or utility accounts, to facilitate address matching.
+use crate::{
+    AddressComponent, AddressErrorKind, Addressable, Builder, Csv, Parse, PartialAddress,
+    Rejections, from_csv_with,
+};
+use derive_more::{Deref, DerefMut};
+use std::collections::HashMap;
+
+/// The `LOCATABLE_COMPONENTS` constant lists the components that can be read from component
+/// columns, in the order they appear in an address label.
+const LOCATABLE_COMPONENTS: [AddressComponent; 13] = [
+    AddressComponent::Number,
+    AddressComponent::NumberSuffix,
+    AddressComponent::Directional,
+    AddressComponent::PreModifier,
+    AddressComponent::PreType,
+    AddressComponent::Separator,
+    AddressComponent::StreetName,
+    AddressComponent::StreetType,
+    AddressComponent::SubaddressType,
+    AddressComponent::SubaddressId,
+    AddressComponent::PostalCommunity,
+    AddressComponent::State,
+    AddressComponent::Zip,
+];
+
+/// The `AddressColumns` enum describes where the address of a locatable record is held in the
+/// source csv.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AddressColumns {
+    /// A single column holds the complete address, such as "1855 NE STEPHENS ST".
+    Label(String),
+    /// Several columns each hold a component of the address, paired with the name of the column.
+    /// The components are joined in label order before parsing, so the order of the pairs does
+    /// not matter.
+    Components(Vec<(AddressComponent, String)>),
+}
+
+impl AddressColumns {
+    /// The `from_spec` method reads the address columns from a text specification.  A plain
+    /// column name, such as "address", reads the complete address from that column.  A list of
+    /// `component=column` pairs separated by semicolons, such as
+    /// "number=ADDR_NUM;directional=PRE_DIR;street_name=STREET;street_type=SUFFIX", reads each
+    /// component from the named column.  Components use the names of [`AddressComponent`], and
+    /// the floor, building and status components are not supported.
+    pub fn from_spec(spec: &str) -> Result<Self, Builder> {
+        if !spec.contains('=') {
+            return Ok(Self::Label(spec.trim().to_string()));
+        }
+        let mut columns = Vec::new();
+        for pair in spec.split(';').filter(|pair| !pair.trim().is_empty()) {
+            let issue = || {
+                Builder::new(
+                    format!("invalid component column '{}'", pair.trim()),
+                    "AddressColumns".to_string(),
+                    line!(),
+                    file!().to_string(),
+                )
+            };
+            let (component, column) = pair.split_once('=').ok_or_else(issue)?;
+            let component = component
+                .trim()
+                .parse::<AddressComponent>()
+                .map_err(|_| issue())?;
+            if !LOCATABLE_COMPONENTS.contains(&component) {
+                return Err(issue());
+            }
+            columns.push((component, column.trim().to_string()));
+        }
+        Ok(Self::Components(columns))
+    }
+
+    /// The `columns` method returns the names of the columns holding the address.
+    pub fn columns(&self) -> Vec<String> {
+        match self {
+            Self::Label(column) => vec![column.clone()],
+            Self::Components(columns) => columns.iter().map(|(_, column)| column.clone()).collect(),
+        }
+    }
+
+    /// The `text` method returns the address text of `row`, a map of column names to values,
+    /// joining component columns in label order.
+    fn text(&self, row: &HashMap<String, String>) -> String {
+        let value = |column: &str| row.get(column).map_or("", |value| value.trim());
+        match self {
+            Self::Label(column) => value(column).to_string(),
+            Self::Components(columns) => LOCATABLE_COMPONENTS
+                .iter()
+                .flat_map(|component| {
+                    columns
+                        .iter()
+                        .filter(move |(c, _)| c == component)
+                        .map(|(_, column)| value(column))
+                })
+                .filter(|value| !value.is_empty())
+                .collect::<Vec<&str>>()
+                .join(" "),
+        }
+    }
+}
+
+/// The `LocatableRecord` struct holds the passthrough attributes of a record from an arbitrary
+/// csv source, with the address of the record mapped to a [`PartialAddress`].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LocatableRecord {
+    /// The `attributes` field holds the values of the passthrough columns, in the order of the
+    /// `columns` field of the [`LocatableRecords`].
+    pub attributes: Vec<String>,
+    /// The `address` field holds the parsed and standardized address of the record.
+    pub address: PartialAddress,
+}
+
+impl LocatableRecord {
+    /// The `from_row` method builds a record from `row`, a map of column names to values,
+    /// reading the address from `address` and copying the `attributes` columns.  Errors if the
+    /// address is empty or fails to parse.
+    fn from_row(
+        row: &HashMap<String, String>,
+        address: &AddressColumns,
+        attributes: &[String],
+    ) -> Result<Self, String> {
+        let text = address.text(row).to_uppercase();
+        if text.is_empty() {
+            return Err("empty address".to_string());
+        }
+        match Parse::address(&text) {
+            Ok((_, mut parsed)) => {
+                parsed.standardize();
+                Ok(Self {
+                    attributes: attributes
+                        .iter()
+                        .map(|column| row.get(column).cloned().unwrap_or_default())
+                        .collect(),
+                    address: parsed,
+                })
+            }
+            Err(source) => Err(format!("nom parsing error: {} from {}", source, text)),
+        }
+    }
+}
+
+impl Addressable for LocatableRecord {
+    fn partial_address(&self) -> PartialAddress {
+        self.address.clone()
+    }
+}
+
+/// The `LocatableRecords` struct holds a vector of type [`LocatableRecord`], along with the
+/// names of the passthrough columns.
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Deref, DerefMut)]
+pub struct LocatableRecords {
+    /// The `columns` field holds the names of the passthrough columns.
+    pub columns: Vec<String>,
+    /// The `records` field holds the locatable records.
+    #[deref]
+    #[deref_mut]
+    pub records: Vec<LocatableRecord>,
+}
+
+impl LocatableRecords {
+    /// Reads records from a csv file at location `path`, reading the address of each record from
+    /// `address` and keeping the passthrough columns named in `attributes`, or every column of
+    /// the file if `attributes` is empty.  Returns the records with a parsed address along with
+    /// a [`Rejections`] report of the rows that failed to deserialize or parse.  Errors if the
+    /// file cannot be read or lacks a named column.
+    pub fn from_csv_with_rejections<P: AsRef<std::path::Path>>(
+        path: P,
+        address: &AddressColumns,
+        attributes: &[String],
+    ) -> Result<(Self, Rejections), AddressErrorKind> {
+        let headers = csv::Reader::from_path(&path)
+            .and_then(|mut rdr| rdr.headers().cloned())
+            .map_err(|source| Csv::new(path.as_ref().into(), source, line!(), file!().into()))?
+            .iter()
+            .map(|header| header.to_string())
+            .collect::<Vec<String>>();
+        let columns = if attributes.is_empty() {
+            headers.clone()
+        } else {
+            attributes.to_vec()
+        };
+        if let Some(missing) = address
+            .columns()
+            .iter()
+            .chain(columns.iter())
+            .find(|column| !headers.contains(column))
+        {
+            return Err(Builder::new(
+                format!("column '{}' not found in {:?}", missing, path.as_ref()),
+                "LocatableRecords".to_string(),
+                line!(),
+                file!().to_string(),
+            )
+            .into());
+        }
+        let (records, rejections) = from_csv_with(path, |row: HashMap<String, String>| {
+            LocatableRecord::from_row(&row, address, &columns)
+        })?;
+        tracing::info!("{} records dropped.", rejections.len());
+        Ok((Self { columns, records }, rejections))
+    }
+}
@@ -13,6 +13,7 @@ mod error;
 mod geo;
 mod import;
 mod lexisnexis;
+mod naics;
 mod parser;
 mod utils;
 
@@ -24,29 +25,45 @@ pub use address_components::{
     AddressStatus, PostalCommunity, State, StreetNamePostType, StreetNamePreDirectional,
     StreetNamePreModifier, StreetNamePreType, StreetSeparator, SubaddressType, zero_floor,
 };
-pub use business::{BusinessLicense, BusinessLicenses, BusinessMatchRecord, BusinessMatchRecords};
+pub use business::{
+    BusinessLicense, BusinessLicenses, BusinessMatchRecord, BusinessMatchRecords, Jurisdiction,
+};
 pub use cli::Cli;
 pub use compare::{
-    AddressMatch, FireInspectionMatch, FireInspectionMatchRecord, FireInspectionMatchRecords,
-    FireInspectionMatches, MatchPartialRecord, MatchPartialRecords, MatchRecord, MatchRecords,
-    MatchStatus, Mismatch,
+    AddressComponent, AddressMatch, Addressable, BusinessSync, BusinessSyncRecord,
+    BusinessSyncRecords, Cardinality, Conflation, Correction, CorrectionRecord, CorrectionRecords,
+    Corrections, DiffRecord, DiffRecords, DiffStatus, Distance, DuplicateGroup, DuplicateGroups,
+    DuplicateRecord, DuplicateRecords, FireInspectionMatch, FireInspectionMatchRecord,
+    FireInspectionMatchRecords, FireInspectionMatches, FuzzyIndex, FuzzyMatchRecord,
+    FuzzyMatchRecords, JoinKind, JoinPair, JoinPairs, LocatableMatchRecord, LocatableMatchRecords,
+    MatchPartialRecord, MatchPartialRecords, MatchPolicy, MatchRecord, MatchRecords, MatchRule,
+    MatchStatus, MatrixRecord, MatrixRecords, Mismatch, MismatchRecord, MismatchRecords,
+    MismatchSeverity, NearDuplicate, NearDuplicateRecord, NearDuplicateRecords, NearDuplicates,
+    PhoneticGroup, PhoneticGroups, PhoneticRecord, PhoneticRecords, PrecedencePolicy, Presence,
+    ProgressRecord, ProgressRecords, ProgressStatus, ProgressSummary, ProvenanceRecord,
+    ProvenanceRecords, ReconcileRecord, ReconcileRecords, ReconcileStatus, ReconcileSummaries,
+    ReconcileSummary, Region, SimilarityScore, SyncStatus, metaphone,
 };
 pub use error::{AddressError, AddressErrorKind, Builder, Csv, Decode, Encode, Io, Nom};
 pub use geo::{
-    AddressPoints, Cartesian, GeoAddress, GeoAddresses, Geographic, SpatialAddress,
-    SpatialAddresses,
+    AddressPoints, Boundary, BoundaryPoint, Cartesian, EARTH_RADIUS, GeoAddress, GeoAddresses,
+    Geographic, SpatialAddress, SpatialAddresses,
 };
 pub use import::{
-    Business, Businesses, FireInspection, FireInspectionRaw, FireInspections, GrantsPassAddress,
-    GrantsPassAddresses, GrantsPassSpatialAddress, GrantsPassSpatialAddresses,
+    AddressColumns, Business, Businesses, FireInspection, FireInspectionRaw, FireInspections,
+    GrantsPassAddress, GrantsPassAddresses, GrantsPassSpatialAddress, GrantsPassSpatialAddresses,
     JosephineCountyAddress, JosephineCountyAddress2024, JosephineCountyAddresses,
     JosephineCountyAddresses2024, JosephineCountySpatialAddress, JosephineCountySpatialAddress2024,
-    JosephineCountySpatialAddresses, JosephineCountySpatialAddresses2024, SpatialAddressesRaw,
+    JosephineCountySpatialAddresses, JosephineCountySpatialAddresses2024, LocatableRecord,
+    LocatableRecords, SpatialAddressesRaw,
 };
 pub use lexisnexis::{
     LexisNexis, LexisNexisItem, LexisNexisItemBuilder, LexisNexisRange, LexisNexisRangeItem,
 };
+pub use naics::{Naics, NaicsLevel, NaicsSummaries, NaicsSummary};
 pub use parser::{Parse, deserialize_phone_number, parse_phone_number};
 pub use utils::{
-    IntoBin, IntoCsv, deserialize_arcgis_data, from_bin, from_csv, to_bin, to_csv, trace_init,
+    IntoBin, IntoCsv, RECORD_NAMESPACE, Rejection, Rejections, deserialize_arcgis_data,
+    deserialize_date, from_bin, from_csv, from_csv_with, record_id, record_identities,
+    record_identity, to_bin, to_csv, trace_init,
 };
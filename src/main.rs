@@ -1,11 +1,18 @@
 use clap::Parser;
 use destination::{
-    Addresses, BusinessLicenses, BusinessMatchRecords, Cartesian, Cli, CommonAddresses,
-    GeoAddresses, GrantsPassAddresses, GrantsPassSpatialAddresses, IntoBin, IntoCsv,
-    JosephineCountyAddresses, JosephineCountyAddresses2024, JosephineCountySpatialAddresses2024,
-    LexisNexis, MatchPartialRecords, MatchRecords, SpatialAddress, SpatialAddresses,
-    SpatialAddressesRaw, trace_init,
+    AddressColumns, Addresses, Boundary, BusinessLicenses, BusinessMatchRecords, BusinessSync,
+    Businesses, Cartesian, Cli, CommonAddresses, Conflation, Corrections, DiffRecords, DiffStatus,
+    DuplicateRecords, FireInspectionMatchRecords, FireInspectionMatches, FireInspections,
+    FuzzyMatchRecords, GeoAddresses, GrantsPassAddresses, GrantsPassSpatialAddresses, IntoBin,
+    IntoCsv, JosephineCountyAddresses, JosephineCountyAddresses2024,
+    JosephineCountySpatialAddresses, JosephineCountySpatialAddresses2024, LexisNexis,
+    LocatableMatchRecords, LocatableRecords, MatchPartialRecords, MatchPolicy, MatchRecords,
+    MatrixRecords, MismatchRecords, NaicsLevel, NaicsSummaries, NearDuplicateRecords,
+    NearDuplicates, PhoneticRecords, PrecedencePolicy, ProgressRecords, ReconcileRecords,
+    SpatialAddress, SpatialAddresses, SpatialAddressesRaw, to_csv, trace_init,
 };
+use std::collections::HashMap;
+use strum::IntoEnumIterator;
 use tracing::{error, info, trace, warn};
 
 fn main() -> anyhow::Result<()> {
@@ -39,6 +46,28 @@ fn main() -> anyhow::Result<()> {
                             info!("Records remaining: {} entries.", filtered.len());
                             filtered.to_csv(cli.output)?;
                         }
+                        "fire" => {
+                            let mut match_records =
+                                FireInspectionMatchRecords::from_csv(&cli.source)?;
+                            info!("Source records read: {} entries.", match_records.len());
+                            match_records.filter(&filter);
+                            info!("Records remaining: {} entries.", match_records.len());
+                            match_records.to_csv(cli.output)?;
+                        }
+                        "reconcile" => {
+                            let records = ReconcileRecords::from_csv(&cli.source)?;
+                            info!("Source records read: {} entries.", records.len());
+                            let mut filtered = records.filter(&filter);
+                            info!("Records remaining: {} entries.", filtered.len());
+                            filtered.to_csv(cli.output)?;
+                        }
+                        "diff" => {
+                            let records = DiffRecords::from_csv(&cli.source)?;
+                            info!("Source records read: {} entries.", records.len());
+                            let mut filtered = records.filter(&filter);
+                            info!("Records remaining: {} entries.", filtered.len());
+                            filtered.to_csv(cli.output)?;
+                        }
                         _ => warn!("Unrecognized source type: {source}"),
                     }
                 }
@@ -261,16 +290,95 @@ fn main() -> anyhow::Result<()> {
 
             info!("Source records read: {} entries.", source_addresses.len());
             info!("Screening addresses for duplicate records.");
-            let mut duplicates = CommonAddresses::from(&source_addresses.filter("duplicate")[..]);
+            let groups = source_addresses.duplicates();
+            info!("Duplicate groups: {:?}", groups.len());
+            let mut duplicates = DuplicateRecords::from(&groups);
             info!("Duplicate records: {:?}", duplicates.len());
             info!("Output file: {:?}", cli.output);
             duplicates.to_csv(cli.output)?;
         }
+        "phonetic" => {
+            info!("Reading source records.");
+            let mut source_addresses = CommonAddresses::default();
+            if let Some(source_type) = &cli.source_type {
+                match source_type.as_str() {
+                    "grants_pass" => {
+                        source_addresses = CommonAddresses::from(
+                            &GrantsPassSpatialAddresses::from_csv(&cli.source)?[..],
+                        )
+                    }
+                    "josephine_county" => {
+                        source_addresses = CommonAddresses::from(
+                            &JosephineCountySpatialAddresses2024::from_csv(&cli.source)?[..],
+                        )
+                    }
+                    "common" => {
+                        source_addresses =
+                            CommonAddresses::from(SpatialAddressesRaw::from_csv(&cli.source)?)
+                    }
+                    _ => error!("Unrecognized file format."),
+                }
+            }
+
+            info!("Source records read: {} entries.", source_addresses.len());
+            info!("Grouping street names by phonetic code.");
+            let groups = source_addresses.phonetic_groups().sound_alike();
+            info!("Street names that sound alike: {:?} groups.", groups.len());
+            let mut records = PhoneticRecords::from(&groups);
+            info!("Output file: {:?}", cli.output);
+            records.to_csv(cli.output)?;
+        }
+        "near_duplicates" => {
+            info!("Reading source records.");
+            let mut source_addresses = SpatialAddresses::default();
+            if let Some(source_type) = &cli.source_type {
+                match source_type.as_str() {
+                    "grants_pass" => {
+                        source_addresses = SpatialAddresses::from(
+                            &GrantsPassSpatialAddresses::from_csv(&cli.source)?[..],
+                        )
+                    }
+                    "josephine_county" => {
+                        source_addresses = SpatialAddresses::from(
+                            &JosephineCountySpatialAddresses2024::from_csv(&cli.source)?[..],
+                        )
+                    }
+                    "common" => {
+                        source_addresses =
+                            SpatialAddresses::from(SpatialAddressesRaw::from_csv(&cli.source)?)
+                    }
+                    _ => error!("Unrecognized file format."),
+                }
+            }
+
+            info!("Source records read: {} entries.", source_addresses.len());
+            info!("Screening addresses for near duplicates.");
+            let pairs = match cli.distance {
+                Some(distance) => {
+                    NearDuplicates::within(&source_addresses, cli.threshold, distance)
+                }
+                None => source_addresses.near_duplicates(cli.threshold),
+            };
+            info!("Near duplicate pairs: {:?}", pairs.len());
+            let mut records = NearDuplicateRecords::from(&pairs);
+            info!("Output file: {:?}", cli.output);
+            records.to_csv(cli.output)?;
+        }
         "business" => {
             info!("Matching business addresses.");
+            let policy = match &cli.policy {
+                Some(policy) => MatchPolicy::load(policy)?,
+                None => MatchPolicy::partial(),
+            };
             info!("Reading source records.");
-            let source_addresses = BusinessLicenses::from_csv(cli.source.clone())?;
+            let (source_addresses, mut rejections) =
+                BusinessLicenses::from_csv_with_rejections(cli.source.clone())?;
             info!("Source records read: {} entries.", source_addresses.len());
+            info!("Source records rejected: {} entries.", rejections.len());
+            if let Some(path) = &cli.rejections {
+                info!("Rejection report: {:?}", path);
+                rejections.to_csv(path)?;
+            }
             let mut source_addresses = source_addresses.deduplicate();
             source_addresses.detype_subaddresses()?;
             info!(
@@ -301,7 +409,7 @@ fn main() -> anyhow::Result<()> {
                 }
                 info!("Target records read: {} entries.", target_addresses.len());
             }
-            if let Some(alternate) = cli.alternate {
+            let mut match_records = if let Some(alternate) = cli.alternate {
                 info!("Comparing multiple targets.");
                 let mut alt_target = GeoAddresses::default();
                 if let Some(target_type) = &cli.alternate_type {
@@ -328,21 +436,283 @@ fn main() -> anyhow::Result<()> {
                     alt_target.len()
                 );
                 info!("Comparing records.");
-                let mut match_records = BusinessMatchRecords::compare_chain(
+                BusinessMatchRecords::compare_chain_with_policy(
                     &source_addresses,
                     &[&target_addresses, &alt_target],
-                );
-                info!("{:?} records categorized.", match_records.len());
-                info!("Output file: {:?}", cli.output);
-                match_records.to_csv(cli.output)?;
+                    &policy,
+                )
             } else {
                 info!("Comparing records.");
-                let mut match_records =
-                    BusinessMatchRecords::compare(&source_addresses, &target_addresses);
-                info!("{:?} records categorized.", match_records.len());
-                info!("Output file: {:?}", cli.output);
-                match_records.to_csv(cli.output)?;
+                BusinessMatchRecords::compare_with_policy(
+                    &source_addresses,
+                    &target_addresses,
+                    &policy,
+                )
+            };
+            info!("{:?} records categorized.", match_records.len());
+            // Only the city address set lies entirely inside city limits, so matching against
+            // any other target cannot place a license inside or outside the city.
+            if let Some(path) = &cli.boundary {
+                info!("Checking license jurisdiction against boundary.");
+                match_records.check_boundary(&Boundary::from_csv(path)?);
+            } else if cli.target_type.as_deref() == Some("grants_pass") {
+                info!("Checking license jurisdiction against target addresses.");
+                match_records.check_addresses(&target_addresses);
+            } else {
+                warn!(
+                    "Skipping jurisdiction check: requires a boundary (--boundary) or a grants_pass target."
+                );
             }
+            info!("Output file: {:?}", cli.output);
+            match_records.to_csv(cli.output)?;
+        }
+        "expiring" => {
+            info!("Reporting expiring business licenses.");
+            let date = match &cli.date {
+                Some(date) => chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")?,
+                None => chrono::Local::now().date_naive(),
+            };
+            let policy = match &cli.policy {
+                Some(policy) => MatchPolicy::load(policy)?,
+                None => MatchPolicy::partial(),
+            };
+            info!("Reading source records.");
+            let (source_addresses, mut rejections) =
+                BusinessLicenses::from_csv_with_rejections(cli.source.clone())?;
+            info!("Source records read: {} entries.", source_addresses.len());
+            info!("Source records rejected: {} entries.", rejections.len());
+            if let Some(path) = &cli.rejections {
+                info!("Rejection report: {:?}", path);
+                rejections.to_csv(path)?;
+            }
+            let mut source_addresses = source_addresses
+                .deduplicate()
+                .filter_expiring(date, cli.days);
+            source_addresses.detype_subaddresses()?;
+            info!(
+                "Licenses expiring within {} days of {}: {} entries.",
+                cli.days,
+                date,
+                source_addresses.len()
+            );
+            info!("Reading comparison records.");
+            let mut target_addresses = GeoAddresses::default();
+            if let Some(target) = &cli.target {
+                if let Some(target_type) = &cli.target_type {
+                    match target_type.as_str() {
+                        "grants_pass" => {
+                            target_addresses = GeoAddresses::from(
+                                &GrantsPassSpatialAddresses::from_csv(target)?[..],
+                            )
+                        }
+                        "josephine_county" => {
+                            target_addresses = GeoAddresses::from(
+                                &JosephineCountySpatialAddresses2024::from_csv(target)?[..],
+                            )
+                        }
+                        "common" => {
+                            target_addresses =
+                                GeoAddresses::from(SpatialAddressesRaw::from_csv(target)?)
+                        }
+                        _ => error!("Unrecognized file format."),
+                    }
+                }
+                info!("Target records read: {} entries.", target_addresses.len());
+            }
+            info!("Comparing records.");
+            let mut match_records = BusinessMatchRecords::compare_chain_with_policy(
+                &source_addresses,
+                &[&target_addresses],
+                &policy,
+            );
+            if let Some(filter) = &cli.filter {
+                match_records = match_records.filter(filter);
+                info!("Records remaining: {} entries.", match_records.len());
+            }
+            info!("Output file: {:?}", cli.output);
+            match_records.to_csv(cli.output)?;
+        }
+        "sync" => {
+            info!("Synchronizing business layer with licenses.");
+            let policy = match &cli.policy {
+                Some(policy) => MatchPolicy::load(policy)?,
+                None => MatchPolicy::partial(),
+            };
+            info!("Reading source records.");
+            let (licenses, mut rejections) =
+                BusinessLicenses::from_csv_with_rejections(cli.source.clone())?;
+            info!("Source records read: {} entries.", licenses.len());
+            info!("Source records rejected: {} entries.", rejections.len());
+            if let Some(path) = &cli.rejections {
+                info!("Rejection report: {:?}", path);
+                rejections.to_csv(path)?;
+            }
+            let mut licenses = licenses.deduplicate();
+            licenses.detype_subaddresses()?;
+            info!("Records deduplicated: {} remaining.", licenses.len());
+            let mut layer = Businesses::new(Vec::new());
+            if let Some(path) = &cli.layer {
+                layer = Businesses::from_raw_csv(path)?;
+                info!("Business layer read: {} entries.", layer.len());
+            } else {
+                warn!("Business layer (--layer) not set, treating all licenses as new.");
+            }
+            info!("Reading comparison records.");
+            let mut target_addresses = GeoAddresses::default();
+            if let Some(target) = &cli.target {
+                if let Some(target_type) = &cli.target_type {
+                    match target_type.as_str() {
+                        "grants_pass" => {
+                            target_addresses = GeoAddresses::from(
+                                &GrantsPassSpatialAddresses::from_csv(target)?[..],
+                            )
+                        }
+                        "josephine_county" => {
+                            target_addresses = GeoAddresses::from(
+                                &JosephineCountySpatialAddresses2024::from_csv(target)?[..],
+                            )
+                        }
+                        "common" => {
+                            target_addresses =
+                                GeoAddresses::from(SpatialAddressesRaw::from_csv(target)?)
+                        }
+                        _ => info!("Unrecognized file format."),
+                    }
+                }
+                info!("Target records read: {} entries.", target_addresses.len());
+            }
+            let BusinessSync { mut records, layer } =
+                BusinessSync::compare(&layer, &licenses, &target_addresses, &policy);
+            if let Some(path) = &cli.updated {
+                info!("Updated business layer: {:?}", path);
+                layer.to_raw_csv(path)?;
+            }
+            if let Some(filter) = &cli.filter {
+                records = records.filter(filter);
+                info!("Records remaining: {} entries.", records.len());
+            }
+            info!("Output file: {:?}", cli.output);
+            records.to_csv(cli.output)?;
+        }
+        "naics" => {
+            info!("Summarizing business licenses by NAICS code.");
+            let level = cli.level.parse::<NaicsLevel>()?;
+            let match_records = BusinessMatchRecords::from_csv(&cli.source)?;
+            info!("Source records read: {} entries.", match_records.len());
+            let mut districts = HashMap::new();
+            if let Some(target) = &cli.target {
+                let businesses = Businesses::from_raw_csv(target)?;
+                info!("Business layer read: {} entries.", businesses.len());
+                districts = businesses.districts();
+            }
+            let mut summaries = NaicsSummaries::from_matches(&match_records, level, &districts);
+            info!("Output file: {:?}", cli.output);
+            summaries.to_csv(cli.output)?;
+        }
+        "fire" => {
+            info!("Matching fire inspection addresses.");
+            let policy = match &cli.policy {
+                Some(policy) => MatchPolicy::load(policy)?,
+                None => MatchPolicy::partial(),
+            };
+            info!("Reading source records.");
+            let (inspections, mut rejections) =
+                FireInspections::from_csv_with_rejections(&cli.source)?;
+            info!("Source records read: {} entries.", inspections.len());
+            info!("Source records rejected: {} entries.", rejections.len());
+            if let Some(path) = &cli.rejections {
+                info!("Rejection report: {:?}", path);
+                rejections.to_csv(path)?;
+            }
+            info!("Reading comparison records.");
+            let mut target_addresses = GeoAddresses::default();
+            if let Some(target) = &cli.target {
+                if let Some(target_type) = &cli.target_type {
+                    match target_type.as_str() {
+                        "grants_pass" => {
+                            target_addresses = GeoAddresses::from(
+                                &GrantsPassSpatialAddresses::from_csv(target)?[..],
+                            )
+                        }
+                        "josephine_county" => {
+                            target_addresses = GeoAddresses::from(
+                                &JosephineCountySpatialAddresses2024::from_csv(target)?[..],
+                            )
+                        }
+                        "common" => {
+                            target_addresses =
+                                GeoAddresses::from(SpatialAddressesRaw::from_csv(target)?)
+                        }
+                        _ => error!("Unrecognized file format."),
+                    }
+                }
+                info!("Target records read: {} entries.", target_addresses.len());
+            }
+            info!("Comparing records.");
+            let matches = FireInspectionMatches::compare_with_policy(
+                &inspections,
+                &target_addresses,
+                &policy,
+            );
+            let mut match_records = FireInspectionMatchRecords::from(&matches);
+            info!("{:?} records categorized.", match_records.len());
+            if let Some(filter) = &cli.filter {
+                match_records.filter(filter);
+                info!("Records remaining: {} entries.", match_records.len());
+            }
+            info!("Output file: {:?}", cli.output);
+            match_records.to_csv(cli.output)?;
+        }
+        "locate" => {
+            info!("Locating source records.");
+            let policy = match &cli.policy {
+                Some(policy) => MatchPolicy::load(policy)?,
+                None => MatchPolicy::partial(),
+            };
+            let columns = AddressColumns::from_spec(&cli.address_column)?;
+            info!("Reading source records.");
+            let (records, mut rejections) =
+                LocatableRecords::from_csv_with_rejections(&cli.source, &columns, &cli.attributes)?;
+            info!("Source records read: {} entries.", records.len());
+            info!("Source records rejected: {} entries.", rejections.len());
+            if let Some(path) = &cli.rejections {
+                info!("Rejection report: {:?}", path);
+                rejections.to_csv(path)?;
+            }
+            info!("Reading comparison records.");
+            let mut target_addresses = GeoAddresses::default();
+            if let Some(target) = &cli.target {
+                if let Some(target_type) = &cli.target_type {
+                    match target_type.as_str() {
+                        "grants_pass" => {
+                            target_addresses = GeoAddresses::from(
+                                &GrantsPassSpatialAddresses::from_csv(target)?[..],
+                            )
+                        }
+                        "josephine_county" => {
+                            target_addresses = GeoAddresses::from(
+                                &JosephineCountySpatialAddresses2024::from_csv(target)?[..],
+                            )
+                        }
+                        "common" => {
+                            target_addresses =
+                                GeoAddresses::from(SpatialAddressesRaw::from_csv(target)?)
+                        }
+                        _ => error!("Unrecognized file format."),
+                    }
+                }
+                info!("Target records read: {} entries.", target_addresses.len());
+            }
+            info!("Comparing records.");
+            let mut match_records =
+                LocatableMatchRecords::compare_with_policy(&records, &target_addresses, &policy);
+            info!("{:?} records categorized.", match_records.len());
+            if let Some(filter) = &cli.filter {
+                match_records.filter(filter);
+                info!("Records remaining: {} entries.", match_records.len());
+            }
+            info!("Output file: {:?}", cli.output);
+            match_records.to_csv(cli.output)?;
         }
         "compare" => {
             info!("Reading source records.");
@@ -396,13 +766,462 @@ fn main() -> anyhow::Result<()> {
             // source = GeoAddresses::from(&source.filter("active")[..]);
             info!("Source records post: {}", source.len());
 
-            let mut match_records = MatchRecords::compare(&source, &target);
+            let policy = match &cli.policy {
+                Some(policy) => MatchPolicy::load(policy)?,
+                None => MatchPolicy::default(),
+            };
+            if let Some(distance) = cli.distance {
+                info!("Flagging matches farther apart than {distance} meters.");
+            }
+            let mut match_records = if cli.fuzzy {
+                info!("Reporting fuzzy candidates for missing addresses.");
+                MatchRecords::compare_fuzzy(&source, &target, &policy, cli.threshold, cli.distance)
+            } else {
+                match cli.distance {
+                    Some(distance) => {
+                        MatchRecords::compare_geographic(&source, &target, &policy, distance)
+                    }
+                    None => MatchRecords::compare_with_policy(&source, &target, &policy),
+                }
+            };
             info!("{:?} records categorized.", match_records.len());
+            if let Some(path) = &cli.mismatches {
+                let mut mismatches = MismatchRecords::from(&match_records);
+                info!("Mismatch file: {:?}", path);
+                mismatches.to_csv(path)?;
+            }
             info!("Output file: {:?}", cli.output);
             match_records.to_csv(cli.output)?;
         }
+        "reconcile" => {
+            info!("Reading source records.");
+            let mut source = GeoAddresses::default();
+            if let Some(source_type) = &cli.source_type {
+                match source_type.as_str() {
+                    "grants_pass" => {
+                        source = GeoAddresses::from(
+                            &GrantsPassSpatialAddresses::from_csv(&cli.source)?[..],
+                        )
+                    }
+                    "josephine_county" => {
+                        source = GeoAddresses::from(
+                            &JosephineCountySpatialAddresses2024::from_csv(&cli.source)?[..],
+                        );
+                        source.standardize();
+                    }
+                    "common" => {
+                        source = GeoAddresses::from(SpatialAddressesRaw::from_csv(&cli.source)?)
+                    }
+                    _ => error!("Unrecognized file format."),
+                }
+            }
+            info!("Source records read: {} entries.", source.len());
+            info!("Reading target records.");
+            let mut target = GeoAddresses::default();
+            if let Some(target_type) = &cli.target_type
+                && let Some(target_path) = &cli.target
+            {
+                match target_type.as_str() {
+                    "grants_pass" => {
+                        target = GeoAddresses::from(
+                            &GrantsPassSpatialAddresses::from_csv(target_path)?[..],
+                        )
+                    }
+                    "josephine_county" => {
+                        target = GeoAddresses::from(
+                            &JosephineCountySpatialAddresses2024::from_csv(target_path)?[..],
+                        );
+                        target.standardize();
+                    }
+                    "common" => {
+                        target = GeoAddresses::from(SpatialAddressesRaw::from_csv(target_path)?)
+                    }
+                    _ => error!("Unrecognized file format."),
+                }
+            }
+            info!("Target records read: {} entries.", target.len());
+            info!("Remove retired addresses.");
+            source.filter_field("active", "");
+            target.filter_field("active", "");
+
+            let policy = match &cli.policy {
+                Some(policy) => MatchPolicy::load(policy)?,
+                None => MatchPolicy::default(),
+            };
+            info!("Reconciling records.");
+            let mut records = ReconcileRecords::compare(&source, &target, &policy);
+            info!("{:?} records reconciled.", records.len());
+            info!("Output file: {:?}", cli.output);
+            records.to_csv(&cli.output)?;
+            let mut summary = records.summary();
+            for count in summary.group("status") {
+                info!("{}: {} records.", count.value, count.total);
+            }
+            if let Some(path) = &cli.summary {
+                info!("Summary file: {:?}", path);
+                summary.to_csv(path)?;
+            }
+        }
+        "correct" => {
+            info!("Reading source records.");
+            let mut source = GeoAddresses::default();
+            if let Some(source_type) = &cli.source_type {
+                match source_type.as_str() {
+                    "grants_pass" => {
+                        source = GeoAddresses::from(
+                            &GrantsPassSpatialAddresses::from_csv(&cli.source)?[..],
+                        )
+                    }
+                    "josephine_county" => {
+                        source = GeoAddresses::from(
+                            &JosephineCountySpatialAddresses2024::from_csv(&cli.source)?[..],
+                        );
+                        source.standardize();
+                    }
+                    "common" => {
+                        source = GeoAddresses::from(SpatialAddressesRaw::from_csv(&cli.source)?)
+                    }
+                    _ => error!("Unrecognized file format."),
+                }
+            }
+            info!("Source records read: {} entries.", source.len());
+            source.filter_field("active", "");
+            let policy = match &cli.policy {
+                Some(policy) => MatchPolicy::load(policy)?,
+                None => MatchPolicy::default(),
+            };
+            if let Some(target_type) = &cli.target_type
+                && let Some(target_path) = &cli.target
+            {
+                info!("Reading target records.");
+                match target_type.as_str() {
+                    "grants_pass" => {
+                        let target = GrantsPassSpatialAddresses::from_csv(target_path)?;
+                        info!("Target records read: {} entries.", target.len());
+                        let corrections = Corrections::compare(&source, &target, &policy);
+                        info!("Output file: {:?}", cli.output);
+                        corrections.to_csv(&cli.output)?;
+                        if let Some(path) = &cli.changes {
+                            info!("Change file: {:?}", path);
+                            corrections.changes().to_csv(path)?;
+                        }
+                    }
+                    "josephine_county" => {
+                        let target = JosephineCountySpatialAddresses2024::from_csv(target_path)?;
+                        info!("Target records read: {} entries.", target.len());
+                        let corrections = Corrections::compare(&source, &target, &policy);
+                        info!("Output file: {:?}", cli.output);
+                        corrections.to_csv(&cli.output)?;
+                        if let Some(path) = &cli.changes {
+                            info!("Change file: {:?}", path);
+                            corrections.changes().to_csv(path)?;
+                        }
+                    }
+                    "josephine_county_legacy" => {
+                        let target = JosephineCountySpatialAddresses::from_csv(target_path)?;
+                        info!("Target records read: {} entries.", target.len());
+                        let corrections = Corrections::compare(&source, &target, &policy);
+                        info!("Output file: {:?}", cli.output);
+                        corrections.to_csv(&cli.output)?;
+                        if let Some(path) = &cli.changes {
+                            info!("Change file: {:?}", path);
+                            corrections.changes().to_csv(path)?;
+                        }
+                    }
+                    _ => error!("Unrecognized file format."),
+                }
+            } else {
+                warn!("Target type (-z) and target path (-t) must be set.");
+            }
+        }
+        "progress" => {
+            let mut records = ProgressRecords::default();
+            if let Some(source_type) = &cli.source_type
+                && let Some(target) = &cli.target
+            {
+                info!("Comparing {:?} to {:?}.", cli.source, target);
+                match source_type.as_str() {
+                    "full" => {
+                        let previous = MatchRecords::from_csv(&cli.source)?;
+                        let current = MatchRecords::from_csv(target)?;
+                        records = ProgressRecords::compare_matches(&previous, &current);
+                    }
+                    "business" => {
+                        let previous = BusinessMatchRecords::from_csv(&cli.source)?;
+                        let current = BusinessMatchRecords::from_csv(target)?;
+                        records = ProgressRecords::compare_businesses(&previous, &current);
+                    }
+                    _ => warn!("Unrecognized source type: {source_type}"),
+                }
+            } else {
+                warn!("Source type (-k) and target path (-t) must be set.");
+            }
+            let summary = records.summary();
+            info!(
+                "Open records: {} -> {}.",
+                summary.previous_open, summary.current_open
+            );
+            info!(
+                "Resolved: {}, removed: {}, newly broken: {}, still open: {}, changed category: {}.",
+                summary.resolved,
+                summary.removed,
+                summary.newly_broken,
+                summary.still_open,
+                summary.changed_category
+            );
+            if let Some(filter) = &cli.filter {
+                records = records.filter(filter);
+                info!("Records remaining: {} entries.", records.len());
+            }
+            info!("Output file: {:?}", cli.output);
+            records.to_csv(&cli.output)?;
+            if let Some(path) = &cli.summary {
+                info!("Summary file: {:?}", path);
+                to_csv(&mut [summary], path.clone())?;
+            }
+        }
+        "matrix" => {
+            let collections = read_collections(&cli.collections)?;
+            let sources = collections
+                .iter()
+                .map(|(name, addresses)| (name.as_str(), &addresses[..]))
+                .collect::<Vec<_>>();
+            let policy = match &cli.policy {
+                Some(policy) => MatchPolicy::load(policy)?,
+                None => MatchPolicy::default(),
+            };
+            info!("Comparing collections.");
+            let mut records = MatrixRecords::compare(&sources, &policy);
+            info!("{:?} canonical addresses.", records.len());
+            if let Some(filter) = &cli.filter {
+                records = records.filter(filter);
+                info!("Records remaining: {} entries.", records.len());
+            }
+            info!("Output file: {:?}", cli.output);
+            records.to_csv(cli.output)?;
+        }
+        "diff" => {
+            let mut records = DiffRecords::default();
+            if let Some(source_type) = &cli.source_type
+                && let Some(target) = &cli.target
+            {
+                if cli.distance.is_none() {
+                    info!("No distance threshold set, moved address points are not reported.");
+                }
+                info!("Comparing {:?} to {:?}.", cli.source, target);
+                match source_type.as_str() {
+                    "grants_pass" => {
+                        let old = GrantsPassSpatialAddresses::from_csv(&cli.source)?;
+                        let new = GrantsPassSpatialAddresses::from_csv(target)?;
+                        records = match cli.distance {
+                            Some(distance) => DiffRecords::compare_geographic(&old, &new, distance),
+                            None => DiffRecords::compare(&old, &new),
+                        };
+                    }
+                    "grants_pass_table" => {
+                        let old = GrantsPassAddresses::from_csv(&cli.source)?;
+                        let new = GrantsPassAddresses::from_csv(target)?;
+                        records = DiffRecords::compare(&old, &new);
+                    }
+                    "josephine_county" => {
+                        let old = JosephineCountySpatialAddresses2024::from_csv(&cli.source)?;
+                        let new = JosephineCountySpatialAddresses2024::from_csv(target)?;
+                        records = match cli.distance {
+                            Some(distance) => DiffRecords::compare_geographic(&old, &new, distance),
+                            None => DiffRecords::compare(&old, &new),
+                        };
+                    }
+                    "josephine_county_table" => {
+                        let old = JosephineCountyAddresses2024::from_csv(&cli.source)?;
+                        let new = JosephineCountyAddresses2024::from_csv(target)?;
+                        records = DiffRecords::compare(&old, &new);
+                    }
+                    "josephine_county_legacy" => {
+                        let old = JosephineCountySpatialAddresses::from_csv(&cli.source)?;
+                        let new = JosephineCountySpatialAddresses::from_csv(target)?;
+                        records = match cli.distance {
+                            Some(distance) => DiffRecords::compare_geographic(&old, &new, distance),
+                            None => DiffRecords::compare(&old, &new),
+                        };
+                    }
+                    "josephine_county_legacy_table" => {
+                        let old = JosephineCountyAddresses::from_csv(&cli.source)?;
+                        let new = JosephineCountyAddresses::from_csv(target)?;
+                        records = DiffRecords::compare(&old, &new);
+                    }
+                    "common" => {
+                        let old = GeoAddresses::from(SpatialAddressesRaw::from_csv(&cli.source)?);
+                        let new = GeoAddresses::from(SpatialAddressesRaw::from_csv(target)?);
+                        records = match cli.distance {
+                            Some(distance) => DiffRecords::compare_geographic(&old, &new, distance),
+                            None => DiffRecords::compare(&old, &new),
+                        };
+                    }
+                    "business" => {
+                        let old = Businesses::from_raw_csv(&cli.source)?;
+                        let new = Businesses::from_raw_csv(target)?;
+                        records = match cli.distance {
+                            Some(distance) => {
+                                DiffRecords::compare_businesses_cartesian(&old, &new, distance)
+                            }
+                            None => DiffRecords::compare_businesses(&old, &new),
+                        };
+                    }
+                    "fire" => {
+                        let old = FireInspections::from_csv(&cli.source)?;
+                        let new = FireInspections::from_csv(target)?;
+                        records = DiffRecords::compare_inspections(&old, &new);
+                    }
+                    _ => error!("Unrecognized file format."),
+                }
+            } else {
+                warn!("Source type (-k) and target path (-t) must be set.");
+            }
+            for status in DiffStatus::iter() {
+                info!("{}: {} records.", status, records.count(status));
+            }
+            if let Some(filter) = &cli.filter {
+                records = records.filter(filter);
+                info!("Records remaining: {} entries.", records.len());
+            }
+            info!("Output file: {:?}", cli.output);
+            records.to_csv(cli.output)?;
+        }
+        "conflate" => {
+            let collections = read_collections(&cli.collections)?;
+            let sources = collections
+                .iter()
+                .map(|(name, addresses)| (name.as_str(), &addresses[..]))
+                .collect::<Vec<_>>();
+            let mut precedence = match &cli.precedence {
+                Some(path) => PrecedencePolicy::from_csv(path)?,
+                None => PrecedencePolicy::default(),
+            };
+            let names = sources.iter().map(|(name, _)| *name).collect::<Vec<&str>>();
+            if let Err(message) = precedence.validate(&names) {
+                anyhow::bail!("Invalid precedence policy: {message}");
+            }
+            if let Some(path) = &cli.boundary {
+                precedence = precedence.with_boundary(Boundary::from_csv(path)?);
+            }
+            let policy = match &cli.policy {
+                Some(policy) => MatchPolicy::load(policy)?,
+                None => MatchPolicy::default(),
+            };
+            info!("Conflating collections.");
+            let mut conflation = Conflation::merge(&sources, &precedence, &policy);
+            info!("{:?} addresses conflated.", conflation.addresses.len());
+            let conflicts = conflation.provenance.iter().filter(|r| r.conflict).count();
+            info!("{} fields in conflict.", conflicts);
+            conflation.addresses.save(&cli.output)?;
+            info!("Addresses saved to {:?}", &cli.output);
+            if let Some(path) = &cli.provenance {
+                info!("Provenance file: {:?}", path);
+                conflation.provenance.to_csv(path)?;
+            }
+        }
+        "fuzzy" => {
+            info!("Reading source records.");
+            let mut source = GeoAddresses::default();
+            if let Some(source_type) = &cli.source_type {
+                match source_type.as_str() {
+                    "grants_pass" => {
+                        source = GeoAddresses::from(
+                            &GrantsPassSpatialAddresses::from_csv(&cli.source)?[..],
+                        )
+                    }
+                    "josephine_county" => {
+                        source = GeoAddresses::from(
+                            &JosephineCountySpatialAddresses2024::from_csv(&cli.source)?[..],
+                        )
+                    }
+                    "common" => {
+                        source = GeoAddresses::from(SpatialAddressesRaw::from_csv(&cli.source)?)
+                    }
+                    _ => error!("Unrecognized file format."),
+                }
+            }
+            info!("Reading target records.");
+            let mut target = GeoAddresses::default();
+            if let Some(target_type) = &cli.target_type
+                && let Some(target_path) = &cli.target
+            {
+                match target_type.as_str() {
+                    "grants_pass" => {
+                        target = GeoAddresses::from(
+                            &GrantsPassSpatialAddresses::from_csv(target_path)?[..],
+                        )
+                    }
+                    "josephine_county" => {
+                        target = GeoAddresses::from(
+                            &JosephineCountySpatialAddresses2024::from_csv(target_path)?[..],
+                        );
+                        target.standardize();
+                    }
+                    "common" => {
+                        target = GeoAddresses::from(SpatialAddressesRaw::from_csv(target_path)?)
+                    }
+                    _ => error!("Unrecognized file format."),
+                }
+            }
+            info!("Remove retired addresses from source.");
+            info!("Source records prior: {}", source.len());
+            source.filter_field("active", "");
+            info!("Source records post: {}", source.len());
+
+            info!("Searching fuzzy candidates for missing addresses.");
+            let policy = match &cli.policy {
+                Some(policy) => MatchPolicy::load(policy)?,
+                None => MatchPolicy::default(),
+            };
+            let mut fuzzy_records = FuzzyMatchRecords::compare(
+                &source,
+                &target,
+                &policy,
+                cli.candidates,
+                cli.threshold,
+            );
+            info!("{:?} candidate records reported.", fuzzy_records.len());
+            info!("Output file: {:?}", cli.output);
+            fuzzy_records.to_csv(cli.output)?;
+        }
         _ => {}
     }
 
     Ok(())
 }
+
+/// Reads the address collections named by the `--collection` arguments in `collections`, each of
+/// the form "name,type,path".  Returns an error if there are no collections, if an argument is
+/// malformed, or if the type of a collection is not recognized.
+fn read_collections(collections: &[String]) -> anyhow::Result<Vec<(String, SpatialAddresses)>> {
+    if collections.is_empty() {
+        anyhow::bail!("No collections to read, use --collection 'name,type,path'.");
+    }
+    let mut results = Vec::new();
+    for collection in collections {
+        let parts = collection.splitn(3, ',').collect::<Vec<&str>>();
+        if parts.len() != 3 {
+            anyhow::bail!("Collection must take the form 'name,type,path': {collection}");
+        }
+        info!("Reading {} records.", parts[0]);
+        let mut addresses = match parts[1] {
+            "grants_pass" => {
+                SpatialAddresses::from(&GrantsPassSpatialAddresses::from_csv(parts[2])?[..])
+            }
+            "josephine_county" => {
+                let mut addresses = SpatialAddresses::from(
+                    &JosephineCountySpatialAddresses2024::from_csv(parts[2])?[..],
+                );
+                addresses.standardize();
+                addresses
+            }
+            "common" => SpatialAddresses::from(SpatialAddressesRaw::from_csv(parts[2])?),
+            other => anyhow::bail!("Unrecognized collection type: {other}"),
+        };
+        addresses.filter_field("active", "");
+        info!("{} records read: {} entries.", parts[0], addresses.len());
+        results.push((parts[0].to_string(), addresses));
+    }
+    Ok(results)
+}
@@ -0,0 +1,711 @@
+//! The `naics` module holds the North American Industry Classification System (NAICS) hierarchy,
+//! used to enrich business licenses carrying only an industry code with their sector and
+//! subsector, and to summarize licenses by sector, subsector and business district.
+use crate::{AddressErrorKind, BusinessMatchRecord, IntoCsv, Io, MatchStatus, from_csv, to_csv};
+use derive_more::{Deref, DerefMut};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use tracing::info;
+
+/// The `NAICS_SECTORS` constant pairs each two-digit NAICS sector code with its description.
+/// Manufacturing, retail trade, and transportation and warehousing span several codes, see
+/// [`Naics::sector`].
+const NAICS_SECTORS: [(i32, &str); 25] = [
+    (11, "Agriculture, Forestry, Fishing and Hunting"),
+    (21, "Mining, Quarrying and Oil and Gas Extraction"),
+    (22, "Utilities"),
+    (23, "Construction"),
+    (31, "Manufacturing"),
+    (32, "Manufacturing"),
+    (33, "Manufacturing"),
+    (42, "Wholesale Trade"),
+    (44, "Retail Trade"),
+    (45, "Retail Trade"),
+    (48, "Transportation and Warehousing"),
+    (49, "Transportation and Warehousing"),
+    (51, "Information"),
+    (52, "Finance and Insurance"),
+    (53, "Real Estate Rental and Leasing"),
+    (54, "Professional, Scientific and Technical Services"),
+    (55, "Management of Companies and Enterprises"),
+    (56, "Administrative, Support and Waste Services"),
+    (61, "Educational Services"),
+    (62, "Health Care and Social Assistance"),
+    (71, "Arts, Entertainment and Recreation"),
+    (72, "Accommodation and Food Services"),
+    (81, "Other Services (except Public Administration)"),
+    (92, "Public Administration"),
+    (99, "Unclassified"),
+];
+
+/// The `NAICS_SUBSECTORS` constant pairs each four-digit NAICS industry group code, which the
+/// city business layer records as the subsector, with its description.  Holds the 2017 codes,
+/// along with the 2012 codes for general merchandise stores and telecommunications carriers that
+/// remain in use in the license export.
+const NAICS_SUBSECTORS: [(i32, &str); 316] = [
+    (1111, "Oilseed and Grain Farming"),
+    (1112, "Vegetable and Melon Farming"),
+    (1113, "Fruit and Tree Nut Farming"),
+    (1114, "Greenhouse, Nursery and Floriculture Production"),
+    (1119, "Other Crop Farming"),
+    (1121, "Cattle Ranching and Farming"),
+    (1122, "Hog and Pig Farming"),
+    (1123, "Poultry and Egg Production"),
+    (1124, "Sheep and Goat Farming"),
+    (1125, "Aquaculture"),
+    (1129, "Other Animal Production"),
+    (1131, "Timber Tract Operations"),
+    (1132, "Forest Nurseries and Gathering of Forest Products"),
+    (1133, "Logging"),
+    (1141, "Fishing"),
+    (1142, "Hunting and Trapping"),
+    (1151, "Support Activities for Crop Production"),
+    (1152, "Support Activities for Animal Production"),
+    (1153, "Support Activities for Forestry"),
+    (2111, "Oil and Gas Extraction"),
+    (2121, "Coal Mining"),
+    (2122, "Metal Ore Mining"),
+    (2123, "Nonmetallic Mineral Mining and Quarrying"),
+    (2131, "Support Activities for Mining"),
+    (
+        2211,
+        "Electric Power Generation, Transmission and Distribution",
+    ),
+    (2212, "Natural Gas Distribution"),
+    (2213, "Water, Sewage and Other Systems"),
+    (2361, "Residential Building Construction"),
+    (2362, "Nonresidential Building Construction"),
+    (2371, "Utility System Construction"),
+    (2372, "Land Subdivision"),
+    (2373, "Highway, Street and Bridge Construction"),
+    (2379, "Other Heavy and Civil Engineering Construction"),
+    (
+        2381,
+        "Foundation, Structure and Building Exterior Contractors",
+    ),
+    (2382, "Building Equipment Contractors"),
+    (2383, "Building Finishing Contractors"),
+    (2389, "Other Specialty Trade Contractors"),
+    (3111, "Animal Food Manufacturing"),
+    (3112, "Grain and Oilseed Milling"),
+    (3113, "Sugar and Confectionery Product Manufacturing"),
+    (
+        3114,
+        "Fruit and Vegetable Preserving and Specialty Food Manufacturing",
+    ),
+    (3115, "Dairy Product Manufacturing"),
+    (3116, "Animal Slaughtering and Processing"),
+    (3117, "Seafood Product Preparation and Packaging"),
+    (3118, "Bakeries and Tortilla Manufacturing"),
+    (3119, "Other Food Manufacturing"),
+    (3121, "Beverage Manufacturing"),
+    (3122, "Tobacco Manufacturing"),
+    (3131, "Fiber, Yarn and Thread Mills"),
+    (3132, "Fabric Mills"),
+    (
+        3133,
+        "Textile and Fabric Finishing and Fabric Coating Mills",
+    ),
+    (3141, "Textile Furnishings Mills"),
+    (3149, "Other Textile Product Mills"),
+    (3151, "Apparel Knitting Mills"),
+    (3152, "Cut and Sew Apparel Manufacturing"),
+    (3159, "Apparel Accessories and Other Apparel Manufacturing"),
+    (3161, "Leather and Hide Tanning and Finishing"),
+    (3162, "Footwear Manufacturing"),
+    (3169, "Other Leather and Allied Product Manufacturing"),
+    (3211, "Sawmills and Wood Preservation"),
+    (
+        3212,
+        "Veneer, Plywood and Engineered Wood Product Manufacturing",
+    ),
+    (3219, "Other Wood Product Manufacturing"),
+    (3221, "Pulp, Paper and Paperboard Mills"),
+    (3222, "Converted Paper Product Manufacturing"),
+    (3231, "Printing and Related Support Activities"),
+    (3241, "Petroleum and Coal Products Manufacturing"),
+    (3251, "Basic Chemical Manufacturing"),
+    (
+        3252,
+        "Resin, Synthetic Rubber, and Artificial and Synthetic Fibers and Filaments Manufacturing",
+    ),
+    (
+        3253,
+        "Pesticide, Fertilizer and Other Agricultural Chemical Manufacturing",
+    ),
+    (3254, "Pharmaceutical and Medicine Manufacturing"),
+    (3255, "Paint, Coating and Adhesive Manufacturing"),
+    (
+        3256,
+        "Soap, Cleaning Compound and Toilet Preparation Manufacturing",
+    ),
+    (3259, "Other Chemical Product and Preparation Manufacturing"),
+    (3261, "Plastics Product Manufacturing"),
+    (3262, "Rubber Product Manufacturing"),
+    (3271, "Clay Product and Refractory Manufacturing"),
+    (3272, "Glass and Glass Product Manufacturing"),
+    (3273, "Cement and Concrete Product Manufacturing"),
+    (3274, "Lime and Gypsum Product Manufacturing"),
+    (3279, "Other Nonmetallic Mineral Product Manufacturing"),
+    (3311, "Iron and Steel Mills and Ferroalloy Manufacturing"),
+    (3312, "Steel Product Manufacturing from Purchased Steel"),
+    (3313, "Alumina and Aluminum Production and Processing"),
+    (
+        3314,
+        "Nonferrous Metal (except Aluminum) Production and Processing",
+    ),
+    (3315, "Foundries"),
+    (3321, "Forging and Stamping"),
+    (3322, "Cutlery and Handtool Manufacturing"),
+    (3323, "Architectural and Structural Metals Manufacturing"),
+    (3324, "Boiler, Tank and Shipping Container Manufacturing"),
+    (3325, "Hardware Manufacturing"),
+    (3326, "Spring and Wire Product Manufacturing"),
+    (
+        3327,
+        "Machine Shops; Turned Product; and Screw, Nut and Bolt Manufacturing",
+    ),
+    (
+        3328,
+        "Coating, Engraving, Heat Treating and Allied Activities",
+    ),
+    (3329, "Other Fabricated Metal Product Manufacturing"),
+    (
+        3331,
+        "Agriculture, Construction and Mining Machinery Manufacturing",
+    ),
+    (3332, "Industrial Machinery Manufacturing"),
+    (
+        3333,
+        "Commercial and Service Industry Machinery Manufacturing",
+    ),
+    (
+        3334,
+        "Ventilation, Heating, Air-Conditioning and Commercial Refrigeration Equipment Manufacturing",
+    ),
+    (3335, "Metalworking Machinery Manufacturing"),
+    (
+        3336,
+        "Engine, Turbine and Power Transmission Equipment Manufacturing",
+    ),
+    (3339, "Other General Purpose Machinery Manufacturing"),
+    (3341, "Computer and Peripheral Equipment Manufacturing"),
+    (3342, "Communications Equipment Manufacturing"),
+    (3343, "Audio and Video Equipment Manufacturing"),
+    (
+        3344,
+        "Semiconductor and Other Electronic Component Manufacturing",
+    ),
+    (
+        3345,
+        "Navigational, Measuring, Electromedical and Control Instruments Manufacturing",
+    ),
+    (
+        3346,
+        "Manufacturing and Reproducing Magnetic and Optical Media",
+    ),
+    (3351, "Electric Lighting Equipment Manufacturing"),
+    (3352, "Household Appliance Manufacturing"),
+    (3353, "Electrical Equipment Manufacturing"),
+    (
+        3359,
+        "Other Electrical Equipment and Component Manufacturing",
+    ),
+    (3361, "Motor Vehicle Manufacturing"),
+    (3362, "Motor Vehicle Body and Trailer Manufacturing"),
+    (3363, "Motor Vehicle Parts Manufacturing"),
+    (3364, "Aerospace Product and Parts Manufacturing"),
+    (3365, "Railroad Rolling Stock Manufacturing"),
+    (3366, "Ship and Boat Building"),
+    (3369, "Other Transportation Equipment Manufacturing"),
+    (
+        3371,
+        "Household and Institutional Furniture and Kitchen Cabinet Manufacturing",
+    ),
+    (3372, "Office Furniture (including Fixtures) Manufacturing"),
+    (3379, "Other Furniture Related Product Manufacturing"),
+    (3391, "Medical Equipment and Supplies Manufacturing"),
+    (3399, "Other Miscellaneous Manufacturing"),
+    (
+        4231,
+        "Motor Vehicle and Motor Vehicle Parts and Supplies Merchant Wholesalers",
+    ),
+    (4232, "Furniture and Home Furnishing Merchant Wholesalers"),
+    (
+        4233,
+        "Lumber and Other Construction Materials Merchant Wholesalers",
+    ),
+    (
+        4234,
+        "Professional and Commercial Equipment and Supplies Merchant Wholesalers",
+    ),
+    (
+        4235,
+        "Metal and Mineral (except Petroleum) Merchant Wholesalers",
+    ),
+    (
+        4236,
+        "Household Appliances and Electrical and Electronic Goods Merchant Wholesalers",
+    ),
+    (
+        4237,
+        "Hardware, and Plumbing and Heating Equipment and Supplies Merchant Wholesalers",
+    ),
+    (
+        4238,
+        "Machinery, Equipment and Supplies Merchant Wholesalers",
+    ),
+    (4239, "Miscellaneous Durable Goods Merchant Wholesalers"),
+    (4241, "Paper and Paper Product Merchant Wholesalers"),
+    (4242, "Drugs and Druggists' Sundries Merchant Wholesalers"),
+    (
+        4243,
+        "Apparel, Piece Goods and Notions Merchant Wholesalers",
+    ),
+    (4244, "Grocery and Related Product Merchant Wholesalers"),
+    (4245, "Farm Product Raw Material Merchant Wholesalers"),
+    (4246, "Chemical and Allied Products Merchant Wholesalers"),
+    (
+        4247,
+        "Petroleum and Petroleum Products Merchant Wholesalers",
+    ),
+    (
+        4248,
+        "Beer, Wine and Distilled Alcoholic Beverage Merchant Wholesalers",
+    ),
+    (4249, "Miscellaneous Nondurable Goods Merchant Wholesalers"),
+    (4251, "Wholesale Electronic Markets and Agents and Brokers"),
+    (4411, "Automobile Dealers"),
+    (4412, "Other Motor Vehicle Dealers"),
+    (4413, "Automotive Parts, Accessories and Tire Stores"),
+    (4421, "Furniture Stores"),
+    (4422, "Home Furnishings Stores"),
+    (4431, "Electronics and Appliance Stores"),
+    (4441, "Building Material and Supplies Dealers"),
+    (4442, "Lawn and Garden Equipment and Supplies Stores"),
+    (4451, "Grocery Stores"),
+    (4452, "Specialty Food Stores"),
+    (4453, "Beer, Wine and Liquor Stores"),
+    (4461, "Health and Personal Care Stores"),
+    (4471, "Gasoline Stations"),
+    (4481, "Clothing Stores"),
+    (4482, "Shoe Stores"),
+    (4483, "Jewelry, Luggage and Leather Goods Stores"),
+    (4511, "Sporting Goods, Hobby and Musical Instrument Stores"),
+    (4512, "Book Stores and News Dealers"),
+    (4521, "Department Stores"),
+    (4522, "Department Stores"),
+    (
+        4523,
+        "General Merchandise Stores, including Warehouse Clubs and Supercenters",
+    ),
+    (4529, "Other General Merchandise Stores"),
+    (4531, "Florists"),
+    (4532, "Office Supplies, Stationery and Gift Stores"),
+    (4533, "Used Merchandise Stores"),
+    (4539, "Other Miscellaneous Store Retailers"),
+    (4541, "Electronic Shopping and Mail-Order Houses"),
+    (4542, "Vending Machine Operators"),
+    (4543, "Direct Selling Establishments"),
+    (4811, "Scheduled Air Transportation"),
+    (4812, "Nonscheduled Air Transportation"),
+    (4821, "Rail Transportation"),
+    (
+        4831,
+        "Deep Sea, Coastal and Great Lakes Water Transportation",
+    ),
+    (4832, "Inland Water Transportation"),
+    (4841, "General Freight Trucking"),
+    (4842, "Specialized Freight Trucking"),
+    (4851, "Urban Transit Systems"),
+    (4852, "Interurban and Rural Bus Transportation"),
+    (4853, "Taxi and Limousine Service"),
+    (4854, "School and Employee Bus Transportation"),
+    (4855, "Charter Bus Industry"),
+    (4859, "Other Transit and Ground Passenger Transportation"),
+    (4861, "Pipeline Transportation of Crude Oil"),
+    (4862, "Pipeline Transportation of Natural Gas"),
+    (4869, "Other Pipeline Transportation"),
+    (4871, "Scenic and Sightseeing Transportation, Land"),
+    (4872, "Scenic and Sightseeing Transportation, Water"),
+    (4879, "Scenic and Sightseeing Transportation, Other"),
+    (4881, "Support Activities for Air Transportation"),
+    (4882, "Support Activities for Rail Transportation"),
+    (4883, "Support Activities for Water Transportation"),
+    (4884, "Support Activities for Road Transportation"),
+    (4885, "Freight Transportation Arrangement"),
+    (4889, "Other Support Activities for Transportation"),
+    (4911, "Postal Service"),
+    (4921, "Couriers and Express Delivery Services"),
+    (4922, "Local Messengers and Local Delivery"),
+    (4931, "Warehousing and Storage"),
+    (5111, "Newspaper, Periodical, Book and Directory Publishers"),
+    (5112, "Software Publishers"),
+    (5121, "Motion Picture and Video Industries"),
+    (5122, "Sound Recording Industries"),
+    (5151, "Radio and Television Broadcasting"),
+    (5152, "Cable and Other Subscription Programming"),
+    (5171, "Wired Telecommunications Carriers"),
+    (
+        5172,
+        "Wireless Telecommunications Carriers (except Satellite)",
+    ),
+    (5173, "Wired and Wireless Telecommunications Carriers"),
+    (5174, "Satellite Telecommunications"),
+    (5179, "Other Telecommunications"),
+    (5182, "Data Processing, Hosting and Related Services"),
+    (5191, "Other Information Services"),
+    (5211, "Monetary Authorities-Central Bank"),
+    (5221, "Depository Credit Intermediation"),
+    (5222, "Nondepository Credit Intermediation"),
+    (5223, "Activities Related to Credit Intermediation"),
+    (
+        5231,
+        "Securities and Commodity Contracts Intermediation and Brokerage",
+    ),
+    (5232, "Securities and Commodity Exchanges"),
+    (5239, "Other Financial Investment Activities"),
+    (5241, "Insurance Carriers"),
+    (
+        5242,
+        "Agencies, Brokerages and Other Insurance Related Activities",
+    ),
+    (5251, "Insurance and Employee Benefit Funds"),
+    (5259, "Other Investment Pools and Funds"),
+    (5311, "Lessors of Real Estate"),
+    (5312, "Offices of Real Estate Agents and Brokers"),
+    (5313, "Activities Related to Real Estate"),
+    (5321, "Automotive Equipment Rental and Leasing"),
+    (5322, "Consumer Goods Rental"),
+    (5323, "General Rental Centers"),
+    (
+        5324,
+        "Commercial and Industrial Machinery and Equipment Rental and Leasing",
+    ),
+    (
+        5331,
+        "Lessors of Nonfinancial Intangible Assets (except Copyrighted Works)",
+    ),
+    (5411, "Legal Services"),
+    (
+        5412,
+        "Accounting, Tax Preparation, Bookkeeping and Payroll Services",
+    ),
+    (5413, "Architectural, Engineering and Related Services"),
+    (5414, "Specialized Design Services"),
+    (5415, "Computer Systems Design and Related Services"),
+    (
+        5416,
+        "Management, Scientific and Technical Consulting Services",
+    ),
+    (5417, "Scientific Research and Development Services"),
+    (5418, "Advertising, Public Relations and Related Services"),
+    (
+        5419,
+        "Other Professional, Scientific and Technical Services",
+    ),
+    (5511, "Management of Companies and Enterprises"),
+    (5611, "Office Administrative Services"),
+    (5612, "Facilities Support Services"),
+    (5613, "Employment Services"),
+    (5614, "Business Support Services"),
+    (5615, "Travel Arrangement and Reservation Services"),
+    (5616, "Investigation and Security Services"),
+    (5617, "Services to Buildings and Dwellings"),
+    (5619, "Other Support Services"),
+    (5621, "Waste Collection"),
+    (5622, "Waste Treatment and Disposal"),
+    (5629, "Remediation and Other Waste Management Services"),
+    (6111, "Elementary and Secondary Schools"),
+    (6112, "Junior Colleges"),
+    (6113, "Colleges, Universities and Professional Schools"),
+    (
+        6114,
+        "Business Schools and Computer and Management Training",
+    ),
+    (6115, "Technical and Trade Schools"),
+    (6116, "Other Schools and Instruction"),
+    (6117, "Educational Support Services"),
+    (6211, "Offices of Physicians"),
+    (6212, "Offices of Dentists"),
+    (6213, "Offices of Other Health Practitioners"),
+    (6214, "Outpatient Care Centers"),
+    (6215, "Medical and Diagnostic Laboratories"),
+    (6216, "Home Health Care Services"),
+    (6219, "Other Ambulatory Health Care Services"),
+    (6221, "General Medical and Surgical Hospitals"),
+    (6222, "Psychiatric and Substance Abuse Hospitals"),
+    (
+        6223,
+        "Specialty (except Psychiatric and Substance Abuse) Hospitals",
+    ),
+    (6231, "Nursing Care Facilities (Skilled Nursing Facilities)"),
+    (
+        6232,
+        "Residential Intellectual and Developmental Disability, Mental Health and Substance Abuse Facilities",
+    ),
+    (
+        6233,
+        "Continuing Care Retirement Communities and Assisted Living Facilities for the Elderly",
+    ),
+    (6239, "Other Residential Care Facilities"),
+    (6241, "Individual and Family Services"),
+    (
+        6242,
+        "Community Food and Housing, and Emergency and Other Relief Services",
+    ),
+    (6243, "Vocational Rehabilitation Services"),
+    (6244, "Child Day Care Services"),
+    (7111, "Performing Arts Companies"),
+    (7112, "Spectator Sports"),
+    (
+        7113,
+        "Promoters of Performing Arts, Sports and Similar Events",
+    ),
+    (
+        7114,
+        "Agents and Managers for Artists, Athletes, Entertainers and Other Public Figures",
+    ),
+    (7115, "Independent Artists, Writers and Performers"),
+    (7121, "Museums, Historical Sites and Similar Institutions"),
+    (7131, "Amusement Parks and Arcades"),
+    (7132, "Gambling Industries"),
+    (7139, "Other Amusement and Recreation Industries"),
+    (7211, "Traveler Accommodation"),
+    (
+        7212,
+        "RV (Recreational Vehicle) Parks and Recreational Camps",
+    ),
+    (
+        7213,
+        "Rooming and Boarding Houses, Dormitories and Workers' Camps",
+    ),
+    (7223, "Special Food Services"),
+    (7224, "Drinking Places (Alcoholic Beverages)"),
+    (7225, "Restaurants and Other Eating Places"),
+    (8111, "Automotive Repair and Maintenance"),
+    (
+        8112,
+        "Electronic and Precision Equipment Repair and Maintenance",
+    ),
+    (
+        8113,
+        "Commercial and Industrial Machinery and Equipment (except Automotive and Electronic) Repair and Maintenance",
+    ),
+    (8114, "Personal and Household Goods Repair and Maintenance"),
+    (8121, "Personal Care Services"),
+    (8122, "Death Care Services"),
+    (8123, "Drycleaning and Laundry Services"),
+    (8129, "Other Personal Services"),
+    (8131, "Religious Organizations"),
+    (8132, "Grantmaking and Giving Services"),
+    (8133, "Social Advocacy Organizations"),
+    (8134, "Civic and Social Organizations"),
+    (
+        8139,
+        "Business, Professional, Labor, Political and Similar Organizations",
+    ),
+    (8141, "Private Households"),
+    (
+        9211,
+        "Executive, Legislative and Other General Government Support",
+    ),
+    (9221, "Justice, Public Order and Safety Activities"),
+    (9231, "Administration of Human Resource Programs"),
+    (9241, "Administration of Environmental Quality Programs"),
+    (
+        9251,
+        "Administration of Housing Programs, Urban Planning and Community Development",
+    ),
+    (9261, "Administration of Economic Programs"),
+    (9271, "Space Research and Technology"),
+    (9281, "National Security and International Affairs"),
+    (9999, "Unclassified Establishments"),
+];
+
+/// The `Naics` struct holds the position of a NAICS industry code in the NAICS hierarchy.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+pub struct Naics {
+    /// The `industry_code` field is the NAICS industry code, such as 722511.
+    pub industry_code: i64,
+    /// The `sector_code` field is the two-digit prefix of the industry code, such as 72, as
+    /// recorded in the city business layer.  See [`Naics::sector`] for the canonical sector.
+    pub sector_code: i32,
+    /// The `sector_name` field is the description of the sector.
+    pub sector_name: &'static str,
+    /// The `subsector_code` field is the four-digit NAICS industry group code, such as 7225,
+    /// recorded as the subsector in the city business layer.
+    pub subsector_code: i32,
+    /// The `subsector_name` field is the description of the subsector, if in the embedded
+    /// hierarchy.
+    pub subsector_name: Option<&'static str>,
+}
+
+impl Naics {
+    /// The `lookup` method returns the sector and subsector of the NAICS industry code
+    /// `industry_code`, or `None` if the code is shorter than four digits or the sector is not a
+    /// NAICS sector.
+    pub fn lookup(industry_code: i64) -> Option<Self> {
+        let digits = industry_code.to_string();
+        if industry_code < 0 || digits.len() < 4 {
+            return None;
+        }
+        let sector_code = digits[..2].parse::<i32>().ok()?;
+        let subsector_code = digits[..4].parse::<i32>().ok()?;
+        let (_, sector_name) = NAICS_SECTORS
+            .iter()
+            .find(|(code, _)| *code == sector_code)?;
+        let subsector_name = NAICS_SUBSECTORS
+            .iter()
+            .find(|(code, _)| *code == subsector_code)
+            .map(|(_, name)| *name);
+        Some(Self {
+            industry_code,
+            sector_code,
+            sector_name,
+            subsector_code,
+            subsector_name,
+        })
+    }
+
+    /// The `sector` method returns the canonical code of the sector, the first of the codes
+    /// spanned by manufacturing (31-33), retail trade (44-45), and transportation and warehousing
+    /// (48-49), or the sector code for other sectors.
+    pub fn sector(&self) -> i32 {
+        match self.sector_code {
+            32 | 33 => 31,
+            45 => 44,
+            49 => 48,
+            code => code,
+        }
+    }
+}
+
+/// The `NaicsLevel` enum sets the level of the NAICS hierarchy used to group a
+/// [`NaicsSummaries`] report.
+#[derive(
+    Debug,
+    Default,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    strum::Display,
+    strum::EnumString,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum NaicsLevel {
+    /// Groups licenses by two-digit sector.
+    Sector,
+    /// Groups licenses by four-digit subsector.
+    #[default]
+    Subsector,
+}
+
+/// The `NaicsSummary` struct counts the licenses in a NAICS sector or subsector, within a
+/// business district when districts are provided.
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct NaicsSummary {
+    /// The `district` field is the business district, if known.
+    pub district: Option<String>,
+    /// The `sector_code` field is the canonical NAICS sector code from [`Naics::sector`], or
+    /// `None` for licenses with an unrecognized industry code.
+    pub sector_code: Option<i32>,
+    /// The `sector_name` field is the description of the sector.
+    pub sector_name: Option<String>,
+    /// The `subsector_code` field is the NAICS subsector code, or `None` when summarizing by
+    /// sector.
+    pub subsector_code: Option<i32>,
+    /// The `subsector_name` field is the description of the subsector.
+    pub subsector_name: Option<String>,
+    /// The `licenses` field is the number of distinct licenses in the group.
+    pub licenses: usize,
+    /// The `matched` field is the number of licenses in the group with a matched location, meaning
+    /// a matching or divergent address.
+    pub matched: usize,
+}
+
+/// The `NaicsSummaries` struct holds a vector of type [`NaicsSummary`].
+#[derive(
+    Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Deref, DerefMut,
+)]
+pub struct NaicsSummaries(Vec<NaicsSummary>);
+
+impl NaicsSummaries {
+    /// The `from_matches` method counts the licenses in `records` by the NAICS hierarchy at
+    /// `level`, along with the number of licenses with a matched location.  Sectors spanning
+    /// several codes are grouped under the canonical code from [`Naics::sector`].  The
+    /// `districts` map of license numbers to business districts, such as from
+    /// [`crate::Businesses::districts`], further groups the licenses by district.  Pass an empty
+    /// map to ignore districts.
+    pub fn from_matches(
+        records: &[BusinessMatchRecord],
+        level: NaicsLevel,
+        districts: &HashMap<String, String>,
+    ) -> Self {
+        type Key = (Option<String>, Option<i32>, Option<i32>);
+        let mut licenses: BTreeMap<Key, HashSet<String>> = BTreeMap::new();
+        let mut matched: BTreeMap<Key, HashSet<String>> = BTreeMap::new();
+        let mut summaries: BTreeMap<Key, NaicsSummary> = BTreeMap::new();
+        for record in records {
+            let naics = record.naics();
+            let subsector = match level {
+                NaicsLevel::Sector => None,
+                NaicsLevel::Subsector => naics,
+            };
+            let district = districts.get(&record.license()).cloned();
+            let key = (
+                district.clone(),
+                naics.map(|n| n.sector()),
+                subsector.map(|n| n.subsector_code),
+            );
+            summaries
+                .entry(key.clone())
+                .or_insert_with(|| NaicsSummary {
+                    district,
+                    sector_code: naics.map(|n| n.sector()),
+                    sector_name: naics.map(|n| n.sector_name.to_string()),
+                    subsector_code: subsector.map(|n| n.subsector_code),
+                    subsector_name: subsector.and_then(|n| n.subsector_name.map(|s| s.to_string())),
+                    ..Default::default()
+                });
+            licenses
+                .entry(key.clone())
+                .or_default()
+                .insert(record.license());
+            if matches!(
+                record.match_status(),
+                MatchStatus::Matching | MatchStatus::Divergent
+            ) {
+                matched.entry(key).or_default().insert(record.license());
+            }
+        }
+        let records = summaries
+            .into_iter()
+            .map(|(key, mut summary)| {
+                summary.licenses = licenses.get(&key).map_or(0, |l| l.len());
+                summary.matched = matched.get(&key).map_or(0, |l| l.len());
+                summary
+            })
+            .collect::<Vec<NaicsSummary>>();
+        info!("{} groups summarized.", records.len());
+        Self(records)
+    }
+}
+
+impl IntoCsv<NaicsSummaries> for NaicsSummaries {
+    fn from_csv<P: AsRef<std::path::Path>>(path: P) -> Result<Self, Io> {
+        let records = from_csv(path)?;
+        Ok(Self(records))
+    }
+
+    fn to_csv<P: AsRef<std::path::Path>>(&mut self, path: P) -> Result<(), AddressErrorKind> {
+        to_csv(&mut self.0, path.as_ref().into())
+    }
+}
@@ -1,9 +1,10 @@
 //! The `utils` module contains utility functions accessed by multiple data types, where declaring
 //! a stand-alone function eliminates code duplication in different methods.
-use crate::{AddressError, AddressErrorKind, Csv, Encode, Io};
+use crate::{Address, AddressError, AddressErrorKind, Csv, Encode, Geographic, Io};
 use indicatif::{ProgressBar, ProgressStyle};
 use serde::Serialize;
 use serde::de::{Deserialize, DeserializeOwned, Deserializer};
+use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::time::Duration;
@@ -27,6 +28,75 @@ pub fn deserialize_arcgis_data<'de, D: Deserializer<'de>>(
     }
 }
 
+/// Function for deserializing dates from EnerGov exports, which record dates as timestamps such
+/// as "2025-07-31 14:04:36.000".  Also accepts plain dates such as "2025-07-31", the format used
+/// when serializing a date.  The time of day is discarded.  Empty values and the null markers
+/// "NULL" and "<Null>" translate to `None`.  Any other value that fails to parse is an error, so
+/// that the row is rejected rather than read as a license without the date.
+///
+/// Used to deserialize the license dates of a [`BusinessLicense`](crate::BusinessLicense).
+pub fn deserialize_date<'de, D: Deserializer<'de>>(
+    de: D,
+) -> Result<Option<chrono::NaiveDate>, D::Error> {
+    let intermediate: Option<String> = Deserialize::deserialize(de)?;
+    let value = match intermediate.as_deref().map(str::trim) {
+        None | Some("") | Some("NULL") | Some("<Null>") => return Ok(None),
+        Some(value) => value,
+    };
+    chrono::NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S%.f")
+        .map(|timestamp| timestamp.date())
+        .or_else(|_| chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d"))
+        .map(Some)
+        .map_err(|e| serde::de::Error::custom(format!("invalid date {}: {}", value, e)))
+}
+
+/// The `RECORD_NAMESPACE` constant is the namespace for the version 5 uuids produced by
+/// [`record_id`].
+pub const RECORD_NAMESPACE: uuid::Uuid =
+    uuid::Uuid::from_u128(0x6d8c_5a1e_3f4b_4c2a_9e71_b0d2_4f86_a913);
+
+/// The `record_id` function returns a version 5 uuid over the canonical key formed by joining
+/// `parts` with a "|" separator.  Match records use the function to produce identifiers that are
+/// identical across runs on the same inputs.
+pub fn record_id(parts: &[&str]) -> uuid::Uuid {
+    uuid::Uuid::new_v5(&RECORD_NAMESPACE, parts.join("|").as_bytes())
+}
+
+/// The `record_identity` function returns the canonical identity of `address` for use in a
+/// [`record_id`] key.  Uses the native identifier of the address if present, otherwise the
+/// address label and coordinates.
+pub fn record_identity<T: Address + Geographic + ?Sized>(address: &T) -> String {
+    match address.identifier() {
+        Some(id) => id,
+        None => format!(
+            "{} ({}, {})",
+            address.label(),
+            address.longitude(),
+            address.latitude()
+        ),
+    }
+}
+
+/// The `record_identities` function returns the [`record_identity`] of each address in
+/// `addresses`.  When several addresses share an identity, each repeat after the first is told
+/// apart by its occurrence among the addresses with that identity, so adding, removing or
+/// reordering unrelated addresses leaves the identities unchanged.
+pub fn record_identities<T: Address + Geographic>(addresses: &[T]) -> Vec<String> {
+    let mut occurrences: HashMap<String, usize> = HashMap::new();
+    addresses
+        .iter()
+        .map(|address| {
+            let identity = record_identity(address);
+            let occurrence = occurrences.entry(identity.clone()).or_default();
+            *occurrence += 1;
+            match *occurrence {
+                1 => identity,
+                n => format!("{} #{}", identity, n),
+            }
+        })
+        .collect()
+}
+
 /// Generic function to serialize data types into a CSV file.  Called by methods to avoid code
 /// duplication.
 ///
@@ -87,31 +157,131 @@ pub fn to_csv<T: Serialize + Clone>(item: &mut [T], path: PathBuf) -> Result<(),
 pub fn from_csv<T: DeserializeOwned + Clone, P: AsRef<std::path::Path>>(
     path: P,
 ) -> Result<Vec<T>, Io> {
+    let (records, rejections) = from_csv_with(path, Ok::<T, std::convert::Infallible>)?;
+    for rejection in rejections.iter() {
+        tracing::trace!("Dropping: {}", rejection.error);
+    }
+    tracing::info!("{} records dropped.", rejections.len());
+    Ok(records)
+}
+
+/// Generic function to deserialize rows of type `T` from a CSV file, converting each row to type
+/// `U` using `convert`.  Rows that fail to deserialize or convert are collected as [`Rejections`]
+/// instead of aborting the load, so that callers can keep every good row and report every bad
+/// one.
+///
+/// See
+/// [`BusinessLicenses::from_csv_with_rejections`](crate::BusinessLicenses::from_csv_with_rejections),
+/// [`Businesses::from_raw_csv_with_rejections`](crate::Businesses::from_raw_csv_with_rejections),
+/// [`FireInspections::from_csv_with_rejections`](crate::FireInspections::from_csv_with_rejections).
+pub fn from_csv_with<T, U, E, F, P>(path: P, convert: F) -> Result<(Vec<U>, Rejections), Io>
+where
+    T: DeserializeOwned,
+    E: std::fmt::Display,
+    F: Fn(T) -> Result<U, E>,
+    P: AsRef<std::path::Path>,
+{
+    let file = std::fs::File::open(&path)
+        .map_err(|source| Io::new(path.as_ref().into(), source, line!(), file!().into()))?;
+    // The reader is flexible so that rows with the wrong number of fields can be reported with
+    // their raw text, rather than as a bare read error.
+    let mut rdr = csv::ReaderBuilder::new().flexible(true).from_reader(file);
+    // A missing or unreadable header leaves every row to fail deserialization.
+    let headers = rdr.headers().cloned().ok();
+    let width = headers.as_ref().map_or(0, |headers| headers.len());
     let mut records = Vec::new();
-    match std::fs::File::open(&path) {
-        Ok(file) => {
-            let mut rdr = csv::Reader::from_reader(file);
-
-            let mut dropped = 0;
-            for result in rdr.deserialize() {
-                match result {
-                    Ok(record) => records.push(record),
-                    Err(e) => {
-                        tracing::trace!("Dropping: {}", e.to_string());
-                        dropped += 1;
-                    }
+    let mut rejections = Vec::new();
+    for result in rdr.records() {
+        match result {
+            Ok(record) => {
+                let row = record.position().map_or(0, |position| position.line());
+                let converted = if record.len() != width {
+                    Err(format!(
+                        "found record with {} fields, but the header has {} fields",
+                        record.len(),
+                        width
+                    ))
+                } else {
+                    record
+                        .deserialize::<T>(headers.as_ref())
+                        .map_err(|e| e.to_string())
+                        .and_then(|value| convert(value).map_err(|e| e.to_string()))
+                };
+                match converted {
+                    Ok(value) => records.push(value),
+                    Err(error) => rejections.push(Rejection {
+                        row,
+                        raw: raw_record(&record),
+                        error,
+                    }),
                 }
             }
-            tracing::info!("{} records dropped.", dropped);
-
-            Ok(records)
+            Err(e) => rejections.push(Rejection {
+                row: e.position().map_or(0, |position| position.line()),
+                raw: String::new(),
+                error: e.to_string(),
+            }),
         }
-        Err(source) => Err(Io::new(
-            path.as_ref().into(),
-            source,
-            line!(),
-            file!().into(),
-        )),
+    }
+    Ok((records, Rejections(rejections)))
+}
+
+/// The `raw_record` function writes `record` back to a line of csv text, preserving the quoting
+/// of fields that contain delimiters.
+fn raw_record(record: &csv::StringRecord) -> String {
+    let mut wtr = csv::WriterBuilder::new()
+        .terminator(csv::Terminator::Any(b'\n'))
+        .from_writer(Vec::new());
+    if wtr.write_record(record).is_err() {
+        return record.iter().collect::<Vec<&str>>().join(",");
+    }
+    match wtr.into_inner() {
+        Ok(bytes) => String::from_utf8_lossy(&bytes).trim_end().to_string(),
+        Err(_) => record.iter().collect::<Vec<&str>>().join(","),
+    }
+}
+
+/// The `Rejection` struct reports a row of an import file that could not be read, either because
+/// the row failed to deserialize or because a field, such as an address, failed to parse.
+#[derive(
+    Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, serde::Deserialize,
+)]
+pub struct Rejection {
+    /// The `row` field is the line number of the row in the import file, where the header is
+    /// line 1.
+    pub row: u64,
+    /// The `raw` field is the text of the row as csv.
+    pub raw: String,
+    /// The `error` field describes the deserialize or parse error.
+    pub error: String,
+}
+
+/// The `Rejections` struct holds a vector of type [`Rejection`], the rejection report of an
+/// import.
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    serde::Deserialize,
+    derive_more::Deref,
+    derive_more::DerefMut,
+)]
+pub struct Rejections(Vec<Rejection>);
+
+impl IntoCsv<Rejections> for Rejections {
+    fn from_csv<P: AsRef<Path>>(path: P) -> Result<Self, Io> {
+        let records = from_csv(path)?;
+        Ok(Self(records))
+    }
+
+    fn to_csv<P: AsRef<Path>>(&mut self, path: P) -> Result<(), AddressErrorKind> {
+        to_csv(&mut self.0, path.as_ref().into())
     }
 }
 
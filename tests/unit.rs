@@ -1,10 +1,21 @@
+use chrono::NaiveDate;
 use destination::{
-    Address, Addresses, BusinessLicenses, BusinessMatchRecords, Businesses,
-    FireInspectionMatchRecords, FireInspections, GeoAddresses, GrantsPassAddresses,
-    GrantsPassSpatialAddresses, IntoBin, IntoCsv, JosephineCountyAddresses2024, MatchRecords, Nom,
-    Parse, PartialAddress, PostalCommunity, SpatialAddresses, StreetNamePostType,
-    StreetNamePreDirectional, SubaddressType, from_csv,
+    Address, AddressColumns, AddressComponent, AddressStatus, Addressable, Addresses, Boundary,
+    BoundaryPoint, Business, BusinessLicenses, BusinessMatchRecords, BusinessSync,
+    BusinessSyncRecords, Businesses, Cardinality, CommonAddresses, Conflation, CorrectionRecords,
+    Corrections, DiffRecords, DiffStatus, DuplicateRecords, FireInspectionMatchRecords,
+    FireInspectionMatches, FireInspections, FuzzyIndex, FuzzyMatchRecords, GeoAddresses,
+    Geographic, GrantsPassAddresses, GrantsPassSpatialAddresses, IntoBin, IntoCsv, JoinKind,
+    JoinPairs, JosephineCountyAddresses2024, JosephineCountySpatialAddress2024, Jurisdiction,
+    LocatableMatchRecords, LocatableRecords, MatchPartialRecord, MatchPolicy, MatchRecords,
+    MatchRule, MatchStatus, MatrixRecords, Mismatch, MismatchRecords, MismatchSeverity, Naics,
+    NaicsLevel, NaicsSummaries, NearDuplicate, NearDuplicateRecords, NearDuplicates, Nom, Parse,
+    PartialAddress, PhoneticRecords, PostalCommunity, PrecedencePolicy, Presence, ProgressRecords,
+    ReconcileRecords, ReconcileStatus, Region, Rejections, SpatialAddresses, StreetNamePostType,
+    StreetNamePreDirectional, StreetNamePreType, SubaddressType, SyncStatus, from_csv,
+    from_csv_with, metaphone,
 };
+use std::collections::HashMap;
 use test_log::test;
 use tracing::{info, trace};
 
@@ -409,25 +420,34 @@ fn load_fire_inspections() -> anyhow::Result<()> {
     Ok(())
 }
 
-// #[test]
-// fn compare_fire_inspections() -> Clean<()> {
-//     if let Ok(()) = tracing_subscriber::fmt()
-//         .with_max_level(tracing::Level::TRACE)
-//         .try_init()
-//     {};
-//     let file_path = "p:/fire_inspection.csv";
-//     let fire = FireInspections::from_csv(file_path)?;
-//     let path = std::env::current_dir()?;
-//     let file_path = path.join("tests/test_data/city_addresses_20230626.csv");
-//     let addresses = CityAddresses::from_csv(file_path)?;
-//     let addresses = Addresses::from(addresses);
-//     let mut compared =
-//         FireInspectionMatchRecords::from(&FireInspectionMatches::compare(&fire, &addresses));
-//     compared.to_csv("p:/fire_inspections_matched.csv".into())?;
-//     info!("Total records: {}.", compared.records().len());
-//
-//     Ok(())
-// }
+#[test]
+fn compare_fire_inspections() -> anyhow::Result<()> {
+    let city_path = "data/addresses.data";
+    let addresses = SpatialAddresses::load(city_path)?;
+    let file_path = std::env::temp_dir().join("destination_fire_inspections.csv");
+    let mut wtr = csv::Writer::from_path(&file_path)?;
+    wtr.write_record(["Name", "Address", "Class", "Subclass"])?;
+    wtr.write_record(["First", &addresses[0].label(), "B", "Office"])?;
+    wtr.write_record(["Second", "99999 NOWHERE ST", "M", ""])?;
+    wtr.flush()?;
+    let fire = FireInspections::from_csv(&file_path)?;
+    let matches = FireInspectionMatches::compare(&fire, &addresses);
+    let mut compared = FireInspectionMatchRecords::from(&matches);
+    assert_eq!(compared[0].status(), MatchStatus::Matching);
+    assert_eq!(compared[0].name(), "First");
+    assert_eq!(compared[0].class(), Some("B".to_string()));
+    assert_eq!(compared[0].subclass(), Some("Office".to_string()));
+    let output = std::env::temp_dir().join("destination_fire_inspections_matched.csv");
+    compared.to_csv(&output)?;
+    let mut missing = FireInspectionMatchRecords::from_csv(&output)?;
+    missing.filter("missing");
+    assert_eq!(missing.len(), 1);
+    assert_eq!(missing[0].name(), "Second");
+    assert_eq!(missing[0].class(), Some("M".to_string()));
+    assert_eq!(missing[0].subclass(), None);
+    info!("Total records: {}.", compared.len());
+    Ok(())
+}
 
 #[test]
 #[cfg_attr(feature = "ci", ignore)]
@@ -600,3 +620,1291 @@ fn business_mailing() -> anyhow::Result<()> {
     tracing::info!("Mailing list: {} records", mail.len());
     Ok(())
 }
+
+#[test]
+fn duplicate_groups() -> anyhow::Result<()> {
+    let city_path = "data/addresses.data";
+    let city_addresses = SpatialAddresses::load(city_path)?;
+    let mut addresses = CommonAddresses::from(&city_addresses[0..100]);
+    let mut copy = addresses[3].clone();
+    let same = copy.clone();
+    copy.status = AddressStatus::Retired;
+    addresses.push(copy);
+    addresses.push(same);
+    let groups = addresses.duplicates();
+    let group = groups
+        .iter()
+        .find(|g| g.label == addresses[3].label())
+        .unwrap();
+    assert_eq!(group.size(), 3);
+    assert_eq!(group.differences, vec!["status".to_string()]);
+    let records = DuplicateRecords::from(&groups);
+    assert_eq!(records.len(), groups.records().len());
+    assert_eq!(addresses.filter("duplicate").len(), records.len());
+    info!("Duplicate groups: {}", groups.len());
+    Ok(())
+}
+
+#[test]
+fn near_duplicate_addresses() -> anyhow::Result<()> {
+    let city_path = "data/addresses.data";
+    let city_addresses = SpatialAddresses::load(city_path)?;
+    let mut addresses = SpatialAddresses::from(&city_addresses[0..100]);
+    let original = addresses
+        .iter()
+        .find(|a| a.subaddress_id().is_none() && a.number_suffix().is_none())
+        .unwrap()
+        .clone();
+    let mut typo = original.clone();
+    let mut name = typo.street_name().clone();
+    name.push('S');
+    *typo.street_name_mut() = name;
+    let mut suffix = original.clone();
+    *suffix.number_suffix_mut() = Some("A".to_string());
+    let mut unit = original.clone();
+    *unit.subaddress_id_mut() = Some("A".to_string());
+    addresses.push(typo.clone());
+    addresses.push(suffix.clone());
+    addresses.push(unit.clone());
+    let pairs = addresses.near_duplicates(0.8);
+    assert!(pairs.iter().any(|p| {
+        p.left.label() == original.label() && p.right.label() == typo.label()
+            || p.left.label() == typo.label() && p.right.label() == original.label()
+    }));
+    let pair = pairs
+        .iter()
+        .find(|p| {
+            p.left.label() == suffix.label() && p.right.label() == unit.label()
+                || p.left.label() == unit.label() && p.right.label() == suffix.label()
+        })
+        .unwrap();
+    assert_eq!(pair.score.subaddress, 1.0);
+    assert!(pair.reasons.iter().any(|r| r.starts_with("unit")));
+    // A parent address and its unit differ only in the unit, and are not duplicates.
+    let mut apartment = original.clone();
+    *apartment.subaddress_id_mut() = Some("1".to_string());
+    assert!(NearDuplicate::compare(&original, &apartment, 0.0).is_none());
+    assert!(!pairs.iter().any(|p| {
+        p.left.label() == original.label() && p.right.label() == unit.label()
+            || p.left.label() == unit.label() && p.right.label() == original.label()
+    }));
+    let nearby = NearDuplicates::within(&addresses, 0.8, 1.0);
+    assert!(nearby.iter().all(|p| p.distance.unwrap() <= 1.0));
+    let records = NearDuplicateRecords::from(&pairs);
+    assert_eq!(records.len(), pairs.len());
+    info!("Near duplicate pairs: {}", pairs.len());
+    Ok(())
+}
+
+#[test]
+fn fuzzy_match_addresses() -> anyhow::Result<()> {
+    let city_path = "data/addresses.data";
+    let city_addresses = SpatialAddresses::load(city_path)?;
+    let targets = &city_addresses[0..200];
+    let mut source = SpatialAddresses::from(&city_addresses[0..10]);
+    let original = source[0].clone();
+    let mut name = source[0].street_name().clone();
+    name.push('S');
+    *source[0].street_name_mut() = name;
+    let match_records =
+        MatchRecords::compare_fuzzy(&source, targets, &MatchPolicy::default(), 0.8, None);
+    let record = match_records
+        .iter()
+        .find(|r| r.address_label == source[0].label())
+        .unwrap();
+    assert_eq!(record.match_status, MatchStatus::Missing);
+    assert_eq!(record.candidate_label, Some(original.label()));
+    let fuzzy_records =
+        FuzzyMatchRecords::compare(&source, targets, &MatchPolicy::default(), 3, 0.8);
+    assert!(!fuzzy_records.is_empty());
+    assert!(
+        fuzzy_records
+            .iter()
+            .all(|r| r.address_label == source[0].label())
+    );
+    assert_eq!(fuzzy_records[0].rank, 1);
+    assert_eq!(fuzzy_records[0].candidate_label, Some(original.label()));
+    assert!(fuzzy_records[0].street_name.unwrap() < 1.0);
+    // The index finds the exact matches of the unchanged addresses, blocking on the number.
+    let index = FuzzyIndex::new(targets);
+    assert!(!index.coincident(&source[0], &MatchPolicy::default()));
+    assert!(index.coincident(&source[1], &MatchPolicy::default()));
+    let unblocked = MatchPolicy::partial().with_rule(AddressComponent::Number, MatchRule::Ignored);
+    assert!(!index.coincident(&source[0], &unblocked));
+    assert!(index.coincident(&source[1], &unblocked));
+
+    // A mistyped address number is found through the street name.
+    let mut source = SpatialAddresses::from(&city_addresses[0..1]);
+    let number = original.number();
+    *source[0].number_mut() = number * 10 + number % 10;
+    let fuzzy_records =
+        FuzzyMatchRecords::compare(&source, targets, &MatchPolicy::default(), 3, 0.8);
+    assert_eq!(fuzzy_records[0].candidate_label, Some(original.label()));
+    let score = fuzzy_records[0].number.unwrap();
+    assert!(score > 0.0 && score < 1.0);
+    Ok(())
+}
+
+#[test]
+fn phonetic_street_names() -> anyhow::Result<()> {
+    assert_eq!(metaphone("ASHLEY"), metaphone("ASHLEE"));
+    assert_eq!(metaphone("BRIDGE"), metaphone("BRIDGES"));
+    assert_ne!(metaphone("BRIDGE"), metaphone("BROOK"));
+    let city_path = "data/addresses.data";
+    let city_addresses = SpatialAddresses::load(city_path)?;
+    let mut addresses = SpatialAddresses::from(&city_addresses[0..100]);
+    *addresses[0].street_name_mut() = "ASHLEY".to_string();
+    let mut alike = addresses[0].clone();
+    *alike.street_name_mut() = "ASHLEE".to_string();
+    assert_eq!(
+        alike.phonetic_street_name(),
+        addresses[0].phonetic_street_name()
+    );
+    addresses.push(alike);
+    let groups = addresses.phonetic_groups();
+    let similar = groups.similar(&addresses[0].common_street_name());
+    assert!(similar.contains(&addresses[100].common_street_name()));
+    let sound_alike = groups.sound_alike();
+    assert!(sound_alike.iter().all(|g| g.names.len() > 1));
+    let records = PhoneticRecords::from(&sound_alike);
+    assert_eq!(records.len(), sound_alike.len());
+    Ok(())
+}
+
+#[test]
+fn match_policy() -> anyhow::Result<()> {
+    let city_path = "data/addresses.data";
+    let city_addresses = SpatialAddresses::load(city_path)?;
+    let mut address = city_addresses[0].clone();
+    let original = address.clone();
+    *address.zip_mut() = 97000;
+    assert!(!address.coincident(&original).coincident);
+    // The same pair is missing under the strict policy and divergent under the loose policy.
+    let status = |policy: &MatchPolicy| {
+        MatchRecords::new_with_policy(&address, std::slice::from_ref(&original), policy)[0]
+            .match_status
+            .clone()
+    };
+    assert_eq!(status(&MatchPolicy::strict()), MatchStatus::Missing);
+    assert_eq!(status(&MatchPolicy::loose()), MatchStatus::Divergent);
+    let loose = MatchPolicy::loose();
+    assert_eq!(loose.rule(AddressComponent::Zip), MatchRule::Mismatch);
+    assert!(address.coincident_with(&original, &loose).coincident);
+    let ignore_zip = MatchPolicy::strict().with_rule(AddressComponent::Zip, MatchRule::Ignored);
+    let matched = address.coincident_with(&original, &ignore_zip);
+    assert!(matched.coincident);
+    assert_eq!(matched.mismatches, None);
+    let path = std::env::temp_dir().join("destination_match_policy.csv");
+    let mut written = MatchPolicy::loose();
+    written.to_csv(&path)?;
+    assert_eq!(MatchPolicy::load(path.to_str().unwrap())?, loose);
+    assert_eq!(MatchPolicy::load("partial")?, MatchPolicy::partial());
+    Ok(())
+}
+
+#[test]
+fn match_policy_invalid() -> anyhow::Result<()> {
+    let path = std::env::temp_dir().join("destination_match_policy_invalid.csv");
+    let path = path.to_str().unwrap();
+    // A misspelled component fails the load rather than leaving the component ignored.
+    std::fs::write(
+        path,
+        "component,rule\nnumber,required\nstreet_nam,required\n",
+    )?;
+    assert!(MatchPolicy::load(path).is_err());
+    // So does a misspelled rule.
+    std::fs::write(path, "component,rule\nnumber,requried\n")?;
+    assert!(MatchPolicy::load(path).is_err());
+    // A policy must compare the address number.
+    std::fs::write(path, "component,rule\nstreet_name,required\n")?;
+    assert!(MatchPolicy::load(path).is_err());
+    std::fs::write(
+        path,
+        "component,rule\nnumber,required\nstreet_name,required\n",
+    )?;
+    let policy = MatchPolicy::load(path)?;
+    assert_eq!(policy.rule(AddressComponent::Number), MatchRule::Required);
+    assert_eq!(policy.rule(AddressComponent::Zip), MatchRule::Ignored);
+    Ok(())
+}
+
+#[test]
+fn component_mismatches() -> anyhow::Result<()> {
+    let city_path = "data/addresses.data";
+    let city_addresses = SpatialAddresses::load(city_path)?;
+    let original = city_addresses[0].clone();
+    let mut address = original.clone();
+    *address.zip_mut() = 97000;
+    let mismatch = AddressComponent::Zip.mismatch(&address, &original);
+    assert_eq!(
+        mismatch,
+        Mismatch::Zip {
+            from: 97000,
+            to: original.zip()
+        }
+    );
+    assert_eq!(mismatch.severity(), MismatchSeverity::Moderate);
+    assert_eq!(
+        mismatch.message(),
+        format!("97000 not equal to {}", original.zip())
+    );
+    let records =
+        MatchRecords::new_with_policy(&address, &city_addresses[0..1], &MatchPolicy::loose());
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].match_status, MatchStatus::Divergent);
+    assert_eq!(records[0].components, Some("zip".to_string()));
+    assert!(records[0].has_mismatch(AddressComponent::Zip));
+    assert_eq!(records[0].severity, Some(MismatchSeverity::Moderate));
+    assert_eq!(records.clone().filter("zip").len(), 1);
+    assert_eq!(records.clone().filter("moderate").len(), 1);
+    assert_eq!(records.clone().filter("major").len(), 0);
+    let path = std::env::temp_dir().join("destination_component_mismatches.csv");
+    let mut written = records.clone();
+    written.to_csv(&path)?;
+    let read = MatchRecords::from_csv(&path)?;
+    assert_eq!(read[0].components, records[0].components);
+    assert_eq!(read[0].severity, records[0].severity);
+    assert_eq!(read.filter("zip").len(), 1);
+    // The values of each mismatched component are reported in their own columns.
+    let mut mismatches = MismatchRecords::from(&records);
+    assert_eq!(mismatches.len(), 1);
+    assert_eq!(mismatches[0].id, records[0].id);
+    assert_eq!(mismatches[0].component, AddressComponent::Zip);
+    assert_eq!(mismatches[0].severity, MismatchSeverity::Moderate);
+    assert_eq!(mismatches[0].source, "97000");
+    assert_eq!(mismatches[0].target, original.zip().to_string());
+    let path = std::env::temp_dir().join("destination_component_mismatch_values.csv");
+    mismatches.to_csv(&path)?;
+    assert_eq!(MismatchRecords::from_csv(&path)?, mismatches);
+    Ok(())
+}
+
+#[test]
+fn reconcile_addresses() -> anyhow::Result<()> {
+    let city_path = "data/addresses.data";
+    let city_addresses = SpatialAddresses::load(city_path)?;
+    let source = city_addresses[0..50].to_vec();
+    let mut target = city_addresses[25..75].to_vec();
+    *target[0].zip_mut() = 97000;
+    let records = ReconcileRecords::compare(&source, &target, &MatchPolicy::loose());
+    let divergent = records.clone().filter("divergent");
+    assert!(
+        divergent
+            .iter()
+            .any(|r| r.source_index == Some(25) && r.target_index == Some(0))
+    );
+    assert!(
+        records
+            .iter()
+            .filter(|r| r.status == ReconcileStatus::OnlySource)
+            .all(|r| r.target_label.is_none() && r.source_index < Some(25))
+    );
+    assert!(
+        records
+            .iter()
+            .filter(|r| r.status == ReconcileStatus::OnlyTarget)
+            .all(|r| r.source_label.is_none() && r.target_index >= Some(25))
+    );
+    let summary = records.summary();
+    let total = summary
+        .group("status")
+        .iter()
+        .map(|s| s.total)
+        .sum::<usize>();
+    assert_eq!(total, records.len());
+    let zips = summary.group("zip").iter().map(|s| s.total).sum::<usize>();
+    assert_eq!(zips, records.len());
+    info!("Reconciled records: {}", records.len());
+    Ok(())
+}
+
+#[test]
+fn match_target_identity() -> anyhow::Result<()> {
+    let city_path = "data/addresses.data";
+    let city_addresses = SpatialAddresses::load(city_path)?;
+    let source = city_addresses[0..10].to_vec();
+    let mut target = GeoAddresses::from(&city_addresses[0..10]);
+    assert_eq!(target[0].identifier(), None);
+    for (i, address) in target.iter_mut().enumerate() {
+        address.id = Some(format!("T{i}"));
+    }
+    let records = MatchRecords::compare(&source, &target);
+    for record in records.iter() {
+        assert_ne!(record.match_status, MatchStatus::Missing);
+        let index = target
+            .iter()
+            .position(|t| t.id == record.target_id)
+            .unwrap();
+        assert_eq!(record.target_label, Some(target[index].label()));
+        assert_eq!(record.target_latitude, Some(target[index].latitude));
+        assert_eq!(record.target_longitude, Some(target[index].longitude));
+        assert_eq!(record.source_id, None);
+    }
+    Ok(())
+}
+
+#[test]
+fn deterministic_match_ids() -> anyhow::Result<()> {
+    let city_path = "data/addresses.data";
+    let city_addresses = SpatialAddresses::load(city_path)?;
+    let source = city_addresses[0..20].to_vec();
+    let target = city_addresses[10..30].to_vec();
+    let first = MatchRecords::compare(&source, &target);
+    let second = MatchRecords::compare(&source, &target);
+    let ids = |records: &MatchRecords| records.iter().map(|r| r.id).collect::<Vec<uuid::Uuid>>();
+    assert_eq!(ids(&first), ids(&second));
+    let unique = ids(&first)
+        .into_iter()
+        .collect::<std::collections::HashSet<uuid::Uuid>>();
+    assert_eq!(unique.len(), first.len());
+    assert_eq!(first[0].id.get_version_num(), 5);
+    // The status is keyed by its variant name, not its debug output.
+    assert_eq!(MatchStatus::Matching.as_ref(), "Matching");
+    // Identical subject rows without a native identifier receive different ids.
+    assert!(source[0].identifier().is_none());
+    let repeated = vec![source[0].clone(), source[0].clone()];
+    let records = MatchRecords::compare(&repeated, &target);
+    assert_eq!(records.len(), 2);
+    assert_ne!(records[0].id, records[1].id);
+    assert_eq!(MatchRecords::compare(&repeated, &target), records);
+    // Prepending an unrelated row leaves the ids of the existing records unchanged.
+    assert_ne!(source[1].label(), source[0].label());
+    let mut prepended = vec![source[1].clone()];
+    prepended.extend(repeated.clone());
+    let shifted = MatchRecords::compare(&prepended, &target);
+    assert_eq!(shifted.len(), 3);
+    assert_eq!(ids(&shifted)[1..], ids(&records)[..]);
+    let label = source[0].label();
+    let (_, partial) = Parse::address(&label).map_err(|e| anyhow::anyhow!("{e}"))?;
+    let partial_first = MatchPartialRecord::compare(&partial, &target);
+    let partial_second = MatchPartialRecord::compare(&partial, &target);
+    assert_eq!(partial_first[0].id(), partial_second[0].id());
+    Ok(())
+}
+
+#[test]
+fn match_cardinality() -> anyhow::Result<()> {
+    let city_path = "data/addresses.data";
+    let city_addresses = SpatialAddresses::load(city_path)?;
+    let mut parent = city_addresses[0].clone();
+    *parent.subaddress_id_mut() = None;
+    *parent.building_mut() = None;
+    let mut units = Vec::new();
+    for unit in ["A", "B"] {
+        let mut address = parent.clone();
+        *address.subaddress_id_mut() = Some(unit.to_string());
+        units.push(address);
+    }
+    let policy =
+        MatchPolicy::strict().with_rule(AddressComponent::SubaddressId, MatchRule::Mismatch);
+    let records = MatchRecords::compare_with_policy(&[parent.clone()], &units, &policy);
+    assert_eq!(records.len(), 2);
+    assert!(
+        records
+            .iter()
+            .all(|r| r.cardinality == Cardinality::OneToMany)
+    );
+    assert!(records.iter().all(|r| r.target_count == 2));
+    assert_eq!(records.clone().filter("one_to_many").len(), 2);
+
+    let mut twin = parent.clone();
+    twin.latitude += 1.0;
+    let records = MatchRecords::compare(&[parent.clone(), twin], &[parent.clone()]);
+    assert!(
+        records
+            .iter()
+            .all(|r| r.cardinality == Cardinality::ManyToOne)
+    );
+    assert!(records.iter().all(|r| r.source_count == 2));
+    // Identical subject rows without a native identifier are counted separately.
+    assert!(parent.identifier().is_none());
+    let records = MatchRecords::compare(&[parent.clone(), parent.clone()], &[parent.clone()]);
+    assert!(
+        records
+            .iter()
+            .all(|r| r.cardinality == Cardinality::ManyToOne)
+    );
+    assert!(records.iter().all(|r| r.source_count == 2));
+
+    let records = MatchRecords::compare(&[parent.clone()], &[parent.clone()]);
+    assert_eq!(records[0].cardinality, Cardinality::OneToOne);
+    let records = MatchRecords::compare(&[parent], &units);
+    assert_eq!(records[0].cardinality, Cardinality::Unmatched);
+    Ok(())
+}
+
+#[test]
+fn displaced_matches() -> anyhow::Result<()> {
+    let city_path = "data/addresses.data";
+    let city_addresses = SpatialAddresses::load(city_path)?;
+    let source = city_addresses[0].clone();
+    let mut near = source.clone();
+    near.latitude += 0.0001;
+    let mut far = source.clone();
+    far.latitude += 0.01;
+    assert!((source.haversine(&far) - 1111.95).abs() < 1.0);
+    let records = MatchRecords::compare_geographic(
+        std::slice::from_ref(&source),
+        &[far.clone(), near.clone()],
+        &MatchPolicy::default(),
+        100.0,
+    );
+    // Both matches are reported as ambiguous, nearest first.
+    assert_eq!(records.len(), 2);
+    assert!(
+        records
+            .iter()
+            .all(|r| r.cardinality == Cardinality::Ambiguous)
+    );
+    assert_eq!(records[0].match_status, MatchStatus::Matching);
+    assert!(records[0].distance.unwrap() < 100.0);
+    assert_eq!(records[1].match_status, MatchStatus::Displaced);
+    // Degenerate coordinates sort after any measured distance.
+    let mut degenerate = source.clone();
+    degenerate.latitude = f64::NAN;
+    let records = MatchRecords::compare_geographic(
+        std::slice::from_ref(&source),
+        &[degenerate, near.clone()],
+        &MatchPolicy::default(),
+        100.0,
+    );
+    assert_eq!(records.len(), 2);
+    assert!(records[1].distance.unwrap().is_nan());
+    let records =
+        MatchRecords::compare_geographic(&[source], &[far], &MatchPolicy::default(), 100.0);
+    assert_eq!(records[0].match_status, MatchStatus::Displaced);
+    assert_eq!(records.filter("displaced").len(), 1);
+    Ok(())
+}
+
+#[test]
+fn address_matrix() -> anyhow::Result<()> {
+    let city_path = "data/addresses.data";
+    let city_addresses = SpatialAddresses::load(city_path)?;
+    let city = city_addresses[0..30].to_vec();
+    let county = city_addresses[10..40].to_vec();
+    let mut dispatch = city_addresses[0..20].to_vec();
+    *dispatch[0].status_mut() = AddressStatus::Current;
+    let sources = [
+        ("city", &city[..]),
+        ("county", &county[..]),
+        ("dispatch", &dispatch[..]),
+    ];
+    let records = MatrixRecords::compare(&sources, &MatchPolicy::default());
+    assert_eq!(records.sources, vec!["city", "county", "dispatch"]);
+    let first = records.iter().find(|r| r.label == city[0].label()).unwrap();
+    assert_eq!(first.presence[0], Presence::Present);
+    assert_eq!(first.presence[2], Presence::Divergent);
+    assert_eq!(
+        first.out_of_step(&records.sources),
+        vec!["county", "dispatch"]
+    );
+    let complete = records.clone().filter("complete");
+    assert!(complete.iter().all(|r| r.count(Presence::Present) == 3));
+    let path = std::env::temp_dir().join("destination_address_matrix.csv");
+    let mut written = records.clone();
+    written.to_csv(&path)?;
+    let read = MatrixRecords::from_csv(&path)?;
+    assert_eq!(read.sources, records.sources);
+    assert_eq!(read.len(), records.len());
+    assert_eq!(read[0].presence, records[0].presence);
+    Ok(())
+}
+
+#[test]
+fn diff_snapshots() -> anyhow::Result<()> {
+    let city_path = "data/addresses.data";
+    let city_addresses = SpatialAddresses::load(city_path)?;
+    let mut old = GeoAddresses::from(&city_addresses[0..30]);
+    for (i, address) in old.iter_mut().enumerate() {
+        address.id = Some(format!("A{i}"));
+    }
+    let mut new = GeoAddresses::from(&city_addresses[5..35]);
+    for (i, address) in new.iter_mut().enumerate() {
+        address.id = Some(format!("A{}", i + 5));
+    }
+    *new[0].address.zip_mut() = 97000;
+    new[1].latitude += 0.001;
+    let records = DiffRecords::compare_geographic(&old, &new, 50.0);
+    assert_eq!(records.count(DiffStatus::Added), 5);
+    assert_eq!(records.count(DiffStatus::Removed), 5);
+    assert_eq!(records.count(DiffStatus::Modified), 2);
+    let zip = records.clone().filter("zip");
+    assert_eq!(zip.len(), 1);
+    assert_eq!(zip[0].key, "A5");
+    assert!(!zip[0].moved);
+    let moved = records.clone().filter("moved");
+    assert_eq!(moved.len(), 1);
+    assert_eq!(moved[0].key, "A6");
+    assert!(moved[0].distance > Some(100.0));
+    // Without a native identifier, records are paired on the label.
+    let records = DiffRecords::compare(&city_addresses[0..30], &city_addresses[5..35]);
+    assert_eq!(records.count(DiffStatus::Modified), 0);
+    assert_eq!(records.len(), 10);
+
+    // A business point that moved without an address edit is reported as moved.
+    let businesses = Businesses::from_raw_csv("data/business_points.csv")?;
+    let old = businesses[0..10].to_vec();
+    let mut new = old.clone();
+    let (x, y) = (
+        new[0].x_coordinate().unwrap(),
+        new[0].y_coordinate().unwrap(),
+    );
+    let address = new[0].address();
+    new[0].relocate(address, Some((x + 100.0, y)));
+    assert_eq!(DiffRecords::compare_businesses(&old, &new).len(), 0);
+    let records = DiffRecords::compare_businesses_cartesian(&old, &new, 50.0);
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].key, old[0].license());
+    assert!(records[0].moved);
+    assert!(records[0].changes.is_none());
+
+    // Inspections of different businesses at the same address are paired separately.
+    let path = std::env::temp_dir().join("destination_diff_inspections.csv");
+    std::fs::write(
+        &path,
+        "Name,Address,Class,Subclass\nFIRST,307 SW G ST,,\nSECOND,307 SW G ST,,\n",
+    )?;
+    let old = FireInspections::from_csv(&path)?;
+    let mut new = old.clone();
+    new.remove(0);
+    let records = DiffRecords::compare_inspections(&old, &new);
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].status, DiffStatus::Removed);
+    assert_eq!(records[0].key, "FIRST: 307 SW G ST");
+    Ok(())
+}
+
+#[test]
+fn conflate_addresses() -> anyhow::Result<()> {
+    let city_path = "data/addresses.data";
+    let city_addresses = SpatialAddresses::load(city_path)?;
+    let city = city_addresses[0..20].to_vec();
+    let mut county = city_addresses[10..30].to_vec();
+    *county[0].zip_mut() = 97000;
+    county[0].latitude += 0.001;
+    let (lon, lat) = (city[10].longitude, city[10].latitude);
+    let square = |offset: f64, size: f64| {
+        Boundary::new(vec![
+            BoundaryPoint {
+                longitude: lon + offset - size,
+                latitude: lat - size,
+            },
+            BoundaryPoint {
+                longitude: lon + offset + size,
+                latitude: lat - size,
+            },
+            BoundaryPoint {
+                longitude: lon + offset + size,
+                latitude: lat + size,
+            },
+            BoundaryPoint {
+                longitude: lon + offset - size,
+                latitude: lat + size,
+            },
+        ])
+    };
+    assert!(square(0.0, 0.0001).contains(&city[10]));
+    assert!(!square(0.0, 0.0001).contains(&county[0]));
+    let precedence = PrecedencePolicy::new()
+        .with_rule("street", Region::Anywhere, &["city", "county"])
+        .with_rule("zip", Region::Anywhere, &["county", "city"])
+        .with_rule("coordinates", Region::Outside, &["county", "city"]);
+    let path = std::env::temp_dir().join("destination_precedence.csv");
+    let path = path.to_str().unwrap();
+    precedence.clone().to_csv(path)?;
+    assert_eq!(PrecedencePolicy::from_csv(path)?, precedence);
+    // A misspelled field fails the load rather than never applying.
+    std::fs::write(
+        path,
+        "field,region,precedence
+zip_code,anywhere,county;city
+",
+    )?;
+    assert!(PrecedencePolicy::from_csv(path).is_err());
+    // A precedence naming a collection outside the conflation is rejected.
+    assert!(precedence.validate(&["city", "county"]).is_ok());
+    assert!(precedence.validate(&["city", "state"]).is_err());
+    let sources = [("city", &city[..]), ("county", &county[..])];
+    let policy = MatchPolicy::loose();
+    // Outside the boundary, the county supplies the coordinates.
+    let outside = precedence.clone().with_boundary(square(1.0, 0.01));
+    let conflation = Conflation::merge(&sources, &outside, &policy);
+    assert_eq!(conflation.addresses.len(), 30);
+    assert_eq!(conflation.provenance.len(), 30 * 17);
+    assert_eq!(conflation.addresses[10].zip(), 97000);
+    assert_eq!(conflation.addresses[10].latitude, county[0].latitude);
+    let conflicts = conflation.provenance.clone().filter("conflict");
+    assert_eq!(conflicts.len(), 2);
+    assert!(
+        conflicts
+            .iter()
+            .all(|r| r.index == 10 && r.source == "county" && r.sources == "city;county")
+    );
+    assert!(
+        conflation
+            .provenance
+            .iter()
+            .filter(|r| r.field == "street_name")
+            .all(|r| r.source == "city" || r.sources == "county")
+    );
+    // Inside the boundary, the collection order applies and the city supplies the coordinates.
+    let inside = precedence.with_boundary(square(0.0, 0.01));
+    let conflation = Conflation::merge(&sources, &inside, &policy);
+    assert_eq!(conflation.addresses[10].latitude, city[10].latitude);
+    assert_eq!(conflation.addresses[10].zip(), 97000);
+    Ok(())
+}
+
+#[test]
+fn native_corrections() -> anyhow::Result<()> {
+    use strum::IntoEnumIterator;
+    let city_path = "data/addresses.data";
+    let city_addresses = SpatialAddresses::load(city_path)?;
+    let mut source = city_addresses[0..10].to_vec();
+    let mut target = Vec::new();
+    for (i, address) in source.iter().enumerate() {
+        let mut county = JosephineCountySpatialAddress2024 {
+            object_id: Some(i as i64 + 100),
+            lat: address.latitude,
+            lon: address.longitude,
+            ..Default::default()
+        };
+        for component in AddressComponent::iter() {
+            component.assign(address, &mut county);
+        }
+        target.push(county);
+    }
+    *target[2].status_mut() = AddressStatus::Pending;
+    *target[3].floor_mut() = Some(2);
+    *target[5].status_mut() = AddressStatus::Retired;
+    *source[5].building_mut() = Some("B".to_string());
+    let corrections = Corrections::compare(&source, &target, &MatchPolicy::default());
+    assert_eq!(corrections.len(), 3);
+    assert_eq!(corrections.count(AddressComponent::Status), 2);
+    assert_eq!(corrections.count(AddressComponent::Floor), 1);
+    for correction in corrections.iter() {
+        let expected = &source[correction.index];
+        assert_eq!(
+            correction.record.object_id,
+            target[correction.index].object_id
+        );
+        assert_eq!(correction.record.status(), expected.status());
+        assert_eq!(correction.record.floor(), expected.floor());
+    }
+    // The county schema has no building field, so the building is reported as skipped.
+    let skipped = corrections.iter().find(|c| c.index == 5).unwrap();
+    assert_eq!(skipped.skipped, vec![AddressComponent::Building]);
+    assert_eq!(skipped.record.number_suffix(), target[5].number_suffix());
+    // Each corrected component is reported with its old and new value.
+    let mut changes = corrections.changes();
+    assert_eq!(changes.len(), 3);
+    let floor = changes
+        .iter()
+        .find(|c| c.component == AddressComponent::Floor)
+        .unwrap();
+    assert_eq!(floor.index, 3);
+    assert_eq!(floor.old, "2");
+    assert_eq!(
+        floor.new,
+        source[3].floor().map_or(String::new(), |f| f.to_string())
+    );
+    let path = std::env::temp_dir().join("destination_correction_changes.csv");
+    changes.to_csv(&path)?;
+    assert_eq!(CorrectionRecords::from_csv(&path)?, changes);
+    Ok(())
+}
+
+#[test]
+fn match_progress() -> anyhow::Result<()> {
+    let city_path = "data/addresses.data";
+    let city_addresses = SpatialAddresses::load(city_path)?;
+    let source = city_addresses[0..20].to_vec();
+    let mut previous = source.clone();
+    *previous[0].zip_mut() = 97000;
+    *previous[2].status_mut() = AddressStatus::Pending;
+    *previous[8].status_mut() = AddressStatus::Pending;
+    *previous[9].status_mut() = AddressStatus::Pending;
+    *previous[19].status_mut() = AddressStatus::Pending;
+    let mut current = source.clone();
+    *current[5].status_mut() = AddressStatus::Pending;
+    *current[7].zip_mut() = 97000;
+    *current[8].status_mut() = AddressStatus::Pending;
+    *current[9].zip_mut() = 97000;
+    let previous = MatchRecords::compare(&source, &previous);
+    // The last address is dropped from the current run, so it is removed rather than resolved.
+    let current = MatchRecords::compare(&source[0..19], &current);
+    let records = ProgressRecords::compare_matches(&previous, &current);
+    let label = |i: usize| source[i].label();
+    let keys = |status: &str| {
+        records
+            .clone()
+            .filter(status)
+            .iter()
+            .map(|r| r.key.clone())
+            .collect::<Vec<String>>()
+    };
+    let mut resolved = keys("resolved");
+    resolved.sort();
+    let mut expected = vec![label(0), label(2)];
+    expected.sort();
+    assert_eq!(resolved, expected);
+    assert_eq!(keys("still_open"), vec![label(8)]);
+    assert_eq!(keys("changed_category"), vec![label(9)]);
+    assert_eq!(keys("removed"), vec![label(19)]);
+    let summary = records.summary();
+    assert_eq!(summary.previous_open, 5);
+    assert_eq!(summary.current_open, 4);
+    assert_eq!(summary.resolved, 2);
+    assert_eq!(summary.removed, 1);
+    assert_eq!(summary.newly_broken, 2);
+    assert_eq!(summary.unchanged, 13);
+    Ok(())
+}
+
+#[test]
+fn import_rejections() -> anyhow::Result<()> {
+    let file_path = std::env::temp_dir().join("destination_fire_rejections.csv");
+    let mut wtr = csv::WriterBuilder::new()
+        .flexible(true)
+        .from_path(&file_path)?;
+    wtr.write_record(["Name", "Address", "Class", "Subclass"])?;
+    wtr.write_record(["First", "1855 NE STEPHENS ST", "B", "Office"])?;
+    wtr.write_record(["Short", "1855 NE STEPHENS ST, SUITE 2"])?;
+    wtr.write_record(["Second", "701 NW DIMMICK ST", "M", ""])?;
+    wtr.write_record(["Unnumbered", "NW DIMMICK ST", "M", ""])?;
+    wtr.flush()?;
+    // The strict loader used to abort on the first bad row.
+    let (fire, mut rejections) = FireInspections::from_csv_with_rejections(&file_path)?;
+    assert_eq!(fire.len(), 3);
+    assert_eq!(fire[1].name(), "Second");
+    assert_eq!(rejections.len(), 1);
+    assert_eq!(rejections[0].row, 3);
+    assert_eq!(rejections[0].raw, "Short,\"1855 NE STEPHENS ST, SUITE 2\"");
+    let output = std::env::temp_dir().join("destination_fire_rejections_report.csv");
+    rejections.to_csv(&output)?;
+    assert_eq!(Rejections::from_csv(&output)?, rejections);
+
+    // Conversion errors are collected alongside deserialize errors.
+    #[derive(serde::Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct Inspection {
+        address: String,
+    }
+    let (numbered, rejections) =
+        from_csv_with(&file_path, |sample: Inspection| {
+            match Parse::address(&sample.address) {
+                Ok((_, address)) if address.address_number().is_some() => Ok(address),
+                _ => Err(format!("no address number in {}", sample.address)),
+            }
+        })?;
+    assert_eq!(numbered.len(), 2);
+    assert_eq!(rejections.len(), 2);
+    assert_eq!(rejections[1].row, 5);
+    assert_eq!(rejections[1].error, "no address number in NW DIMMICK ST");
+    Ok(())
+}
+
+#[test]
+fn locate_records() -> anyhow::Result<()> {
+    let addresses = SpatialAddresses::load("data/addresses.data")?;
+    let address = &addresses[0];
+    let file_path = std::env::temp_dir().join("destination_permits.csv");
+    let mut wtr = csv::Writer::from_path(&file_path)?;
+    wtr.write_record(["permit", "site_address", "number", "street", "valuation"])?;
+    let street = address.complete_street_name(true);
+    let number = address.number().to_string();
+    wtr.write_record(["P-1", &address.label(), &number, &street, "1000"])?;
+    wtr.write_record(["P-2", "99999 NOWHERE ST", "99999", "NOWHERE ST", "2000"])?;
+    wtr.write_record(["P-3", "", "", "", "3000"])?;
+    wtr.flush()?;
+
+    let columns = AddressColumns::from_spec("site_address")?;
+    let attributes = vec!["permit".to_string(), "valuation".to_string()];
+    let (records, rejections) =
+        LocatableRecords::from_csv_with_rejections(&file_path, &columns, &attributes)?;
+    assert_eq!(records.len(), 2);
+    assert_eq!(rejections.len(), 1);
+    assert_eq!(rejections[0].row, 4);
+    let mut located = LocatableMatchRecords::compare(&records, &addresses);
+    assert_eq!(located[0].attributes, vec!["P-1", "1000"]);
+    assert_eq!(located[0].status, MatchStatus::Matching);
+    assert_eq!(located[0].longitude, Some(address.longitude()));
+    located.filter("missing");
+    assert_eq!(located.len(), 1);
+    assert_eq!(located[0].attributes, vec!["P-2", "2000"]);
+
+    // Component columns locate the same address, passing through every column by default.
+    let columns = AddressColumns::from_spec("street_name=street;number=number")?;
+    let (records, _) = LocatableRecords::from_csv_with_rejections(&file_path, &columns, &[])?;
+    assert_eq!(records.columns.len(), 5);
+    let located = LocatableMatchRecords::compare(&records, &addresses);
+    assert_eq!(located[0].status, MatchStatus::Matching);
+    let output = std::env::temp_dir().join("destination_permits_located.csv");
+    located.to_csv(&output)?;
+    let mut rdr = csv::Reader::from_path(&output)?;
+    assert_eq!(rdr.headers()?.len(), 10);
+    assert_eq!(&rdr.records().next().unwrap()?[5], "Matching");
+    // Passthrough columns named like an output column are prefixed, keeping the headers unique.
+    let mut clash = located.clone();
+    clash.columns[0] = "status".to_string();
+    clash.columns[4] = "latitude".to_string();
+    clash.to_csv(&output)?;
+    let mut rdr = csv::Reader::from_path(&output)?;
+    let headers = rdr.headers()?.clone();
+    assert_eq!(&headers[0], "source_status");
+    assert_eq!(&headers[4], "source_latitude");
+    assert_eq!(
+        headers
+            .iter()
+            .collect::<std::collections::HashSet<&str>>()
+            .len(),
+        10
+    );
+    // A prefixed name already used by another passthrough column is prefixed again.
+    clash.columns[1] = "source_status".to_string();
+    clash.columns[2] = "source_source_status".to_string();
+    clash.to_csv(&output)?;
+    let mut rdr = csv::Reader::from_path(&output)?;
+    let headers = rdr.headers()?.clone();
+    assert_eq!(&headers[0], "source_source_source_status");
+    assert_eq!(
+        headers
+            .iter()
+            .collect::<std::collections::HashSet<&str>>()
+            .len(),
+        10
+    );
+    assert!(AddressColumns::from_spec("floor=level").is_err());
+    Ok(())
+}
+
+#[test]
+fn join_by_address() -> anyhow::Result<()> {
+    let addresses = SpatialAddresses::load("data/addresses.data")?;
+    let file_path = std::env::temp_dir().join("destination_join_inspections.csv");
+    let mut wtr = csv::Writer::from_path(&file_path)?;
+    wtr.write_record(["Name", "Address", "Class", "Subclass"])?;
+    wtr.write_record(["First", &addresses[0].label(), "B", ""])?;
+    wtr.write_record(["Second", &addresses[1].label(), "M", ""])?;
+    wtr.write_record(["Third", "99999 NOWHERE ST", "M", ""])?;
+    wtr.flush()?;
+    let inspections = FireInspections::from_csv(&file_path)?;
+    let policy = MatchPolicy::partial();
+
+    let inner = JoinPairs::join(&inspections, &addresses, JoinKind::Inner, &policy);
+    assert_eq!(inner.len(), 2);
+    assert!(
+        inner
+            .iter()
+            .all(|pair| pair.status == MatchStatus::Matching)
+    );
+    assert_eq!(inner[0].right_index, Some(0));
+    assert_eq!(
+        inner[1].right.map(|r| r.label()),
+        Some(addresses[1].label())
+    );
+
+    let left = JoinPairs::join(&inspections, &addresses, JoinKind::Left, &policy);
+    assert_eq!(left.len(), 3);
+    assert_eq!(left.clone().filter("missing").len(), 1);
+
+    // Addresses with no fire inspection, joining full addresses to partial addresses.
+    let sample = &addresses[..10];
+    let anti = JoinPairs::join(sample, &inspections, JoinKind::Anti, &policy);
+    assert_eq!(anti.len(), 8);
+    assert!(anti.iter().all(|pair| pair.right.is_none()));
+    assert!(
+        !anti
+            .lefts()
+            .iter()
+            .any(|a| a.label() == addresses[0].label())
+    );
+    assert_eq!("anti".parse::<JoinKind>()?, JoinKind::Anti);
+    Ok(())
+}
+
+#[test]
+fn license_dates() -> anyhow::Result<()> {
+    let (licenses, rejections) =
+        BusinessLicenses::from_csv_with_rejections("data/business_licenses_20250317.csv")?;
+    // Rejected rows lack a postal code, not a date.
+    assert!(rejections.iter().all(|r| r.error.contains("field 18")));
+    assert!(licenses.iter().all(|license| license.expires().is_some()));
+    let first = &licenses[0];
+    assert_eq!(first.opened(), NaiveDate::from_ymd_opt(1986, 4, 1));
+    assert_eq!(first.issued(), NaiveDate::from_ymd_opt(2024, 7, 11));
+    assert_eq!(first.expires(), NaiveDate::from_ymd_opt(2025, 7, 31));
+
+    let date = NaiveDate::from_ymd_opt(2025, 3, 17).unwrap();
+    let expired = licenses.clone().filter_expired(date);
+    assert_eq!(expired.len(), 93);
+    let expiring = licenses.clone().filter_expiring(date, 30);
+    assert_eq!(expiring.len(), 70);
+    assert!(expiring.iter().all(|license| !license.is_expired(date)));
+    let opened = licenses.clone().filter_opened(
+        NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+        NaiveDate::from_ymd_opt(2024, 12, 31).unwrap(),
+    );
+    assert_eq!(opened.len(), 29);
+
+    // Match records carry the dates through a round trip to csv.
+    let addresses = SpatialAddresses::load("data/addresses.data")?;
+    let mut records = BusinessMatchRecords::compare(&expiring, &addresses);
+    let output = std::env::temp_dir().join("destination_expiring_licenses.csv");
+    records.to_csv(&output)?;
+    let records = BusinessMatchRecords::from_csv(&output)?;
+    assert_eq!(
+        records.clone().filter_expiring(date, 30).len(),
+        records.len()
+    );
+    assert!(records.clone().filter_expired(date).is_empty());
+    Ok(())
+}
+
+#[test]
+fn license_malformed_date() -> anyhow::Result<()> {
+    let export = std::fs::read_to_string("data/business_licenses_20250317.csv")?;
+    let mut lines = export.lines();
+    let header = lines.next().unwrap();
+    let row = lines.next().unwrap();
+    // The expiration date is the last column.
+    let (fields, _) = row.rsplit_once(',').unwrap();
+    let path = std::env::temp_dir().join("destination_malformed_date.csv");
+    std::fs::write(
+        &path,
+        format!("{header}\n{row}\n{fields},2025-31-07 14:04:36.000\n{fields},\n"),
+    )?;
+    let (licenses, rejections) = BusinessLicenses::from_csv_with_rejections(&path)?;
+    // A malformed date rejects the row, while an empty date reads as missing.
+    assert_eq!(licenses.len(), 2);
+    assert_eq!(licenses[1].expires(), None);
+    assert_eq!(rejections.len(), 1);
+    assert_eq!(rejections[0].row, 3);
+    assert!(rejections[0].error.contains("invalid date"));
+    Ok(())
+}
+
+#[test]
+fn license_jurisdiction() -> anyhow::Result<()> {
+    let licenses = BusinessLicenses::from_csv("data/business_licenses_20250317.csv")?;
+    let claimed = |jurisdiction| {
+        licenses
+            .iter()
+            .filter(|license| license.location() == Some(jurisdiction))
+            .count()
+    };
+    assert_eq!(claimed(Jurisdiction::Inside), 2251);
+    assert_eq!(claimed(Jurisdiction::Outside), 169);
+
+    // Addresses in the city address set lie inside city limits.
+    let date = NaiveDate::from_ymd_opt(2025, 3, 17).unwrap();
+    let licenses = licenses.filter_expiring(date, 30);
+    let addresses = SpatialAddresses::load("data/addresses.data")?;
+    let mut records = BusinessMatchRecords::compare(&licenses, &addresses);
+    records.check_addresses(&addresses);
+    // Three licenses conflict, but one of them matches seventeen units of the same building, so
+    // the filter keeps twenty records.
+    assert_eq!(records.jurisdiction_conflicts(), 3);
+    let conflicts = records.clone().filter("jurisdiction");
+    assert_eq!(conflicts.len(), 20);
+    assert!(conflicts.iter().all(|r| {
+        r.location() == Some(Jurisdiction::Outside)
+            && r.jurisdiction() == Some(Jurisdiction::Inside)
+    }));
+
+    // Every matching address lies outside a boundary far from the city.
+    let far = |longitude: f64, latitude: f64| BoundaryPoint {
+        longitude,
+        latitude,
+    };
+    let boundary = Boundary::new(vec![far(0.0, 0.0), far(1.0, 0.0), far(1.0, 1.0)]);
+    records.check_boundary(&boundary);
+    assert!(records.iter().all(|r| {
+        r.other_address_label().is_none() || r.jurisdiction() == Some(Jurisdiction::Outside)
+    }));
+
+    // The jurisdiction survives a round trip to csv.
+    let output = std::env::temp_dir().join("destination_license_jurisdiction.csv");
+    records.to_csv(&output)?;
+    let read = BusinessMatchRecords::from_csv(&output)?;
+    assert_eq!(
+        read.clone().filter("jurisdiction").len(),
+        records.clone().filter("jurisdiction").len()
+    );
+    Ok(())
+}
+
+#[test]
+fn naics_summary() -> anyhow::Result<()> {
+    let restaurant = Naics::lookup(722511).unwrap();
+    assert_eq!(restaurant.sector_code, 72);
+    assert_eq!(restaurant.sector_name, "Accommodation and Food Services");
+    assert_eq!(restaurant.subsector_code, 7225);
+    assert_eq!(
+        restaurant.subsector_name,
+        Some("Restaurants and Other Eating Places")
+    );
+    // Sectors spanning several codes share the canonical code of the first.
+    let store = Naics::lookup(452319).unwrap();
+    assert_eq!(store.sector_code, 45);
+    assert_eq!(store.sector(), 44);
+    assert_eq!(Naics::lookup(332710).unwrap().sector(), 31);
+    assert_eq!(Naics::lookup(493110).unwrap().sector(), 48);
+    assert_eq!(Naics::lookup(10), None);
+    assert_eq!(Naics::lookup(101010), None);
+
+    // Every license in the export has a recognized industry code.
+    let licenses = BusinessLicenses::from_csv("data/business_licenses_20250317.csv")?;
+    assert!(licenses.iter().all(|license| {
+        license
+            .naics()
+            .is_some_and(|naics| naics.subsector_name.is_some())
+    }));
+    // The hierarchy agrees with the codes in the business layer.
+    let businesses = Businesses::from_raw_csv("data/business_points.csv")?;
+    assert!(businesses.iter().all(|business| {
+        Naics::lookup(business.industry_code() as i64).is_some_and(|naics| {
+            naics.sector_code == business.sector_code()
+                && naics.subsector_code == business.subsector_code()
+        })
+    }));
+
+    let date = NaiveDate::from_ymd_opt(2025, 3, 17).unwrap();
+    let licenses = licenses.filter_expiring(date, 30);
+    let addresses = SpatialAddresses::load("data/addresses.data")?;
+    let records = BusinessMatchRecords::compare(&licenses, &addresses);
+    let sectors = NaicsSummaries::from_matches(&records, NaicsLevel::Sector, &HashMap::new());
+    let subsectors = NaicsSummaries::from_matches(&records, NaicsLevel::Subsector, &HashMap::new());
+    // A license may list several locations, but counts once in its group.
+    let unique = licenses
+        .iter()
+        .map(|license| license.license())
+        .collect::<std::collections::HashSet<String>>()
+        .len();
+    let count = |summaries: &NaicsSummaries| summaries.iter().map(|s| s.licenses).sum::<usize>();
+    assert_eq!(count(&sectors), unique);
+    assert_eq!(count(&subsectors), unique);
+    assert!(sectors.len() < subsectors.len());
+    assert!(
+        sectors
+            .iter()
+            .all(|s| s.subsector_code.is_none() && s.matched <= s.licenses)
+    );
+    // Each sector appears once, under its canonical code.
+    let names = sectors
+        .iter()
+        .map(|s| s.sector_name.clone())
+        .collect::<std::collections::HashSet<Option<String>>>();
+    assert_eq!(names.len(), sectors.len());
+    assert!(sectors.iter().all(|s| {
+        s.sector_code
+            .is_none_or(|code| ![32, 33, 45, 49].contains(&code))
+    }));
+    // Divergent addresses count as matched locations.
+    let located = records
+        .iter()
+        .filter(|r| r.match_status() != MatchStatus::Missing)
+        .map(|r| r.license())
+        .collect::<std::collections::HashSet<String>>()
+        .len();
+    assert_eq!(sectors.iter().map(|s| s.matched).sum::<usize>(), located);
+    assert!(
+        businesses
+            .districts()
+            .values()
+            .all(|district| !district.is_empty())
+    );
+    // The layer predates the export, so assign a district to a single license.
+    let districts = HashMap::from([(records[0].license(), "NORTH".to_string())]);
+    let mut by_district = NaicsSummaries::from_matches(&records, NaicsLevel::Sector, &districts);
+    assert_eq!(count(&by_district), unique);
+    assert_eq!(
+        by_district
+            .iter()
+            .filter(|s| s.district.is_some())
+            .map(|s| s.licenses)
+            .sum::<usize>(),
+        1
+    );
+
+    let output = std::env::temp_dir().join("destination_naics_summary.csv");
+    by_district.to_csv(&output)?;
+    assert_eq!(NaicsSummaries::from_csv(&output)?, by_district);
+    Ok(())
+}
+
+#[test]
+fn sync_business_layer() -> anyhow::Result<()> {
+    let addresses = SpatialAddresses::load("data/addresses.data")?;
+    let businesses = Businesses::from_raw_csv("data/business_points.csv")?;
+    // The layer coordinates are in Web Mercator.
+    let first = &businesses[0];
+    let label = first.address().label();
+    let point = addresses.iter().find(|a| a.label() == label).unwrap();
+    let (x, y) = point.web_mercator();
+    assert!((x - first.x_coordinate().unwrap()).abs() < 0.01);
+    assert!((y - first.y_coordinate().unwrap()).abs() < 0.01);
+
+    let date = NaiveDate::from_ymd_opt(2025, 3, 17).unwrap();
+    let licenses = BusinessLicenses::from_csv("data/business_licenses_20250317.csv")?
+        .filter_expiring(date, 30)
+        .deduplicate();
+    let count = licenses.len();
+    // Publish all but the last two licenses, plus a business with a license since closed.
+    let mut layer = licenses[..count - 2]
+        .iter()
+        .filter_map(|license| Business::from_license(license, &businesses, None))
+        .collect::<Vec<Business>>();
+    assert_eq!(layer.len(), count - 2);
+    layer.push(first.clone());
+    let (_, moved) = Parse::address("1 NOWHERE WAY")?;
+    // A business that fails to geocode keeps its previous location.
+    let mut kept = first.clone();
+    assert!(!kept.relocate(moved.clone(), None));
+    assert_eq!(kept.x_coordinate(), first.x_coordinate());
+    assert_eq!(kept.address().label(), "1 NOWHERE WAY");
+    layer[0].relocate(moved, None);
+    layer[1].rename(&licenses[count - 1]);
+
+    let BusinessSync {
+        mut records,
+        layer: updated,
+    } = BusinessSync::compare(&layer, &licenses, &addresses, &MatchPolicy::partial());
+    assert_eq!(records.len(), count + 1);
+    assert_eq!(records.count(SyncStatus::New), 2);
+    assert_eq!(records.count(SyncStatus::Closed), 1);
+    assert_eq!(records.count(SyncStatus::Moved), 1);
+    assert_eq!(records.count(SyncStatus::Renamed), 1);
+    assert_eq!(records.count(SyncStatus::Unchanged), count - 4);
+    let closed = records.clone().filter("closed");
+    assert_eq!(closed[0].license, first.license());
+    let moved = records.clone().filter("moved");
+    assert_eq!(
+        moved[0].previous_address_label.as_deref(),
+        Some("1 NOWHERE WAY")
+    );
+    let renamed = records.clone().filter("renamed");
+    assert_eq!(renamed[0].company_name, licenses[1].company_name().unwrap());
+    // Matching locations are geocoded near the city.
+    assert!(records.iter().all(|r| {
+        (r.match_status == Some(MatchStatus::Matching)) == r.x_coordinate.is_some()
+            && r.x_coordinate
+                .is_none_or(|x| (x - first.x_coordinate().unwrap()).abs() < 20_000.0)
+    }));
+
+    // The updated layer retires the closed license and adds the new licenses.
+    assert_eq!(updated.len(), count);
+    assert!(updated.iter().all(|b| b.license() != first.license()));
+    assert_eq!(
+        updated[0].address().label(),
+        licenses[0].partial_address().label()
+    );
+    assert_eq!(
+        updated[1].company_name(),
+        licenses[1].company_name().unwrap()
+    );
+    let output = std::env::temp_dir().join("destination_business_layer.csv");
+    updated.to_raw_csv(&output)?;
+    assert_eq!(Businesses::from_raw_csv(&output)?.len(), count);
+    // Geocoded coordinates survive a binary round trip.
+    let binary = std::env::temp_dir().join("destination_business_layer.data");
+    updated.save(&binary)?;
+    let loaded = Businesses::load(&binary)?;
+    assert!(loaded.iter().any(|b| b.x_coordinate().is_some()));
+    assert_eq!(loaded, updated);
+    let report = std::env::temp_dir().join("destination_business_sync.csv");
+    records.to_csv(&report)?;
+    assert_eq!(BusinessSyncRecords::from_csv(&report)?, records);
+
+    // A license with an industry code out of range for the layer is not added.
+    let export = std::env::temp_dir().join("destination_business_industry_code.csv");
+    let source = std::fs::read_to_string("data/business_licenses_20250317.csv")?;
+    let mut lines = source.lines();
+    let header = lines.next().unwrap();
+    let row = lines
+        .next()
+        .unwrap()
+        .replacen(",811122,", ",9999999999,", 1);
+    std::fs::write(&export, format!("{header}\n{row}\n"))?;
+    let invalid = BusinessLicenses::from_csv(&export)?;
+    assert_eq!(invalid[0].industry_code(), 9_999_999_999);
+    assert!(Business::from_license(&invalid[0], &businesses, None).is_none());
+    let sync = BusinessSync::compare(&[], &invalid, &addresses, &MatchPolicy::partial());
+    assert_eq!(sync.records.count(SyncStatus::New), 1);
+    assert!(sync.layer.is_empty());
+    Ok(())
+}
+
+#[test]
+fn license_partial_address() -> anyhow::Result<()> {
+    let licenses = BusinessLicenses::from_csv("data/business_licenses_20250317.csv")?;
+    let partial = |license: &str| licenses.clone().filter("license", license)[0].partial_address();
+
+    // The post directional stands in for the missing pre-directional.
+    let address = partial("BTAX-037571-07-2024");
+    assert_eq!(address.address_number, Some(550));
+    assert_eq!(
+        address.street_name_pre_directional,
+        Some(StreetNamePreDirectional::SOUTHEAST)
+    );
+    assert_eq!(address.street_name, Some("HAWTHORNE".to_string()));
+    assert_eq!(
+        address.street_name_post_type,
+        Some(StreetNamePostType::AVENUE)
+    );
+    assert_eq!(address.subaddress_identifier, Some("200".to_string()));
+
+    // Street names holding a pre-type are parsed into components.
+    let address = partial("BTAX-040023-03-2025");
+    assert_eq!(address.pre_type, Some(StreetNamePreType::Highway));
+    assert_eq!(address.street_name, Some("62".to_string()));
+
+    // Suffixes entered with the number and subaddress types entered with the unit.
+    assert_eq!(
+        partial("BTAX-037706-07-2024").address_number_suffix,
+        Some("B".to_string())
+    );
+    let address = partial("BTAX-037690-07-2024");
+    assert_eq!(address.subaddress_type, Some(SubaddressType::Suite));
+
+    // Matching runs through the partial address matcher.
+    let addresses = SpatialAddresses::load("data/addresses.data")?;
+    let records = BusinessMatchRecords::compare(&licenses, &addresses);
+    let matching = records
+        .iter()
+        .filter(|r| r.match_status() == MatchStatus::Matching)
+        .map(|r| r.license())
+        .collect::<std::collections::HashSet<String>>();
+    assert_eq!(matching.len(), 1511);
+    let address = licenses.clone().filter("license", "BTAX-037690-07-2024")[0].clone();
+    let record = addresses
+        .iter()
+        .find_map(|other| address.coincident(other))
+        .unwrap();
+    assert_eq!(
+        record.business_address_label(),
+        address.partial_address().label()
+    );
+
+    // Licenses join by address through the same partial address, to full and partial addresses.
+    let policy = MatchPolicy::partial();
+    let sample = [address.clone()];
+    let joined = JoinPairs::join(&sample, &addresses, JoinKind::Inner, &policy);
+    assert!(!joined.is_empty());
+    assert!(joined.iter().all(|pair| {
+        pair.right.map(|r| r.number()) == address.partial_address().address_number
+    }));
+    let file_path = std::env::temp_dir().join("destination_join_licenses.csv");
+    let mut wtr = csv::Writer::from_path(&file_path)?;
+    wtr.write_record(["Name", "Address", "Class", "Subclass"])?;
+    wtr.write_record(["Licensed", &address.partial_address().label(), "B", ""])?;
+    wtr.write_record(["Unlicensed", "99999 NOWHERE ST", "M", ""])?;
+    wtr.flush()?;
+    let inspections = FireInspections::from_csv(&file_path)?;
+    let joined = JoinPairs::join(&inspections, &sample, JoinKind::Left, &policy);
+    assert_eq!(joined.len(), 2);
+    assert_eq!(joined[0].status, MatchStatus::Matching);
+    assert_eq!(joined[1].status, MatchStatus::Missing);
+    Ok(())
+}